@@ -38,3 +38,5 @@ COMMENT ON COLUMN saved_addresses.user_fingerprint IS 'SHA256 hash of user SSH p
 -- DROP POLICY IF EXISTS "Allow delete addresses" ON saved_addresses;
 -- CREATE POLICY "Allow delete addresses" ON saved_addresses FOR DELETE USING (true);
 
+-- DROP POLICY IF EXISTS "Allow update addresses" ON saved_addresses;
+-- CREATE POLICY "Allow update addresses" ON saved_addresses FOR UPDATE USING (true);
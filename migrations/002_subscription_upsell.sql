@@ -0,0 +1,7 @@
+-- Supabase migration: Subscribe-and-save upsell
+-- One-time products with a non-zero discount can be converted to a subscription at checkout
+
+ALTER TABLE products
+ADD COLUMN IF NOT EXISTS subscription_discount_pct INTEGER NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN products.subscription_discount_pct IS 'Percent off when a one-time product is bought as a subscription (0 = not offered)';
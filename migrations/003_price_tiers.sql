@@ -0,0 +1,7 @@
+-- Supabase migration: Quantity-based tiered pricing
+-- Each tier is a [min_qty, price_cents] pair; an empty array means flat pricing
+
+ALTER TABLE products
+ADD COLUMN IF NOT EXISTS price_tiers JSONB NOT NULL DEFAULT '[]'::jsonb;
+
+COMMENT ON COLUMN products.price_tiers IS 'Per-unit price breaks as [[min_qty, price_cents], ...]';
@@ -0,0 +1,12 @@
+-- Supabase migration: Per-region product descriptions
+-- Products carry localized descriptions keyed by locale or region id;
+-- regions name the locale their content should use
+
+ALTER TABLE products
+ADD COLUMN IF NOT EXISTS translations JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+ALTER TABLE regions
+ADD COLUMN IF NOT EXISTS locale TEXT;
+
+COMMENT ON COLUMN products.translations IS 'Localized descriptions, e.g. {"uz": "...", "ru": "..."}';
+COMMENT ON COLUMN regions.locale IS 'Locale used to pick localized product content (NULL = default)';
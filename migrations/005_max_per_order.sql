@@ -0,0 +1,7 @@
+-- Supabase migration: Per-product order limits
+-- Some products (limited roasts, samplers) cap how many one customer can buy
+
+ALTER TABLE products
+ADD COLUMN IF NOT EXISTS max_per_order INTEGER CHECK (max_per_order > 0);
+
+COMMENT ON COLUMN products.max_per_order IS 'Maximum quantity per order (NULL = no limit)';
@@ -0,0 +1,9 @@
+-- Supabase migration: Product ratings
+-- Average review score and review count shown in the product details
+
+ALTER TABLE products
+ADD COLUMN IF NOT EXISTS rating REAL CHECK (rating BETWEEN 0 AND 5),
+ADD COLUMN IF NOT EXISTS review_count INTEGER CHECK (review_count >= 0);
+
+COMMENT ON COLUMN products.rating IS 'Average review score out of 5 (NULL = no reviews yet)';
+COMMENT ON COLUMN products.review_count IS 'Number of reviews behind the rating';
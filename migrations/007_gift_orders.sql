@@ -0,0 +1,11 @@
+-- Supabase migration: Gift orders
+-- A gift order ships to the recipient's address and is billed to the purchaser
+
+ALTER TABLE orders
+ADD COLUMN IF NOT EXISTS is_gift BOOLEAN NOT NULL DEFAULT FALSE,
+ADD COLUMN IF NOT EXISTS purchaser_name TEXT,
+ADD COLUMN IF NOT EXISTS purchaser_email TEXT,
+ADD COLUMN IF NOT EXISTS gift_message TEXT;
+
+COMMENT ON COLUMN orders.purchaser_name IS 'Who paid for a gift order (the shipping name is the recipient)';
+COMMENT ON COLUMN orders.gift_message IS 'Optional note included with a gift order';
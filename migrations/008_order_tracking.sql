@@ -0,0 +1,9 @@
+-- Supabase migration: Order tracking
+-- Shipped orders carry the carrier's tracking number
+
+ALTER TABLE orders
+ADD COLUMN IF NOT EXISTS tracking_number TEXT,
+ADD COLUMN IF NOT EXISTS carrier TEXT;
+
+COMMENT ON COLUMN orders.tracking_number IS 'Carrier tracking number, set once the order ships';
+COMMENT ON COLUMN orders.carrier IS 'Shipping carrier name (e.g. UPS, DHL)';
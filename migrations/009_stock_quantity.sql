@@ -0,0 +1,7 @@
+-- Supabase migration: Per-product stock counts
+-- Tracks how many units are left so carts can't take more than exist
+
+ALTER TABLE products
+ADD COLUMN IF NOT EXISTS stock_quantity INTEGER CHECK (stock_quantity >= 0);
+
+COMMENT ON COLUMN products.stock_quantity IS 'Units left to sell (NULL = not tracked, unlimited)';
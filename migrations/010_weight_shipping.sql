@@ -0,0 +1,7 @@
+-- Supabase migration: Weight-based shipping per region
+-- Regions without a tier keep the flat rate below the free-shipping threshold
+
+ALTER TABLE regions
+ADD COLUMN IF NOT EXISTS weight_shipping JSONB;
+
+COMMENT ON COLUMN regions.weight_shipping IS 'Shipping as {"base_cents", "included_oz", "per_oz_cents"} (NULL = flat rate)';
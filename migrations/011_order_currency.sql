@@ -0,0 +1,7 @@
+-- Supabase migration: Currency per order
+-- Past orders keep showing what was charged after the customer switches region
+
+ALTER TABLE orders
+ADD COLUMN IF NOT EXISTS currency TEXT NOT NULL DEFAULT 'USD';
+
+COMMENT ON COLUMN orders.currency IS 'ISO currency code the order amounts are in, from the region it was placed in';
@@ -1,7 +1,71 @@
-use crate::db::{DataCache, SshIdentity, SupabaseClient};
-use crate::models::{Cart, Order, PaymentInfo, Product, Region, SavedAddress, ShippingAddress, Subscription};
-use anyhow::Result;
-use std::time::Instant;
+use crate::clipboard::{self, CopyOutcome};
+use crate::db::{DataCache, Database, LocalStore, SshIdentity, SupabaseClient};
+use crate::i18n::t;
+use crate::models::{
+    Cart, GiftDetails, Order, OrderLine, OrderStatus, PaymentInfo, PersistedCart, Product, ProductCategory, ProductType, Region, RoastLevel,
+    SavedAddress, Settings, ShippingAddress, Subscription, SubscriptionStatus,
+};
+use crate::ui::Theme;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use ratatui::layout::{Position, Rect};
+use tokio::sync::mpsc;
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// How long a deleted address can be restored with `u`
+const ADDRESS_UNDO_WINDOW: Duration = Duration::from_secs(8);
+
+/// Local store file holding the last version the user saw "what's new" for
+const LAST_SEEN_VERSION_FILE: &str = "last_seen_version";
+
+/// Startup data load: attempts before giving up, and the first backoff delay (doubles each retry)
+const STARTUP_LOAD_ATTEMPTS: u32 = 3;
+const STARTUP_RETRY_BASE: Duration = Duration::from_millis(500);
+
+/// How long the splash shows before moving on (override with ANORA_SPLASH_SECS, 0 skips it)
+const DEFAULT_SPLASH_SECS: u64 = 5;
+
+/// Local store file holding the user's preferences
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Cart file from before carts were kept per SSH identity; read once, then replaced
+const LEGACY_CART_FILE: &str = "cart.json";
+/// Saved carts older than this are re-validated against the catalog
+/// (override with ANORA_CART_MAX_AGE_DAYS)
+const DEFAULT_CART_MAX_AGE_DAYS: i64 = 7;
+
+/// Only nudge towards free shipping when the cart is at most this far below the threshold
+const FREE_SHIPPING_NUDGE_GAP_CENTS: i32 = 1500;
+
+/// How long the footer shows "free shipping unlocked" after the cart crosses the threshold
+const FREE_SHIPPING_UNLOCKED_DURATION: Duration = Duration::from_secs(3);
+
+/// Most of one product the shop's quantity stepper goes up to
+pub const MAX_PRODUCT_QUANTITY: i32 = 99;
+
+/// Saved addresses kept per user
+const MAX_SAVED_ADDRESSES: usize = 3;
+
+/// How long the post-order confetti plays
+pub const CELEBRATION_DURATION: Duration = Duration::from_secs(1);
+
+/// Repeat r presses within this window are ignored, so holding r can't queue a fetch per key repeat
+const REGION_CYCLE_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Tabs remembered for going back
+const TAB_HISTORY_LIMIT: usize = 10;
+
+/// Orders fetched per page in the order history
+const ORDERS_PAGE_SIZE: usize = 20;
+
+/// How long a footer notification stays up
+const NOTIFICATION_DURATION: Duration = Duration::from_secs(3);
+
+/// Notifications shown at once; the oldest drops off first
+const MAX_NOTIFICATIONS: usize = 3;
 
 /// Main application tabs
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -13,6 +77,47 @@ pub enum Tab {
     Cart,
 }
 
+/// How the shop list is split into sections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShopGrouping {
+    #[default]
+    Category,
+    Roast,
+}
+
+impl ShopGrouping {
+    /// Section headings, in display order
+    pub fn headings(&self) -> Vec<String> {
+        match self {
+            Self::Category => ProductCategory::ALL.iter().map(|c| c.to_string()).collect(),
+            Self::Roast => RoastLevel::ALL
+                .iter()
+                .map(|r| format!("~ {} ~", r))
+                .chain(std::iter::once("~ other ~".to_string()))
+                .collect(),
+        }
+    }
+
+    /// Index into `headings()` of the section a product is listed under
+    /// Products without a roast level go under "other"
+    pub fn section_of(&self, product: &Product) -> usize {
+        match self {
+            Self::Category => ProductCategory::ALL.iter().position(|c| *c == product.category),
+            Self::Roast => product.roast_level.and_then(|r| RoastLevel::ALL.iter().position(|l| *l == r)),
+        }
+        .unwrap_or(self.headings().len() - 1)
+    }
+}
+
+/// Result of a fetch that ran in the background, for the main loop to apply
+#[derive(Debug)]
+pub enum DataUpdate {
+    Products {
+        region_id: String,
+        result: Result<Vec<Product>, String>,
+    },
+}
+
 /// Account section tabs
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AccountSection {
@@ -21,6 +126,8 @@ pub enum AccountSection {
     Subscriptions,
     Faq,
     About,
+    WhatsNew,
+    Settings,
 }
 
 /// Checkout flow steps
@@ -29,6 +136,8 @@ pub enum CheckoutStep {
     #[default]
     Cart,
     Shipping,
+    // Gift orders only
+    Gift,
     Payment,
     Confirmation,
 }
@@ -67,9 +176,10 @@ pub enum InputField {
     PaymentName,
     PaymentEmail,
     CardNumber,
-    ExpiryMonth,
-    ExpiryYear,
+    Expiry,
     Cvv,
+    // Gift orders only
+    GiftMessage,
 }
 
 impl InputField {
@@ -92,8 +202,7 @@ impl InputField {
             Self::PaymentName,
             Self::PaymentEmail,
             Self::CardNumber,
-            Self::ExpiryMonth,
-            Self::ExpiryYear,
+            Self::Expiry,
             Self::Cvv,
         ]
     }
@@ -109,6 +218,37 @@ impl InputField {
         let current_idx = fields.iter().position(|f| f == self).unwrap_or(0);
         fields.get(current_idx + 1).copied().unwrap_or(fields[0])
     }
+
+    /// Maximum length (in characters) of a free-text field
+    /// Generous, but bounded so a stray paste can't break the form layout or the backend
+    pub fn max_len(&self) -> Option<usize> {
+        match self {
+            Self::Name | Self::PaymentName => Some(64),
+            Self::Street1 | Self::Street2 => Some(96),
+            Self::City | Self::State | Self::Country => Some(56),
+            Self::Phone => Some(24),
+            Self::PostalCode => Some(16),
+            Self::PaymentEmail => Some(254),
+            Self::GiftMessage => Some(200),
+            _ => None,
+        }
+    }
+
+    /// "42/64" counter once a free-text field is at 80% of its limit
+    pub fn length_counter(&self, len: usize) -> Option<String> {
+        self.max_len()
+            .filter(|max| len * 5 >= max * 4)
+            .map(|max| format!("{}/{}", len, max))
+    }
+
+    /// 1-based position of this field within its form, used as its jump key
+    pub fn jump_number(&self) -> Option<usize> {
+        Self::shipping_fields()
+            .iter()
+            .position(|f| f == self)
+            .or_else(|| Self::payment_fields().iter().position(|f| f == self))
+            .map(|i| i + 1)
+    }
 }
 
 /// Loading state for async operations
@@ -120,21 +260,121 @@ pub enum LoadingState {
     Error,
 }
 
+/// Time source for render-path animations (cursor blink)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnimationClock {
+    #[default]
+    System,
+    /// Pinned time since the epoch, so tests render a stable frame
+    #[allow(dead_code)]
+    Fixed(Duration),
+}
+
+impl AnimationClock {
+    pub fn since_epoch(&self) -> Duration {
+        match self {
+            AnimationClock::System => SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default(),
+            AnimationClock::Fixed(at) => *at,
+        }
+    }
+
+    /// Animation frame counter advancing every `period_ms`
+    pub fn tick(&self, period_ms: u128) -> usize {
+        (self.since_epoch().as_millis() / period_ms) as usize
+    }
+
+    /// Cursor blink state, toggling every 500ms
+    pub fn blink_on(&self) -> bool {
+        self.tick(500).is_multiple_of(2)
+    }
+}
+
+/// Notice key: no SSH key was found, so the identity is tied to this machine
+pub const NOTICE_FALLBACK_IDENTITY: &str = "fallback_identity";
+/// Notice key: SUPABASE_URL isn't set, so nothing can load
+pub const NOTICE_SUPABASE_NOT_CONFIGURED: &str = "supabase_not_configured";
+
+/// How a footer notification is colored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Info,
+    Success,
+}
+
+/// A queued footer notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub message: String,
+    pub severity: Severity,
+    /// Animation-clock time it disappears; `None` stays until dismissed
+    pub expires_at: Option<Duration>,
+}
+
+/// A one-time warning: shown until the user dismisses it, then never again
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notice {
+    /// Remembered in settings once dismissed
+    pub key: &'static str,
+    pub message: String,
+}
+
+/// Clickable screen areas recorded while drawing the last frame
+#[derive(Debug, Default)]
+pub struct ClickTargets {
+    pub tabs: Vec<(Tab, Rect)>,
+    /// Shop list rows, by index into `App::products`
+    pub products: Vec<(usize, Rect)>,
+}
+
+impl ClickTargets {
+    pub fn clear(&mut self) {
+        self.tabs.clear();
+        self.products.clear();
+    }
+
+    fn tab_at(&self, position: Position) -> Option<Tab> {
+        self.tabs.iter().find(|(_, rect)| rect.contains(position)).map(|(tab, _)| *tab)
+    }
+
+    fn product_at(&self, position: Position) -> Option<usize> {
+        self.products.iter().find(|(_, rect)| rect.contains(position)).map(|(index, _)| *index)
+    }
+}
+
 /// Main application state
 pub struct App {
     pub running: bool,
     pub current_tab: Tab,
+    // Tabs visited before the current one, most recent last, for b to go back
+    pub tab_history: Vec<Tab>,
     pub region: Region,
     pub regions: Vec<Region>,
     pub products: Vec<Product>,
     pub cart: Cart,
     pub orders: Vec<Order>,
+    pub orders_exhausted: bool,
     pub subscriptions: Vec<Subscription>,
 
     // UI state
+    pub subscription_index: usize,
     pub selected_product_index: usize,
     pub product_quantity: i32,
+    /// Digits typed in the shop for a quantity, applied on Enter
+    pub quantity_input: String,
+    /// Whether the selected product's full description is shown
+    pub description_expanded: bool,
+    /// Shop filter query, and whether it's being typed
+    pub shop_filter: String,
+    pub shop_filter_active: bool,
+    pub shop_grouping: ShopGrouping,
+    /// Favorite product ids (saved per identity), and whether the shop shows only them
+    pub favorites: HashSet<uuid::Uuid>,
+    pub favorites_only: bool,
     pub account_section: AccountSection,
+    pub order_scroll: usize,
+    /// Showing the order at `order_scroll` in full instead of the history list
+    pub show_order_detail: bool,
     pub checkout_step: CheckoutStep,
     pub cart_item_index: usize,
     pub payment_option_index: usize,
@@ -145,47 +385,191 @@ pub struct App {
     pub saved_addresses: Vec<SavedAddress>,
     pub shipping_mode: ShippingMode,
     pub address_select_index: usize,
+    pub undo_deleted_address: Option<SavedAddress>,
+    pub undo_deadline: Option<Instant>,
+    // Saved address loaded into the form with e; saving updates it instead of adding one
+    pub editing_address_id: Option<uuid::Uuid>,
     pub payment_info: PaymentInfo,
+    // Gift order: shipping address is the recipient's, payment contact is the purchaser's
+    pub gift_order: bool,
+    pub gift_message: String,
     pub active_input: InputField,
-
-    // Notification message (for errors)
-    pub notification: Option<String>,
-
-    // Loading state
+    /// Caret within `input_cursor_field`; any other field edits at its end
+    input_cursor: usize,
+    input_cursor_field: InputField,
+    /// Field-jump mode: the next digit selects a form field
+    pub field_jump: bool,
+
+    // Order snapshot shown on the confirmation step
+    pub pending_order: Option<Order>,
+    // First enter pressed on the confirmation step, waiting for the second
+    pub order_confirm_armed: bool,
+    // Order just created, shown on the thank-you screen until the next key
+    pub placed_order: Option<Order>,
+    // Confirmation email preview overlay (read-only)
+    pub show_email_preview: bool,
+    pub email_preview_scroll: u16,
+    // Read-only cart overlay, available from any tab
+    pub show_cart_peek: bool,
+    /// Keyboard help overlay, toggled with ?
+    pub show_help: bool,
+    // Where the last frame drew tabs and product rows, for mouse hit-testing
+    // (written while rendering, which only borrows the app immutably)
+    pub click_targets: RefCell<ClickTargets>,
+
+    // Mask sensitive fields (card, cvv, phone, email) for screen-sharing
+    pub privacy_mode: bool,
+
+    // Footer notifications, oldest first; each expires on its own
+    pub notifications: VecDeque<Notification>,
+    // One-time warning, shown below notifications until dismissed with D
+    pub notice: Option<Notice>,
+    // Cart lines not sold in the current region, awaiting keep/remove
+    pub region_conflicts: Vec<uuid::Uuid>,
+    // When r last switched region; presses right after it are dropped
+    pub last_region_cycle: Option<Instant>,
+    // Waiting for the user to confirm wiping local data
+    pub confirm_reset: bool,
+    // Waiting for the user to confirm cancelling the selected subscription
+    pub confirm_cancel_subscription: bool,
+    // q pressed with items in the cart; a second q quits
+    pub confirm_quit: bool,
+    // Cart line (by product) that `-` at quantity 1 asked to remove; a second `-` does
+    pub confirm_remove: Option<uuid::Uuid>,
+
+    // Loading state; a background product fetch shows a spinner but leaves keys working
     pub loading: LoadingState,
+    // Startup load in progress, the only load that holds keys back
+    pub initial_load_pending: bool,
+    // Startup load retries
+    pub load_attempts: u32,
+    pub next_load_retry: Option<Instant>,
+    pub load_failed: bool,
+    // Regions couldn't be fetched, so only the default region is on offer
+    pub regions_failed: bool,
+    // Region code from `--region=<code>`, picked once regions have loaded
+    pub preferred_region: Option<String>,
 
     // Splash screen state
     pub show_splash: bool,
     pub splash_start: Instant,
+    pub splash_duration: Duration,
+    pub clock: AnimationClock,
+
+    // "What's new" overlay, shown once after a version bump
+    pub show_whats_new: bool,
+
+    // Post-order confetti: animation-clock time it started
+    pub celebration_start: Option<Duration>,
+
+    // Whether the cart qualified for free shipping last frame, and when it last started to
+    pub free_shipping_qualified: bool,
+    pub free_shipping_unlocked_at: Option<Duration>,
 
     // User identity (SSH key fingerprint)
     pub identity: SshIdentity,
+    // How the identity is worked out again after a local data reset
+    resolve_identity: fn(&LocalStore) -> SshIdentity,
 
     // Database client and cache
-    pub db: SupabaseClient,
+    pub db: Arc<dyn Database>,
+    // Results of background fetches, drained by `apply_updates` each tick
+    updates_tx: mpsc::UnboundedSender<DataUpdate>,
+    updates: mpsc::UnboundedReceiver<DataUpdate>,
     pub cache: DataCache,
+
+    // Local on-disk state (data dir)
+    pub store: LocalStore,
+    pub settings: Settings,
+    // Colors the UI is drawn with
+    pub theme: Theme,
+    // The cart as last written to the store, to save only on change
+    saved_cart_json: String,
+    // Age in days of a stale restored cart still waiting for a catalog to validate against
+    stale_cart_age_days: Option<i64>,
 }
 
 impl App {
     pub fn new() -> Self {
         let db = SupabaseClient::default();
-        let cache = DataCache::new();
-        let identity = SshIdentity::get_or_create();
+        let db_configured = db.is_configured();
+        let ssh_identity = SshIdentity::from_ssh_key();
+        let fallback_identity = ssh_identity.is_none();
+        let mut app = Self::with_deps(
+            Box::new(db),
+            ssh_identity.unwrap_or_else(SshIdentity::fallback_identity),
+            DataCache::new(),
+            LocalStore::new(),
+        );
+        app.show_whats_new = Self::version_changed(&app.store);
+        app.settings = Self::load_settings(&app.store);
+        app.theme = Theme::from_env();
+        if !db_configured {
+            app.notify_once(NOTICE_SUPABASE_NOT_CONFIGURED, "SUPABASE_URL isn't set — see env.example");
+        } else if fallback_identity {
+            app.notify_once(NOTICE_FALLBACK_IDENTITY, "no SSH key found — orders are tied to this machine");
+        }
+        app
+    }
+
+    /// Offline app for tests: no identity lookup, empty in-memory database, throwaway store
+    #[cfg(test)]
+    pub fn for_test() -> Self {
+        Self::for_test_with(crate::db::MockDatabase::default())
+    }
+
+    /// Offline app for tests backed by the given mock database
+    #[cfg(test)]
+    pub fn for_test_with(db: crate::db::MockDatabase) -> Self {
+        let fingerprint = "0".repeat(64);
+        let identity = SshIdentity {
+            short_id: fingerprint[..8].to_string(),
+            fingerprint,
+        };
+        let store = LocalStore::at(std::env::temp_dir().join(format!("anora-test-{}", uuid::Uuid::new_v4())));
+        let mut app = Self::with_deps(
+            Box::new(db),
+            identity,
+            DataCache::new(),
+            store,
+        );
+        app.show_splash = false;
+        app.clock = AnimationClock::Fixed(Duration::ZERO);
+        app.resolve_identity = SshIdentity::generated;
+        app
+    }
+
+    /// App state around injected dependencies; performs no disk or network I/O itself
+    /// `new()` is the production convenience that resolves the real ones
+    pub fn with_deps(db: Box<dyn Database>, identity: SshIdentity, cache: DataCache, store: LocalStore) -> Self {
         // Start with a default region, will be updated when regions are loaded
         let region = Region::default();
+        let (updates_tx, updates) = mpsc::unbounded_channel();
 
         Self {
             running: true,
             current_tab: Tab::Home,
+            tab_history: Vec::new(),
             region,
             regions: Vec::new(),
             products: Vec::new(),
             cart: Cart::new(),
             orders: Vec::new(),
+            orders_exhausted: false,
             subscriptions: Vec::new(),
+            subscription_index: 0,
             selected_product_index: 0,
+            description_expanded: false,
+            quantity_input: String::new(),
+            shop_filter: String::new(),
+            shop_grouping: ShopGrouping::default(),
+            favorites: HashSet::new(),
+            favorites_only: false,
+            shop_filter_active: false,
             product_quantity: 1,
             account_section: AccountSection::OrderHistory,
+            order_scroll: 0,
+            show_order_detail: false,
             checkout_step: CheckoutStep::Cart,
             cart_item_index: 0,
             payment_option_index: 0,
@@ -194,25 +578,201 @@ impl App {
             saved_addresses: Vec::new(),
             shipping_mode: ShippingMode::SelectAddress,
             address_select_index: 0,
+            undo_deleted_address: None,
+            undo_deadline: None,
+            editing_address_id: None,
             payment_info: PaymentInfo::default(),
+            gift_order: false,
+            gift_message: String::new(),
             active_input: InputField::None,
-            notification: None,
+            input_cursor: 0,
+            input_cursor_field: InputField::None,
+            field_jump: false,
+            pending_order: None,
+            placed_order: None,
+            order_confirm_armed: false,
+            show_email_preview: false,
+            email_preview_scroll: 0,
+            show_cart_peek: false,
+            show_help: false,
+            click_targets: RefCell::new(ClickTargets::default()),
+            privacy_mode: false,
+            notifications: VecDeque::new(),
+            notice: None,
+            region_conflicts: Vec::new(),
+            last_region_cycle: None,
+            confirm_reset: false,
+            confirm_cancel_subscription: false,
+            confirm_quit: false,
+            confirm_remove: None,
             loading: LoadingState::Idle,
+            load_attempts: 0,
+            initial_load_pending: false,
+            next_load_retry: None,
+            load_failed: false,
+            regions_failed: false,
+            preferred_region: None,
             show_splash: true,
             splash_start: Instant::now(),
+            splash_duration: Duration::from_secs(DEFAULT_SPLASH_SECS),
+            clock: AnimationClock::System,
+            show_whats_new: false,
+            celebration_start: None,
+            free_shipping_qualified: false,
+            free_shipping_unlocked_at: None,
             identity,
-            db,
+            resolve_identity: SshIdentity::resolve,
+            db: Arc::from(db),
+            updates_tx,
+            updates,
             cache,
+            store,
+            saved_cart_json: String::new(),
+            stale_cart_age_days: None,
+            settings: Settings::default(),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Whether the app was updated since the last run
+    /// Fresh installs just record the current version without showing the overlay
+    fn version_changed(store: &LocalStore) -> bool {
+        let current = env!("CARGO_PKG_VERSION");
+        match store.read(LAST_SEEN_VERSION_FILE) {
+            Some(seen) => seen.trim() != current,
+            None => {
+                let _ = store.write(LAST_SEEN_VERSION_FILE, current);
+                false
+            }
+        }
+    }
+
+    /// Saved preferences; a missing or unreadable file means the defaults
+    fn load_settings(store: &LocalStore) -> Settings {
+        store
+            .read(SETTINGS_FILE)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_settings(&self) {
+        if let Ok(json) = serde_json::to_string_pretty(&self.settings) {
+            let _ = self.store.write(SETTINGS_FILE, &json);
+        }
+    }
+
+    /// Show a one-time warning unless the user already dismissed it
+    pub fn notify_once(&mut self, key: &'static str, message: &str) {
+        if !self.settings.dismissed_notices.contains(key) {
+            self.notice = Some(Notice { key, message: message.to_string() });
+        }
+    }
+
+    /// Hide the current one-time warning for good
+    pub fn dismiss_notice(&mut self) {
+        if let Some(notice) = self.notice.take() {
+            self.settings.dismissed_notices.insert(notice.key.to_string());
+            self.save_settings();
         }
     }
 
-    /// Check if splash screen duration has elapsed (5 seconds)
+    /// Let every dismissed one-time warning show again
+    pub fn reset_dismissed_notices(&mut self) {
+        self.settings.dismissed_notices.clear();
+        self.save_settings();
+        self.notify(Severity::Info, "dismissed warnings will show again");
+    }
+
+    /// Toggle the second enter before an order is placed
+    pub fn toggle_confirm_before_order(&mut self) {
+        self.settings.confirm_before_order = !self.settings.confirm_before_order;
+        self.save_settings();
+    }
+
+    /// Toggle the confetti after an order is placed
+    pub fn toggle_celebrate_orders(&mut self) {
+        self.settings.celebrate_orders = !self.settings.celebrate_orders;
+        self.save_settings();
+    }
+
+    /// Dismiss the "what's new" overlay and remember the current version
+    pub fn dismiss_whats_new(&mut self) {
+        self.show_whats_new = false;
+        let _ = self.store.write(LAST_SEEN_VERSION_FILE, env!("CARGO_PKG_VERSION"));
+    }
+
+    /// Splash length from ANORA_SPLASH_SECS (0 skips it); `--no-splash` skips it whatever that says
+    pub fn configure_splash(&mut self, env_secs: Option<&str>, no_splash: bool) {
+        let secs = if no_splash {
+            0
+        } else {
+            env_secs.and_then(|v| v.trim().parse().ok()).unwrap_or(DEFAULT_SPLASH_SECS)
+        };
+        self.splash_duration = Duration::from_secs(secs);
+        self.show_splash = secs > 0;
+    }
+
+    /// Check if splash screen duration has elapsed (5 seconds unless configured)
     pub fn check_splash_timeout(&mut self) {
-        if self.show_splash && self.splash_start.elapsed().as_secs() >= 5 {
+        if self.show_splash && self.splash_start.elapsed() >= self.splash_duration {
             self.show_splash = false;
         }
     }
 
+    /// Send checkout back to the cart if the cart was emptied mid-checkout
+    pub fn check_empty_checkout(&mut self) {
+        if self.checkout_step != CheckoutStep::Cart && self.cart.is_empty() {
+            self.checkout_step = CheckoutStep::Cart;
+            self.shipping_mode = ShippingMode::SelectAddress;
+            self.payment_method = None;
+            self.pending_order = None;
+            self.order_confirm_armed = false;
+            self.show_email_preview = false;
+            self.active_input = InputField::None;
+            self.field_jump = false;
+            self.notify(Severity::Error, "your cart is empty");
+        }
+    }
+
+    /// Start the footer's "free shipping unlocked" moment when the cart crosses the threshold
+    pub fn check_free_shipping(&mut self) {
+        let qualified = self.qualifies_for_free_shipping();
+        if qualified && !self.free_shipping_qualified {
+            self.free_shipping_unlocked_at = Some(self.clock.since_epoch());
+        }
+        self.free_shipping_qualified = qualified;
+    }
+
+    /// Whether the "free shipping unlocked" message should still be showing
+    pub fn free_shipping_just_unlocked(&self) -> bool {
+        self.free_shipping_qualified
+            && self
+                .free_shipping_unlocked_at
+                .and_then(|start| self.clock.since_epoch().checked_sub(start))
+                .is_some_and(|elapsed| elapsed < FREE_SHIPPING_UNLOCKED_DURATION)
+    }
+
+    fn qualifies_for_free_shipping(&self) -> bool {
+        !self.cart.is_empty() && self.amount_to_free_shipping() == 0
+    }
+
+    /// Cents still to add before shipping is free in the current region
+    /// 0 once the cart qualifies, or when the region has no threshold
+    pub fn amount_to_free_shipping(&self) -> i32 {
+        let threshold_cents = self.region.free_shipping_threshold_cents();
+        if threshold_cents <= 0 {
+            return 0;
+        }
+        (threshold_cents - self.cart.subtotal_cents()).max(0)
+    }
+
+    /// Drop the undo buffer once its window has elapsed
+    pub fn check_undo_timeout(&mut self) {
+        if self.undo_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.clear_undo();
+        }
+    }
+
     /// Skip splash screen immediately
     pub fn skip_splash(&mut self) {
         self.show_splash = false;
@@ -233,6 +793,7 @@ impl App {
         self.loading = LoadingState::Loading;
         match self.db.get_regions().await {
             Ok(regions) if !regions.is_empty() => {
+                self.regions_failed = false;
                 self.cache.set_regions(regions.clone());
                 self.regions = regions;
                 if self.region.id.is_empty() && !self.regions.is_empty() {
@@ -242,13 +803,15 @@ impl App {
             }
             Ok(_) => {
                 // No regions in database, use a sensible default
+                self.regions_failed = false;
                 self.regions = vec![Region::default()];
                 self.region = Region::default();
                 self.loading = LoadingState::Idle;
             }
             Err(e) => {
                 self.loading = LoadingState::Error;
-                self.notification = Some(format!("Failed to load regions: {}", e));
+                self.notify(Severity::Error, format!("Failed to load regions: {}", e));
+                self.regions_failed = true;
                 // Use default region on error
                 self.regions = vec![Region::default()];
                 self.region = Region::default();
@@ -260,257 +823,1213 @@ impl App {
     /// Load products for the current region (with caching)
     pub async fn load_products(&mut self) -> Result<()> {
         // Check cache first
-        if let Some(products) = self.cache.get_products(&self.region.id) {
-            self.products = products;
+        if self.use_cached_products() {
             return Ok(());
         }
 
         // Fetch from Supabase
         self.loading = LoadingState::Loading;
-        match self.db.get_products(Some(&self.region.id)).await {
+        let result = self.db.get_products(Some(&self.region.id)).await;
+        self.apply_products(result.map_err(|e| e.to_string()));
+        Ok(())
+    }
+
+    /// Like `load_products`, but a cache miss doesn't wait for the network: the shop shows
+    /// as loading (and empty) while a background task fetches, and `apply_updates` takes
+    /// the result from a later tick
+    pub fn load_products_in_background(&mut self) {
+        if self.use_cached_products() {
+            self.loading = LoadingState::Idle;
+            return;
+        }
+
+        self.loading = LoadingState::Loading;
+        self.products = Vec::new();
+        self.clamp_selection();
+        let db = Arc::clone(&self.db);
+        let updates = self.updates_tx.clone();
+        let region_id = self.region.id.clone();
+        tokio::spawn(async move {
+            let result = db.get_products(Some(&region_id)).await.map_err(|e| e.to_string());
+            let _ = updates.send(DataUpdate::Products { region_id, result });
+        });
+    }
+
+    /// Take in whatever background fetches have finished since the last tick
+    pub fn apply_updates(&mut self) {
+        while let Ok(update) = self.updates.try_recv() {
+            match update {
+                // A result for a region we've already moved on from is dropped
+                DataUpdate::Products { region_id, result } if region_id == self.region.id => {
+                    self.apply_products(result);
+                }
+                DataUpdate::Products { .. } => {}
+            }
+        }
+    }
+
+    /// Show the current region's cached products, if there are any
+    fn use_cached_products(&mut self) -> bool {
+        let Some(products) = self.cache.get_products(&self.region.id) else {
+            return false;
+        };
+        self.products = products;
+        self.sort_products();
+        self.sync_cart_prices();
+        self.clamp_selection();
+        true
+    }
+
+    /// Show (and cache) fetched products for the current region, or the fetch error
+    fn apply_products(&mut self, result: Result<Vec<Product>, String>) {
+        match result {
             Ok(products) => {
                 self.cache.set_products(&self.region.id, products.clone());
                 self.products = products;
+                self.sort_products();
                 self.loading = LoadingState::Idle;
+                self.sync_cart_prices();
             }
             Err(e) => {
                 self.loading = LoadingState::Error;
-                self.notification = Some(format!("Failed to load products: {}", e));
+                self.notify(Severity::Error, format!("Failed to load products: {}", e));
                 self.products = Vec::new();
             }
         }
-        Ok(())
+        self.clamp_selection();
     }
 
-    /// Load saved addresses from Supabase
-    pub async fn load_saved_addresses(&mut self) -> Result<()> {
-        match self.db.get_saved_addresses(&self.identity.fingerprint).await {
-            Ok(addresses) => {
-                self.saved_addresses = addresses;
-            }
-            Err(_) => {
-                // Silently fail - addresses are optional
-                self.saved_addresses = Vec::new();
-            }
-        }
-        Ok(())
+    /// F5: drop the current region's cached products and fetch them again in the background
+    pub fn refresh_products(&mut self) {
+        self.cache.invalidate_products(&self.region.id);
+        self.load_products_in_background();
     }
 
-    /// Save current address to Supabase
-    pub async fn save_address_to_db(&mut self) -> Result<()> {
-        if !self.shipping_address.is_complete() || self.saved_addresses.len() >= 3 {
-            return Ok(());
+    /// Bring cart prices in line with the freshly loaded catalog, flagging lines it dropped
+    fn sync_cart_prices(&mut self) {
+        self.validate_stale_cart();
+        let changes = self.cart.refresh_prices(&self.products, &self.region.id);
+        if !changes.repriced.is_empty() {
+            self.notify(Severity::Info, format!("price changed for {}", changes.repriced.join(", ")));
         }
-
-        // Check if address already exists
-        let exists = self.saved_addresses.iter().any(|a| {
-            a.street_1 == self.shipping_address.street_1
-                && a.city == self.shipping_address.city
-                && a.postal_code == self.shipping_address.postal_code
-        });
-
-        if exists {
-            return Ok(());
+        if !changes.unavailable.is_empty() {
+            self.notify(Severity::Error, format!("no longer available: {}", changes.unavailable.join(", ")));
         }
+    }
 
-        let saved_address = SavedAddress::from_shipping(&self.shipping_address, &self.identity.fingerprint);
-        
-        match self.db.save_address(&saved_address).await {
-            Ok(created) => {
-                self.saved_addresses.insert(0, created);
-                // Keep only 3 addresses
-                if self.saved_addresses.len() > 3 {
-                    self.saved_addresses.truncate(3);
-                }
-            }
-            Err(_) => {
-                // Silently fail - continue with checkout
-            }
+    /// Prune and reprice a stale restored cart, once there's a catalog to check it against
+    fn validate_stale_cart(&mut self) {
+        if self.products.is_empty() {
+            return;
+        }
+        let Some(age_days) = self.stale_cart_age_days.take() else {
+            return;
+        };
+        let removed = self.cart.prune_unavailable(&self.products);
+        let repriced = self.cart.refresh_prices(&self.products, &self.region.id).repriced;
+        if removed > 0 || !repriced.is_empty() {
+            self.notify(Severity::Info, format!(
+                "your cart is {} days old — removed {} unavailable, repriced {}",
+                age_days,
+                removed,
+                repriced.len()
+            ));
         }
-        Ok(())
     }
 
-    /// Delete a saved address from Supabase
-    pub async fn delete_address_from_db(&mut self, index: usize) -> Result<()> {
-        if index >= self.saved_addresses.len() {
-            return Ok(());
+    /// Move to a tab, remembering the one we left so b can return to it
+    /// Leaving drops any pending address undo
+    pub fn switch_tab(&mut self, tab: Tab) {
+        if tab == self.current_tab {
+            return;
         }
-
-        let address = &self.saved_addresses[index];
-        if let Some(id) = address.id {
-            let _ = self.db.delete_address(&id).await;
+        self.clear_undo();
+        if self.tab_history.last() != Some(&self.current_tab) {
+            self.tab_history.push(self.current_tab);
         }
-        self.saved_addresses.remove(index);
-        
-        // Adjust selection index if needed
-        if self.address_select_index >= self.saved_addresses.len() + 1 && self.address_select_index > 0 {
-            self.address_select_index -= 1;
+        if self.tab_history.len() > TAB_HISTORY_LIMIT {
+            self.tab_history.remove(0);
         }
-        Ok(())
+        self.current_tab = tab;
     }
 
-    /// Initial data load (regions + products + saved addresses)
-    pub async fn load_initial_data(&mut self) -> Result<()> {
-        self.load_regions().await?;
-        self.load_products().await?;
-        self.load_saved_addresses().await?;
-        Ok(())
+    /// Return to the previously visited tab
+    pub fn go_back(&mut self) {
+        if let Some(tab) = self.tab_history.pop() {
+            self.clear_undo();
+            self.current_tab = tab;
+        }
     }
 
-    /// Change region and reload products
-    pub async fn change_region(&mut self, region: Region) {
-        self.region = region;
-        let _ = self.load_products().await;
-        self.selected_product_index = 0;
+    /// Open the Account tab, loading order history and subscriptions on first visit
+    pub async fn open_account(&mut self) {
+        self.switch_tab(Tab::Account);
+        self.load_orders().await;
+        self.load_subscriptions().await;
     }
 
-    /// Add current product to cart
-    pub fn add_to_cart(&mut self) {
-        if let Some(product) = self.products.get(self.selected_product_index) {
-            self.cart.add_item(product.clone(), self.product_quantity);
-            self.product_quantity = 1; // Reset quantity
+    /// Load the first page of order history for this SSH identity (with caching)
+    pub async fn load_orders(&mut self) {
+        let user_id = self.identity.user_id().to_string();
+        if let Some(orders) = self.cache.get_orders(&user_id) {
+            self.orders_exhausted = orders.len() < ORDERS_PAGE_SIZE;
+            self.orders = orders;
+            return;
         }
-    }
-
-    /// Process current input character
-    pub fn handle_input_char(&mut self, c: char) {
-        // Clear notification when user starts typing
-        self.notification = None;
 
-        match self.active_input {
-            InputField::None => {}
-            InputField::Name => self.shipping_address.name.push(c),
-            InputField::Street1 => self.shipping_address.street_1.push(c),
-            InputField::Street2 => self.shipping_address.street_2.push(c),
-            InputField::City => self.shipping_address.city.push(c),
-            InputField::State => self.shipping_address.state.push(c),
-            InputField::Country => self.shipping_address.country.push(c),
-            InputField::Phone => self.shipping_address.phone.push(c),
-            InputField::PostalCode => self.shipping_address.postal_code.push(c),
-            InputField::PaymentName => self.payment_info.name.push(c),
-            InputField::PaymentEmail => self.payment_info.email.push(c),
-            InputField::CardNumber => {
-                if c.is_ascii_digit() && self.payment_info.card_number.len() < 16 {
-                    self.payment_info.card_number.push(c);
-                }
-            }
-            InputField::ExpiryMonth => {
-                if c.is_ascii_digit() && self.payment_info.expiry_month.len() < 2 {
-                    self.payment_info.expiry_month.push(c);
-                }
-            }
-            InputField::ExpiryYear => {
-                if c.is_ascii_digit() && self.payment_info.expiry_year.len() < 4 {
-                    self.payment_info.expiry_year.push(c);
-                }
+        match self.db.get_orders(&user_id, ORDERS_PAGE_SIZE, 0).await {
+            Ok(orders) => {
+                self.orders_exhausted = orders.len() < ORDERS_PAGE_SIZE;
+                self.order_scroll = 0;
+                self.cache.set_orders(&user_id, orders.clone());
+                self.orders = orders;
             }
-            InputField::Cvv => {
-                if c.is_ascii_digit() && self.payment_info.cvv.len() < 3 {
-                    self.payment_info.cvv.push(c);
-                }
+            Err(e) => {
+                self.notify(Severity::Error, format!("Failed to load orders: {}", e));
             }
         }
     }
 
-    /// Handle backspace in input
-    pub fn handle_input_backspace(&mut self) {
-        match self.active_input {
-            InputField::None => {}
-            InputField::Name => {
-                self.shipping_address.name.pop();
-            }
-            InputField::Street1 => {
-                self.shipping_address.street_1.pop();
-            }
-            InputField::Street2 => {
-                self.shipping_address.street_2.pop();
-            }
-            InputField::City => {
-                self.shipping_address.city.pop();
-            }
-            InputField::State => {
-                self.shipping_address.state.pop();
-            }
-            InputField::Country => {
-                self.shipping_address.country.pop();
-            }
-            InputField::Phone => {
-                self.shipping_address.phone.pop();
-            }
-            InputField::PostalCode => {
-                self.shipping_address.postal_code.pop();
-            }
-            InputField::PaymentName => {
-                self.payment_info.name.pop();
-            }
-            InputField::PaymentEmail => {
-                self.payment_info.email.pop();
-            }
-            InputField::CardNumber => {
-                self.payment_info.card_number.pop();
-            }
-            InputField::ExpiryMonth => {
-                self.payment_info.expiry_month.pop();
-            }
-            InputField::ExpiryYear => {
-                self.payment_info.expiry_year.pop();
+    /// Load subscriptions for this SSH identity (with caching)
+    pub async fn load_subscriptions(&mut self) {
+        let user_id = self.identity.user_id().to_string();
+        if let Some(subscriptions) = self.cache.get_subscriptions(&user_id) {
+            self.subscriptions = subscriptions;
+            return;
+        }
+
+        match self.db.get_subscriptions(&user_id).await {
+            Ok(subscriptions) => {
+                self.cache.set_subscriptions(&user_id, subscriptions.clone());
+                self.subscriptions = subscriptions;
             }
-            InputField::Cvv => {
-                self.payment_info.cvv.pop();
+            Err(e) => {
+                self.notify(Severity::Error, format!("Failed to load subscriptions: {}", e));
             }
         }
     }
 
-    /// Move to next input field
-    pub fn next_input_field(&mut self) {
-        // Clear notification when navigating fields
-        self.notification = None;
-
-        match self.checkout_step {
-            CheckoutStep::Shipping => {
-                self.active_input = self.active_input.next_shipping();
+    /// Fetch the next page of order history (newest orders come first)
+    pub async fn load_more_orders(&mut self) {
+        if self.orders_exhausted {
+            return;
+        }
+        let user_id = self.identity.user_id().to_string();
+        match self.db.get_orders(&user_id, ORDERS_PAGE_SIZE, self.orders.len()).await {
+            Ok(page) => {
+                self.orders_exhausted = page.len() < ORDERS_PAGE_SIZE;
+                self.orders.extend(page);
+                self.cache.set_orders(&user_id, self.orders.clone());
             }
-            CheckoutStep::Payment if self.payment_method == Some(PaymentMethod::Ssh) => {
-                self.active_input = self.active_input.next_payment();
+            Err(e) => {
+                self.orders_exhausted = true;
+                self.notify(Severity::Error, format!("Failed to load orders: {}", e));
             }
-            _ => {}
         }
     }
 
-    /// Navigate products
-    pub fn next_product(&mut self) {
-        if !self.products.is_empty() {
-            self.selected_product_index = (self.selected_product_index + 1) % self.products.len();
-            self.product_quantity = 1;
+    /// Scroll the order history, fetching another page when nearing the end
+    pub async fn scroll_orders(&mut self, delta: isize) {
+        let max = self.orders.len().saturating_sub(1);
+        self.order_scroll = self.order_scroll.saturating_add_signed(delta).min(max);
+        if self.order_scroll + ORDERS_PAGE_SIZE / 2 >= self.orders.len() {
+            self.load_more_orders().await;
         }
     }
 
-    pub fn prev_product(&mut self) {
-        if !self.products.is_empty() {
-            self.selected_product_index = self
-                .selected_product_index
-                .checked_sub(1)
-                .unwrap_or(self.products.len() - 1);
-            self.product_quantity = 1;
-        }
+    /// The order at the top of the history list, shown by the detail view
+    pub fn selected_order(&self) -> Option<&Order> {
+        self.orders.get(self.order_scroll)
     }
 
-    /// Navigate account sections
-    pub fn next_account_section(&mut self) {
-        self.account_section = match self.account_section {
+    /// Open the detail view for the selected order
+    pub fn open_order_detail(&mut self) {
+        self.show_order_detail = self.selected_order().is_some();
+    }
+
+    /// Copy the selected order's tracking number to the clipboard (or save it to a file)
+    pub fn copy_tracking_number(&mut self) {
+        let Some(order) = self.selected_order() else {
+            return;
+        };
+        let Some(tracking) = order.tracking() else {
+            self.notify(Severity::Error, t("notice.no_tracking"));
+            return;
+        };
+        let file_name = format!("anora-tracking-{}.txt", order.short_id());
+        match clipboard::copy_or_save(tracking, &file_name) {
+            Ok(CopyOutcome::Clipboard) => self.notify(Severity::Success, t("notice.tracking_copied")),
+            Ok(CopyOutcome::File(path)) => {
+                self.notify(Severity::Success, t("notice.tracking_saved").replace("{path}", &path.display().to_string()))
+            }
+            Err(e) => self.notify(Severity::Error, format!("Failed to save tracking number: {}", e)),
+        }
+    }
+
+    /// Restore the cart saved by the previous session
+    /// Stale carts are re-validated: unavailable items are pruned and prices refreshed,
+    /// once products have loaded if they haven't yet
+    /// A missing or malformed file just means an empty cart
+    pub fn restore_cart(&mut self) {
+        let Some(saved) = self
+            .store
+            .read(&self.cart_file())
+            .or_else(|| self.store.read(LEGACY_CART_FILE))
+            .and_then(|json| serde_json::from_str::<PersistedCart>(&json).ok())
+        else {
+            return;
+        };
+
+        let max_age_days = std::env::var("ANORA_CART_MAX_AGE_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_CART_MAX_AGE_DAYS);
+        let stale = saved.is_stale(chrono::Duration::days(max_age_days));
+        let age_days = saved.age_days();
+
+        self.cart = saved.cart;
+        self.stale_cart_age_days = stale.then_some(age_days);
+        self.sync_cart_prices();
+        self.clamp_selection();
+        // A cart restored over the threshold isn't a new unlock
+        self.free_shipping_qualified = self.qualifies_for_free_shipping();
+        // Only rewrite the file once the cart actually changes, so a stale cart keeps its age
+        self.saved_cart_json = serde_json::to_string(&self.cart).unwrap_or_default();
+    }
+
+    /// Ask for confirmation before wiping local data
+    pub fn request_reset(&mut self) {
+        self.confirm_reset = true;
+        self.notify_until_dismissed(Severity::Info, t("reset.prompt"));
+    }
+
+    pub fn cancel_reset(&mut self) {
+        self.confirm_reset = false;
+        self.dismiss_notification(t("reset.prompt"));
+    }
+
+    /// Wipe the data dir and start over with a clean local state
+    /// Only the app's own files are removed, never the user's SSH keys
+    pub fn reset_local_data(&mut self) {
+        self.confirm_reset = false;
+        self.dismiss_notification(t("reset.prompt"));
+        self.cart = Cart::new();
+        self.stale_cart_age_days = None;
+        self.cache = DataCache::new();
+        self.favorites.clear();
+        self.favorites_only = false;
+        self.address_select_index = 0;
+        self.clear_undo();
+        self.region_conflicts.clear();
+        self.clamp_selection();
+        match self.store.clear() {
+            Ok(removed) if removed.is_empty() => self.notify(Severity::Info, t("notice.nothing_to_clear")),
+            Ok(removed) => self.notify(Severity::Success, t("notice.cleared").replace("{items}", &removed.join(", "))),
+            Err(e) => self.notify(Severity::Error, format!("couldn't clear local data: {}", e)),
+        }
+        // Nothing in memory may write the wiped preferences or generated id back
+        self.settings = Settings::default();
+        self.identity = (self.resolve_identity)(&self.store);
+    }
+
+    /// Local store file holding this identity's cart, so several SSH keys on one machine
+    /// don't share (or clobber) a cart
+    fn cart_file(&self) -> String {
+        format!("cart-{}.json", self.identity.short_id)
+    }
+
+    /// Local store file holding this identity's favorites
+    fn favorites_file(&self) -> String {
+        format!("favorites-{}.json", self.identity.short_id)
+    }
+
+    /// Load this identity's favorites (a missing or malformed file means none)
+    pub fn load_favorites(&mut self) {
+        self.favorites = self
+            .store
+            .read(&self.favorites_file())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+    }
+
+    fn save_favorites(&self) {
+        if self.favorites.is_empty() {
+            let _ = self.store.remove(&self.favorites_file());
+        } else if let Ok(json) = serde_json::to_string(&self.favorites) {
+            let _ = self.store.write(&self.favorites_file(), &json);
+        }
+    }
+
+    /// f: add or remove the selected product from favorites
+    pub fn toggle_favorite(&mut self) {
+        let Some(id) = self.selected_product().map(|p| p.id) else {
+            return;
+        };
+        if !self.favorites.remove(&id) {
+            self.favorites.insert(id);
+        }
+        self.save_favorites();
+        self.clamp_selection();
+    }
+
+    /// F: show only favorites in the shop, or everything again
+    pub fn toggle_favorites_only(&mut self) {
+        if !self.favorites_only && self.favorites.is_empty() {
+            self.notify(Severity::Info, t("notice.no_favorites"));
+            return;
+        }
+        self.favorites_only = !self.favorites_only;
+        self.clamp_selection();
+    }
+
+    /// Save the cart for the next session (or drop the saved one if empty)
+    pub fn persist_cart(&self) {
+        let _ = self.store.remove(LEGACY_CART_FILE);
+        if self.cart.is_empty() {
+            let _ = self.store.remove(&self.cart_file());
+        } else if let Ok(json) = serde_json::to_string(&PersistedCart::new(self.cart.clone())) {
+            let _ = self.store.write(&self.cart_file(), &json);
+        }
+    }
+
+    /// Save the cart whenever it's changed since the last save, so a crash or a closed
+    /// terminal doesn't lose it
+    pub fn check_cart_saved(&mut self) {
+        let Ok(json) = serde_json::to_string(&self.cart) else {
+            return;
+        };
+        if json != self.saved_cart_json {
+            self.persist_cart();
+            self.saved_cart_json = json;
+        }
+    }
+
+    /// Load saved addresses from Supabase
+    pub async fn load_saved_addresses(&mut self) -> Result<()> {
+        match self.db.get_saved_addresses(&self.identity.fingerprint).await {
+            Ok(addresses) => {
+                self.saved_addresses = addresses;
+            }
+            Err(_) => {
+                // Silently fail - addresses are optional
+                self.saved_addresses = Vec::new();
+            }
+        }
+        self.clamp_selection();
+        Ok(())
+    }
+
+    /// Save current address to Supabase
+    pub async fn save_address_to_db(&mut self) -> Result<()> {
+        if !self.shipping_address.is_complete() {
+            return Ok(());
+        }
+        if let Some(id) = self.editing_address_id.take() {
+            return self.update_address_in_db(id).await;
+        }
+        if self.saved_addresses.len() >= MAX_SAVED_ADDRESSES {
+            return Ok(());
+        }
+
+        // Check if address already exists, however it was typed
+        let exists = self
+            .saved_addresses
+            .iter()
+            .any(|a| a.to_shipping().is_same_place(&self.shipping_address));
+
+        if exists {
+            return Ok(());
+        }
+
+        let saved_address = SavedAddress::from_shipping(&self.shipping_address, &self.identity.fingerprint);
+        
+        match self.db.save_address(&saved_address).await {
+            Ok(created) => {
+                self.saved_addresses.insert(0, created);
+                self.notify(Severity::Success, "address saved");
+                // Keep only the newest few addresses
+                if self.saved_addresses.len() > MAX_SAVED_ADDRESSES {
+                    self.saved_addresses.truncate(MAX_SAVED_ADDRESSES);
+                }
+            }
+            Err(_) => {
+                // Silently fail - continue with checkout
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the form back over the saved address it was loaded from
+    async fn update_address_in_db(&mut self, id: uuid::Uuid) -> Result<()> {
+        let Some(index) = self.saved_addresses.iter().position(|a| a.id == Some(id)) else {
+            return Ok(());
+        };
+        let mut address = SavedAddress::from_shipping(&self.shipping_address, &self.identity.fingerprint);
+        address.id = Some(id);
+        address.created_at = self.saved_addresses[index].created_at;
+
+        match self.db.update_address(&address).await {
+            Ok(updated) => {
+                self.saved_addresses[index] = updated;
+                self.notify(Severity::Success, "address updated");
+            }
+            Err(_) => {
+                // Checkout continues with the edited address either way
+                self.notify(Severity::Error, "couldn't update the saved address");
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete a saved address from Supabase
+    pub async fn delete_address_from_db(&mut self, index: usize) -> Result<()> {
+        if index >= self.saved_addresses.len() {
+            return Ok(());
+        }
+
+        let address = &self.saved_addresses[index];
+        if let Some(id) = address.id {
+            let _ = self.db.delete_address(&id).await;
+        }
+        let removed = self.saved_addresses.remove(index);
+
+        // Keep the deleted address around briefly so it can be restored
+        self.undo_deleted_address = Some(removed);
+        self.undo_deadline = Some(Instant::now() + ADDRESS_UNDO_WINDOW);
+        self.notify_until_dismissed(Severity::Info, t("notice.address_deleted"));
+
+        self.clamp_selection();
+        Ok(())
+    }
+
+    /// Re-save the most recently deleted address, if still within the undo window
+    pub async fn undo_address_deletion(&mut self) {
+        self.check_undo_timeout();
+        let Some(mut address) = self.undo_deleted_address.take() else {
+            return;
+        };
+        self.undo_deadline = None;
+        self.dismiss_notification(t("notice.address_deleted"));
+
+        // Let Supabase assign a fresh id and timestamp
+        address.id = None;
+        address.created_at = None;
+
+        match self.db.save_address(&address).await {
+            Ok(created) => {
+                self.saved_addresses.insert(0, created);
+                self.saved_addresses.truncate(MAX_SAVED_ADDRESSES);
+                self.address_select_index = 0;
+            }
+            Err(_) => {
+                self.notify(Severity::Error, "couldn't restore address");
+            }
+        }
+    }
+
+    /// Forget the deleted address and its undo prompt
+    pub fn clear_undo(&mut self) {
+        if self.undo_deleted_address.take().is_some() {
+            self.undo_deadline = None;
+            self.dismiss_notification(t("notice.address_deleted"));
+        }
+    }
+
+    /// Initial data load (regions + products + saved addresses)
+    /// Returns an error if regions or products failed to load
+    pub async fn load_initial_data(&mut self) -> Result<()> {
+        self.load_regions().await?;
+        let regions_ok = self.loading != LoadingState::Error;
+        if regions_ok {
+            if let Some(code) = self.preferred_region.take() {
+                self.set_region_by_code(&code);
+            }
+        }
+        self.load_products().await?;
+        let products_ok = self.loading != LoadingState::Error;
+        self.load_saved_addresses().await?;
+
+        if regions_ok && products_ok {
+            Ok(())
+        } else {
+            Err(anyhow!("initial data load failed"))
+        }
+    }
+
+    /// Start in the region with this code (any case); unknown codes keep the default
+    pub fn set_region_by_code(&mut self, code: &str) {
+        match self.regions.iter().find(|r| r.code.eq_ignore_ascii_case(code.trim())) {
+            Some(region) => self.region = region.clone(),
+            None => self.notify(
+                Severity::Error,
+                t("notice.unknown_region").replace("{code}", code).replace("{name}", &self.region.name),
+            ),
+        }
+    }
+
+    /// First startup load; failures are retried with backoff by `check_load_retry`
+    pub async fn start_initial_load(&mut self) {
+        self.load_attempts = 0;
+        self.attempt_initial_load().await;
+    }
+
+    /// Retry a failed startup load once its backoff has elapsed
+    pub async fn check_load_retry(&mut self) {
+        if self.next_load_retry.is_some_and(|at| Instant::now() >= at) {
+            self.attempt_initial_load().await;
+        }
+    }
+
+    /// Manually retry the startup load (single attempt)
+    pub async fn retry_initial_load(&mut self) {
+        self.load_attempts = STARTUP_LOAD_ATTEMPTS - 1;
+        self.clear_notifications();
+        self.attempt_initial_load().await;
+    }
+
+    async fn attempt_initial_load(&mut self) {
+        self.load_attempts += 1;
+        self.next_load_retry = None;
+        self.initial_load_pending = true;
+        let result = self.load_initial_data().await;
+        self.initial_load_pending = false;
+        match result {
+            Ok(()) => {
+                self.load_failed = false;
+                self.dismiss_notification(t("notice.load_failed"));
+                self.dismiss_notification(t("notice.still_loading"));
+            }
+            Err(_) if self.load_attempts < STARTUP_LOAD_ATTEMPTS => {
+                let backoff = STARTUP_RETRY_BASE * 2u32.pow(self.load_attempts - 1);
+                self.next_load_retry = Some(Instant::now() + backoff);
+            }
+            Err(_) => {
+                self.load_failed = true;
+                self.notify_until_dismissed(Severity::Error, t("notice.load_failed"));
+            }
+        }
+    }
+
+    /// Whether startup data is still loading, or waiting to retry
+    /// Keys are ignored meanwhile so they can't act on half-loaded state
+    pub fn is_loading(&self) -> bool {
+        self.initial_load_pending || self.next_load_retry.is_some()
+    }
+
+    /// Whether products for the current region are still being fetched, at startup or in the background
+    pub fn is_fetching_products(&self) -> bool {
+        self.is_loading() || self.loading == LoadingState::Loading
+    }
+
+    /// Cue for a key ignored while loading
+    pub fn notify_loading(&mut self) {
+        self.notify(Severity::Info, t("notice.still_loading"));
+    }
+
+    /// Enter on the home screen: open the shop, or say why it can't be opened yet
+    pub async fn enter_shop_from_home(&mut self) {
+        if !self.products.is_empty() {
+            self.switch_tab(Tab::Shop);
+        } else if self.loading == LoadingState::Loading {
+            // A region switch is still fetching in the background
+            self.notify_loading();
+        } else if self.load_failed || self.loading == LoadingState::Error {
+            self.retry_initial_load().await;
+        } else {
+            self.notify(Severity::Error, t("notice.no_products").replace("{name}", &self.region.name));
+        }
+    }
+
+    /// Change region; its products load from the cache or in the background
+    pub fn change_region(&mut self, region: Region) {
+        self.region = region;
+        self.selected_product_index = 0;
+        self.on_selection_changed();
+        self.load_products_in_background();
+        self.check_region_conflicts();
+    }
+
+    /// Flag cart lines from another region so the user can keep or remove them
+    fn check_region_conflicts(&mut self) {
+        self.region_conflicts = self
+            .cart
+            .items
+            .iter()
+            .filter(|i| i.product.region_id != self.region.id)
+            .map(|i| i.product.id)
+            .collect();
+    }
+
+    /// How far into the post-order confetti we are, while it's playing
+    pub fn celebration_elapsed(&self) -> Option<Duration> {
+        let elapsed = self.clock.since_epoch().checked_sub(self.celebration_start?)?;
+        (elapsed < CELEBRATION_DURATION).then_some(elapsed)
+    }
+
+    /// Keep/remove prompt listing the cart lines not sold in the current region
+    pub fn region_conflict_prompt(&self) -> Option<String> {
+        if self.region_conflicts.is_empty() {
+            return None;
+        }
+        let names: Vec<_> = self
+            .cart
+            .items
+            .iter()
+            .filter(|i| self.region_conflicts.contains(&i.product.id))
+            .map(|i| i.product.name.as_str())
+            .collect();
+        Some(
+            t("notice.region_conflict")
+                .replace("{name}", &self.region.name)
+                .replace("{items}", &names.join(", ")),
+        )
+    }
+
+    /// Keep cart lines from another region
+    pub fn keep_region_conflicts(&mut self) {
+        self.region_conflicts.clear();
+    }
+
+    /// Remove cart lines that aren't sold in the current region
+    pub fn remove_region_conflicts(&mut self) {
+        for id in std::mem::take(&mut self.region_conflicts) {
+            self.cart.remove_item(id);
+        }
+        self.clamp_selection();
+    }
+
+    /// Clamp every selection index to its list
+    /// Call after anything that can shrink the products, cart, or saved addresses
+    pub fn clamp_selection(&mut self) {
+        let selected = self.selected_product_index;
+        self.selected_product_index = self.selected_product_index.min(self.products.len().saturating_sub(1));
+        // Keep the shop selection on a product the filter shows
+        if self.selected_product().is_none() {
+            if let Some(&first) = self.visible_product_indices().first() {
+                self.selected_product_index = first;
+            }
+        }
+        if self.selected_product_index != selected {
+            self.on_selection_changed();
+        }
+        self.cart_item_index = self.cart_item_index.min(self.cart.items.len().saturating_sub(1));
+        // Address options end with "add new address", one past the saved ones
+        self.address_select_index = self.address_select_index.min(self.saved_addresses.len());
+        // Two payment options: ssh and browser
+        self.payment_option_index = self.payment_option_index.min(1);
+    }
+
+    /// Add current product to cart
+    pub fn add_to_cart(&mut self) {
+        self.apply_quantity_input();
+        if let Some(product) = self.selected_product().cloned() {
+            if product.is_sold_out() {
+                self.notify(Severity::Error, product.cap_message(0));
+                return;
+            }
+            if let Some(limit) = self.cart.add_item(product.clone(), self.product_quantity) {
+                self.notify(Severity::Error, product.cap_message(limit));
+            }
+            self.product_quantity = 1; // Reset quantity
+        }
+    }
+
+    /// Add one more of the selected cart line, up to its per-order limit
+    pub fn increment_selected_item(&mut self) {
+        if let Some(item) = self.cart.items.get(self.cart_item_index) {
+            let product = item.product.clone();
+            if let Some(limit) = self.cart.increment_item(product.id) {
+                self.notify(Severity::Error, product.cap_message(limit));
+            }
+        }
+    }
+
+    /// One fewer of the selected cart line; the last one is only removed once confirmed
+    /// with a second `-`, so fiddling with quantities can't drop a line by accident
+    pub fn decrement_selected_item(&mut self) {
+        let Some(item) = self.cart.items.get(self.cart_item_index) else {
+            return;
+        };
+        let id = item.product.id;
+        if item.quantity > 1 {
+            self.cart.decrement_item(id);
+        } else if self.confirm_remove == Some(id) {
+            self.remove_selected_item();
+        } else {
+            self.confirm_remove = Some(id);
+        }
+    }
+
+    /// Drop the selected cart line whatever its quantity
+    pub fn remove_selected_item(&mut self) {
+        if let Some(item) = self.cart.items.get(self.cart_item_index) {
+            let id = item.product.id;
+            self.cart.remove_item(id);
+            self.confirm_remove = None;
+            self.clamp_selection();
+        }
+    }
+
+    /// Process current input character, inserted at the caret
+    pub fn handle_input_char(&mut self, c: char) {
+        if let Some(max) = self.active_input.max_len() {
+            if self.active_input_len() >= max {
+                return;
+            }
+        }
+
+        let accepted = match self.active_input {
+            InputField::None => false,
+            // Typed as MM/YY, always at the end
+            InputField::Expiry => {
+                self.payment_info.push_expiry_char(c);
+                return;
+            }
+            InputField::CardNumber => c.is_ascii_digit() && self.payment_info.card_number.len() < 16,
+            InputField::Cvv => c.is_ascii_digit() && self.payment_info.cvv.len() < 3,
+            // Only the gift message spans lines
+            _ if c == '\n' => self.active_input == InputField::GiftMessage,
+            _ => true,
+        };
+        if !accepted {
+            return;
+        }
+
+        let cursor = self.input_cursor();
+        if let Some(value) = self.active_value_mut() {
+            value.insert(byte_index(value, cursor), c);
+            self.set_input_cursor(cursor + 1);
+        }
+    }
+
+    /// Text of the active field, for fields edited as free text
+    fn active_value(&self) -> Option<&String> {
+        Some(match self.active_input {
+            InputField::None | InputField::Expiry => return None,
+            InputField::Name => &self.shipping_address.name,
+            InputField::Street1 => &self.shipping_address.street_1,
+            InputField::Street2 => &self.shipping_address.street_2,
+            InputField::City => &self.shipping_address.city,
+            InputField::State => &self.shipping_address.state,
+            InputField::Country => &self.shipping_address.country,
+            InputField::Phone => &self.shipping_address.phone,
+            InputField::PostalCode => &self.shipping_address.postal_code,
+            InputField::PaymentName => &self.payment_info.name,
+            InputField::PaymentEmail => &self.payment_info.email,
+            InputField::CardNumber => &self.payment_info.card_number,
+            InputField::Cvv => &self.payment_info.cvv,
+            InputField::GiftMessage => &self.gift_message,
+        })
+    }
+
+    fn active_value_mut(&mut self) -> Option<&mut String> {
+        Some(match self.active_input {
+            InputField::None | InputField::Expiry => return None,
+            InputField::Name => &mut self.shipping_address.name,
+            InputField::Street1 => &mut self.shipping_address.street_1,
+            InputField::Street2 => &mut self.shipping_address.street_2,
+            InputField::City => &mut self.shipping_address.city,
+            InputField::State => &mut self.shipping_address.state,
+            InputField::Country => &mut self.shipping_address.country,
+            InputField::Phone => &mut self.shipping_address.phone,
+            InputField::PostalCode => &mut self.shipping_address.postal_code,
+            InputField::PaymentName => &mut self.payment_info.name,
+            InputField::PaymentEmail => &mut self.payment_info.email,
+            InputField::CardNumber => &mut self.payment_info.card_number,
+            InputField::Cvv => &mut self.payment_info.cvv,
+            InputField::GiftMessage => &mut self.gift_message,
+        })
+    }
+
+    /// Length (in characters) of the field being edited
+    fn active_input_len(&self) -> usize {
+        match self.active_input {
+            InputField::Expiry => self.payment_info.expiry_display().chars().count(),
+            _ => self.active_value().map_or(0, |value| value.chars().count()),
+        }
+    }
+
+    /// Caret position (in characters) in the active field: at the end unless moved with ←/→
+    pub fn input_cursor(&self) -> usize {
+        let len = self.active_input_len();
+        if self.input_cursor_field == self.active_input {
+            self.input_cursor.min(len)
+        } else {
+            len
+        }
+    }
+
+    fn set_input_cursor(&mut self, at: usize) {
+        self.input_cursor = at;
+        self.input_cursor_field = self.active_input;
+    }
+
+    /// Move the caret left or right within a free-text field
+    pub fn move_input_cursor(&mut self, delta: isize) {
+        if self.active_value().is_some() {
+            let at = self.input_cursor().saturating_add_signed(delta).min(self.active_input_len());
+            self.set_input_cursor(at);
+        }
+    }
+
+    /// Jump the caret to the start (home) or end of a free-text field
+    pub fn input_cursor_to(&mut self, end: bool) {
+        if self.active_value().is_some() {
+            self.set_input_cursor(if end { self.active_input_len() } else { 0 });
+        }
+    }
+
+    /// Handle backspace in input: delete the character before the caret
+    pub fn handle_input_backspace(&mut self) {
+        if self.active_input == InputField::Expiry {
+            self.payment_info.pop_expiry_char();
+            return;
+        }
+        let cursor = self.input_cursor();
+        if cursor == 0 {
+            return;
+        }
+        if let Some(value) = self.active_value_mut() {
+            value.remove(byte_index(value, cursor - 1));
+            self.set_input_cursor(cursor - 1);
+        }
+    }
+
+    /// Bracketed paste into the active field: each character goes through the same
+    /// validation as typing, and line breaks or other control characters are dropped
+    pub fn handle_paste(&mut self, text: &str) {
+        if self.active_input == InputField::None || self.field_jump {
+            return;
+        }
+        for c in text.chars().filter(|c| !c.is_control() || *c == '\n') {
+            self.handle_input_char(c);
+        }
+    }
+
+    /// Move to next input field
+    pub fn next_input_field(&mut self) {
+        match self.checkout_step {
+            CheckoutStep::Shipping => {
+                self.active_input = self.active_input.next_shipping();
+            }
+            CheckoutStep::Payment if self.payment_method == Some(PaymentMethod::Ssh) => {
+                self.active_input = self.active_input.next_payment();
+            }
+            _ => {}
+        }
+    }
+
+    /// Jump to the nth (1-based) field of the current form, clamped to valid fields
+    pub fn jump_to_field(&mut self, n: usize) {
+        self.field_jump = false;
+        let fields = match self.checkout_step {
+            CheckoutStep::Shipping => InputField::shipping_fields(),
+            CheckoutStep::Payment if self.payment_method == Some(PaymentMethod::Ssh) => {
+                InputField::payment_fields()
+            }
+            _ => return,
+        };
+        let index = n.saturating_sub(1).min(fields.len() - 1);
+        self.active_input = fields[index];
+    }
+
+    /// Keep products in shop section order (by name within a section), so the list index
+    /// runs across sections; the selection follows the selected product, not its old position
+    fn sort_products(&mut self) {
+        let selected = self.products.get(self.selected_product_index).map(|p| p.id);
+        let grouping = self.shop_grouping;
+        self.products.sort_by_cached_key(|p| (grouping.section_of(p), p.name.to_lowercase()));
+        if let Some(index) = selected.and_then(|id| self.products.iter().position(|p| p.id == id)) {
+            if index != self.selected_product_index {
+                self.selected_product_index = index;
+                self.on_selection_changed();
+            }
+        }
+    }
+
+    /// g: switch the shop between category and roast sections
+    pub fn toggle_shop_grouping(&mut self) {
+        self.shop_grouping = match self.shop_grouping {
+            ShopGrouping::Category => ShopGrouping::Roast,
+            ShopGrouping::Roast => ShopGrouping::Category,
+        };
+        self.sort_products();
+    }
+
+    /// Whether the shop filter (and the favorites-only view) lets a product through
+    fn shows_product(&self, product: &Product) -> bool {
+        product.matches_filter(&self.shop_filter, &self.region)
+            && (!self.favorites_only || self.favorites.contains(&product.id))
+    }
+
+    /// Indices into `products` of the products the shop filter shows, in list order
+    pub fn visible_product_indices(&self) -> Vec<usize> {
+        self.products
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| self.shows_product(p))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// The selected product, unless the shop filter hides it
+    pub fn selected_product(&self) -> Option<&Product> {
+        self.products
+            .get(self.selected_product_index)
+            .filter(|p| self.shows_product(p))
+    }
+
+    /// Whether clicks and scrolling should act: not while an overlay, prompt or field has the keyboard
+    pub fn accepts_mouse(&self) -> bool {
+        !self.show_splash
+            && !self.show_whats_new
+            && !self.is_loading()
+            && !self.show_email_preview
+            && !self.show_cart_peek
+            && !self.show_help
+            && !self.confirm_reset
+            && !self.confirm_cancel_subscription
+            && !self.confirm_quit
+            && self.placed_order.is_none()
+            && self.confirm_remove.is_none()
+            && self.region_conflicts.is_empty()
+            && !self.shop_filter_active
+            && self.active_input == InputField::None
+    }
+
+    /// Switch tabs or select a product under a click
+    pub async fn click_at(&mut self, column: u16, row: u16) {
+        let position = Position::new(column, row);
+        let tab = self.click_targets.borrow().tab_at(position);
+        let product = self.click_targets.borrow().product_at(position);
+        match (tab, product) {
+            (Some(Tab::Account), _) => self.open_account().await,
+            (Some(tab), _) => self.switch_tab(tab),
+            (None, Some(index)) if self.current_tab == Tab::Shop && index != self.selected_product_index => {
+                self.select_product(index);
+            }
+            _ => {}
+        }
+    }
+
+    /// Scroll wheel: move the selection in the list the current tab shows
+    pub async fn scroll_selection(&mut self, down: bool) {
+        match self.current_tab {
+            Tab::Shop if down => self.next_product(),
+            Tab::Shop => self.prev_product(),
+            Tab::Cart if self.checkout_step == CheckoutStep::Cart && down => self.next_cart_item(),
+            Tab::Cart if self.checkout_step == CheckoutStep::Cart => self.prev_cart_item(),
+            Tab::Account if self.account_section == AccountSection::OrderHistory => {
+                self.scroll_orders(if down { 1 } else { -1 }).await;
+            }
+            _ => {}
+        }
+    }
+
+    fn select_product(&mut self, index: usize) {
+        self.selected_product_index = index;
+        self.on_selection_changed();
+    }
+
+    /// Reset what belongs to the previously selected product: its quantity and expanded description
+    /// Call whenever `selected_product_index` moves
+    fn on_selection_changed(&mut self) {
+        self.product_quantity = 1;
+        self.quantity_input.clear();
+        self.description_expanded = false;
+    }
+
+    /// Navigate products (only those the filter shows)
+    pub fn next_product(&mut self) {
+        let visible = self.visible_product_indices();
+        if visible.is_empty() {
+            return;
+        }
+        let next = match visible.iter().position(|&i| i == self.selected_product_index) {
+            Some(pos) => visible[(pos + 1) % visible.len()],
+            None => visible[0],
+        };
+        self.select_product(next);
+    }
+
+    pub fn prev_product(&mut self) {
+        let visible = self.visible_product_indices();
+        if visible.is_empty() {
+            return;
+        }
+        let prev = match visible.iter().position(|&i| i == self.selected_product_index) {
+            Some(pos) => visible[pos.checked_sub(1).unwrap_or(visible.len() - 1)],
+            None => visible[0],
+        };
+        self.select_product(prev);
+    }
+
+    /// Whether the selected shop product is a subscription (which has no quantity)
+    pub fn selected_is_subscription(&self) -> bool {
+        self.selected_product().is_some_and(|p| p.product_type == ProductType::Subscription)
+    }
+
+    pub fn notify_no_quantity(&mut self) {
+        self.notify(Severity::Error, t("notice.subscription_quantity"));
+    }
+
+    /// Type a digit of the shop quantity (two digits at most)
+    pub fn push_quantity_digit(&mut self, c: char) {
+        if c.is_ascii_digit() && self.quantity_input.len() < 2 {
+            self.quantity_input.push(c);
+        }
+    }
+
+    pub fn pop_quantity_digit(&mut self) {
+        self.quantity_input.pop();
+    }
+
+    pub fn cancel_quantity_input(&mut self) {
+        self.quantity_input.clear();
+    }
+
+    /// Set the quantity from the typed digits, clamped like the +/- stepper
+    fn apply_quantity_input(&mut self) {
+        if let Ok(quantity) = self.quantity_input.parse::<i32>() {
+            self.product_quantity = quantity.clamp(1, MAX_PRODUCT_QUANTITY);
+        }
+        self.quantity_input.clear();
+    }
+
+    /// Start typing a shop filter
+    pub fn open_shop_filter(&mut self) {
+        self.shop_filter_active = true;
+    }
+
+    pub fn push_shop_filter(&mut self, c: char) {
+        self.shop_filter.push(c);
+        self.clamp_selection();
+    }
+
+    pub fn pop_shop_filter(&mut self) {
+        self.shop_filter.pop();
+        self.clamp_selection();
+    }
+
+    /// Stop typing but keep the list filtered
+    pub fn close_shop_filter(&mut self) {
+        self.shop_filter_active = false;
+    }
+
+    /// Clear the filter and return to the full list
+    pub fn clear_shop_filter(&mut self) {
+        self.shop_filter_active = false;
+        self.shop_filter.clear();
+        self.clamp_selection();
+    }
+
+    /// Navigate account sections
+    /// j in the account menu: step through subscriptions while that section is open,
+    /// moving on to the next section past the last one
+    pub fn next_account_item(&mut self) {
+        if self.account_section == AccountSection::Subscriptions
+            && self.subscription_index + 1 < self.subscriptions.len()
+        {
+            self.subscription_index += 1;
+            return;
+        }
+        self.next_account_section();
+        self.subscription_index = 0;
+    }
+
+    /// k in the account menu: the reverse of `next_account_item`
+    pub fn prev_account_item(&mut self) {
+        if self.account_section == AccountSection::Subscriptions && self.subscription_index > 0 {
+            self.subscription_index -= 1;
+            return;
+        }
+        self.prev_account_section();
+        self.subscription_index = self.subscriptions.len().saturating_sub(1);
+    }
+
+    pub fn selected_subscription(&self) -> Option<&Subscription> {
+        self.subscriptions.get(self.subscription_index)
+    }
+
+    /// Pause an active subscription or resume a paused one
+    pub async fn toggle_selected_subscription_pause(&mut self) {
+        match self.selected_subscription().map(|s| s.status) {
+            Some(SubscriptionStatus::Active) => self.set_selected_subscription_status(SubscriptionStatus::Paused).await,
+            Some(SubscriptionStatus::Paused) => self.set_selected_subscription_status(SubscriptionStatus::Active).await,
+            _ => {}
+        }
+    }
+
+    /// Cancel the selected subscription unless it already is
+    /// Ask for confirmation before cancelling the selected subscription
+    pub fn request_subscription_cancel(&mut self) {
+        if self.selected_subscription().is_some_and(|s| s.status != SubscriptionStatus::Cancelled) {
+            self.confirm_cancel_subscription = true;
+            self.notify_until_dismissed(Severity::Info, t("subscription.cancel_prompt"));
+        }
+    }
+
+    pub fn keep_subscription(&mut self) {
+        self.confirm_cancel_subscription = false;
+        self.dismiss_notification(t("subscription.cancel_prompt"));
+    }
+
+    pub async fn cancel_selected_subscription(&mut self) {
+        self.keep_subscription();
+        if self.selected_subscription().is_some_and(|s| s.status != SubscriptionStatus::Cancelled) {
+            self.set_selected_subscription_status(SubscriptionStatus::Cancelled).await;
+        }
+    }
+
+    /// Show the new status right away, then put the old one back if the backend refuses
+    async fn set_selected_subscription_status(&mut self, status: SubscriptionStatus) {
+        let index = self.subscription_index;
+        let Some(subscription) = self.subscriptions.get_mut(index) else {
+            return;
+        };
+        let previous = std::mem::replace(&mut subscription.status, status);
+        let (id, name) = (subscription.id, subscription.product_name.clone());
+
+        match self.db.update_subscription_status(&id, status).await {
+            Ok(()) => {
+                self.cache.invalidate_account(&self.identity.user_id().to_string());
+                let done = match status {
+                    SubscriptionStatus::Active => "resumed",
+                    SubscriptionStatus::Paused => "paused",
+                    SubscriptionStatus::Cancelled => "cancelled",
+                };
+                self.notify(Severity::Success, format!("{} {}", name, done));
+            }
+            Err(_) => {
+                if let Some(subscription) = self.subscriptions.get_mut(index) {
+                    subscription.status = previous;
+                }
+                self.notify(Severity::Error, format!("couldn't update {}, please try again", name));
+            }
+        }
+    }
+
+    pub fn next_account_section(&mut self) {
+        self.show_order_detail = false;
+        self.account_section = match self.account_section {
             AccountSection::OrderHistory => AccountSection::Subscriptions,
             AccountSection::Subscriptions => AccountSection::Faq,
             AccountSection::Faq => AccountSection::About,
-            AccountSection::About => AccountSection::OrderHistory,
+            AccountSection::About => AccountSection::WhatsNew,
+            AccountSection::WhatsNew => AccountSection::Settings,
+            AccountSection::Settings => AccountSection::OrderHistory,
         };
     }
 
     pub fn prev_account_section(&mut self) {
+        self.show_order_detail = false;
         self.account_section = match self.account_section {
-            AccountSection::OrderHistory => AccountSection::About,
+            AccountSection::OrderHistory => AccountSection::Settings,
             AccountSection::Subscriptions => AccountSection::OrderHistory,
             AccountSection::Faq => AccountSection::Subscriptions,
             AccountSection::About => AccountSection::Faq,
+            AccountSection::WhatsNew => AccountSection::About,
+            AccountSection::Settings => AccountSection::WhatsNew,
         };
     }
 
@@ -553,20 +2072,52 @@ impl App {
 
     /// Select the current address option
     pub fn select_address_option(&mut self) {
+        self.clear_undo();
         if self.address_select_index < self.saved_addresses.len() {
             // Selected a saved address - convert to ShippingAddress
             self.shipping_address = self.saved_addresses[self.address_select_index].to_shipping();
-            // Proceed to payment
-            self.active_input = InputField::None;
-            self.checkout_step = CheckoutStep::Payment;
+            // Proceed to the gift message or payment
+            self.checkout_step = self.step_after_shipping();
         } else {
             // Selected "add new address"
+            self.editing_address_id = None;
             self.shipping_mode = ShippingMode::AddNewAddress;
             self.shipping_address = ShippingAddress::default();
             self.active_input = InputField::Name;
         }
     }
 
+    /// Copy the selected saved address into the new-address form, leaving the original as is
+    pub fn duplicate_selected_address(&mut self) {
+        let Some(address) = self.saved_addresses.get(self.address_select_index).map(SavedAddress::to_shipping) else {
+            return;
+        };
+        if self.saved_addresses.len() >= MAX_SAVED_ADDRESSES {
+            self.notify(Severity::Error, format!(
+                "you can save up to {} addresses — remove one first",
+                MAX_SAVED_ADDRESSES
+            ));
+            return;
+        }
+        self.clear_undo();
+        self.editing_address_id = None;
+        self.shipping_address = address;
+        self.shipping_mode = ShippingMode::AddNewAddress;
+        self.active_input = InputField::Name;
+    }
+
+    /// Load the selected saved address into the form to fix it; saving updates it in place
+    pub fn edit_selected_address(&mut self) {
+        let Some(address) = self.saved_addresses.get(self.address_select_index) else {
+            return;
+        };
+        self.editing_address_id = address.id;
+        self.shipping_address = address.to_shipping();
+        self.clear_undo();
+        self.shipping_mode = ShippingMode::AddNewAddress;
+        self.active_input = InputField::Name;
+    }
+
     /// Remove the selected saved address (async for DB deletion)
     pub async fn remove_selected_address(&mut self) {
         if self.address_select_index < self.saved_addresses.len() {
@@ -580,29 +2131,72 @@ impl App {
         self.checkout_step == CheckoutStep::Shipping && self.shipping_mode == ShippingMode::SelectAddress
     }
 
-    /// Cycle to next region instantly
-    pub async fn cycle_region(&mut self) {
-        if self.regions.is_empty() {
+    /// Pick the next region. Its products come from the cache or a background fetch, so
+    /// the loop keeps drawing (with the loading indicator) instead of waiting on the network
+    pub fn cycle_region(&mut self) {
+        if !self.can_change_region() {
+            self.notify(Severity::Error, "no other regions to switch to");
+            return;
+        }
+        if self.last_region_cycle.is_some_and(|at| at.elapsed() < REGION_CYCLE_DEBOUNCE) {
             return;
         }
+        self.last_region_cycle = Some(Instant::now());
         let current_idx = self
             .regions
             .iter()
             .position(|r| r.id == self.region.id)
             .unwrap_or(0);
         let next_idx = (current_idx + 1) % self.regions.len();
-        if let Some(region) = self.regions.get(next_idx) {
-            self.change_region(region.clone()).await;
+        if let Some(region) = self.regions.get(next_idx).cloned() {
+            self.change_region(region);
+        }
+    }
+
+    /// Whether `r` has another region to switch to
+    pub fn can_change_region(&self) -> bool {
+        self.regions.len() > 1
+    }
+
+    /// Step once the shipping address is settled: gift orders stop for their message
+    fn step_after_shipping(&mut self) -> CheckoutStep {
+        if self.gift_order {
+            self.active_input = InputField::GiftMessage;
+            CheckoutStep::Gift
+        } else {
+            self.active_input = InputField::None;
+            CheckoutStep::Payment
         }
     }
 
     /// Proceed to next checkout step (async for DB operations)
+    /// Validation errors are shown as the notification
     pub async fn next_checkout_step(&mut self) {
-        // Clear any previous notification
-        self.notification = None;
+        if let Err(e) = self.try_next_checkout_step().await {
+            self.notify(Severity::Error, e);
+        }
+    }
+
+    /// Advance the checkout state machine one step, returning why it can't instead of
+    /// notifying, so checkout can be driven without a terminal
+    pub async fn try_next_checkout_step(&mut self) -> Result<(), String> {
+        // Clear any previous notifications
+        self.clear_notifications();
+
+        // Nothing to check out: never place a zero-item order
+        self.check_empty_checkout();
+        if self.cart.is_empty() {
+            return Err("your cart is empty".to_string());
+        }
 
         self.checkout_step = match self.checkout_step {
+            // Products that left the catalog can't be ordered
+            CheckoutStep::Cart if self.cart.unavailable_items().next().is_some() => {
+                let names: Vec<_> = self.cart.unavailable_items().map(|i| i.product.name.as_str()).collect();
+                return Err(format!("remove what's no longer available first: {}", names.join(", ")));
+            }
             CheckoutStep::Cart if !self.cart.is_empty() => {
+                self.cart.acknowledge_price_changes();
                 // Reset shipping mode to selection
                 self.shipping_mode = ShippingMode::SelectAddress;
                 self.address_select_index = 0;
@@ -613,65 +2207,287 @@ impl App {
                 match self.shipping_mode {
                     ShippingMode::SelectAddress => {
                         // This case is handled by select_address_option
-                        return;
+                        return Ok(());
                     }
                     ShippingMode::AddNewAddress => {
-                        // Validate shipping fields
-                        if let Some(empty_field) = self.get_empty_shipping_field() {
-                            self.notification = Some(format!("{} can't be empty", empty_field));
-                            return;
-                        }
+                        self.validate_shipping()?;
                         // Save the address to Supabase
                         let _ = self.save_address_to_db().await;
-                        self.active_input = InputField::None;
-                        CheckoutStep::Payment
+                        self.step_after_shipping()
                     }
                 }
             }
+            CheckoutStep::Gift => {
+                self.active_input = InputField::None;
+                CheckoutStep::Payment
+            }
             CheckoutStep::Payment => {
                 if self.payment_method == Some(PaymentMethod::Ssh) {
-                    // Validate payment fields
-                    if let Some(empty_field) = self.get_empty_payment_field() {
-                        self.notification = Some(format!("{} can't be empty", empty_field));
-                        return;
-                    }
+                    self.validate_payment()?;
+                    self.active_input = InputField::None;
+                    self.pending_order = Some(self.build_order());
                     CheckoutStep::Confirmation
                 } else if self.payment_method == Some(PaymentMethod::Browser) {
+                    self.pending_order = Some(self.build_order());
                     CheckoutStep::Confirmation
                 } else {
                     CheckoutStep::Payment
                 }
             }
+            CheckoutStep::Confirmation if self.settings.confirm_before_order && !self.order_confirm_armed => {
+                // Safer default: the first enter only arms the order
+                self.order_confirm_armed = true;
+                CheckoutStep::Confirmation
+            }
             CheckoutStep::Confirmation => {
+                // Place the order this screen (and its receipt and email preview) showed;
+                // stay on confirmation with the cart intact if it can't be saved
+                let order = match self.pending_order.take() {
+                    Some(order) => order,
+                    None => self.build_order(),
+                };
+                let created = match self.db.create_order(&order).await {
+                    Ok(created) => created,
+                    Err(e) => {
+                        self.pending_order = Some(order);
+                        return Err(format!("couldn't place your order, please try again ({})", e));
+                    }
+                };
+                self.placed_order = Some(created.clone());
+                self.orders.insert(0, created);
+                self.cache.invalidate_account(&self.identity.user_id().to_string());
+                self.order_confirm_armed = false;
+                // Lines upsold to a subscription are created alongside the order
+                self.create_cart_subscriptions().await;
                 // Order placed - reset
                 self.cart.clear();
-                self.checkout_step = CheckoutStep::Cart;
-                self.current_tab = Tab::Home;
+                self.clamp_selection();
+                self.gift_order = false;
+                self.gift_message.clear();
+                if self.settings.celebrate_orders {
+                    self.celebration_start = Some(self.clock.since_epoch());
+                }
                 CheckoutStep::Cart
             }
             _ => self.checkout_step,
         };
+        Ok(())
+    }
+
+    /// Leave the thank-you screen for Home
+    pub fn dismiss_placed_order(&mut self) {
+        self.placed_order = None;
+        self.switch_tab(Tab::Home);
+    }
+
+    /// Create a subscription for every cart line the customer chose to subscribe to
+    async fn create_cart_subscriptions(&mut self) {
+        let now = Utc::now();
+        let subscriptions: Vec<Subscription> = self
+            .cart
+            .subscribed_items()
+            .map(|item| Subscription {
+                id: uuid::Uuid::new_v4(),
+                user_id: self.identity.user_id(),
+                product_id: item.product.id,
+                product_name: item.product.name.clone(),
+                status: SubscriptionStatus::Active,
+                next_delivery: Some(now + chrono::Duration::days(30)),
+                created_at: now,
+            })
+            .collect();
+
+        for subscription in subscriptions {
+            match self.db.create_subscription(&subscription).await {
+                Ok(created) => self.subscriptions.insert(0, created),
+                Err(_) => {
+                    self.notify(Severity::Error, format!(
+                        "Failed to start subscription for {}",
+                        subscription.product_name
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Snapshot the cart and shipping details as an order
+    pub fn build_order(&self) -> Order {
+        let now = Utc::now();
+        let subtotal_cents = self.cart.subtotal_cents();
+        let shipping_cents = self.cart.shipping_cents(&self.region);
+        let paid_over_ssh = self.payment_method == Some(PaymentMethod::Ssh);
+        Order {
+            id: uuid::Uuid::new_v4(),
+            user_id: self.identity.user_id(),
+            items: self.cart.items.iter().map(OrderLine::from).collect(),
+            shipping_address: self.shipping_address.clone(),
+            // Browser checkout collects the purchaser's details itself
+            gift: self.gift_order.then(|| GiftDetails {
+                purchaser_name: paid_over_ssh.then(|| self.payment_info.name.clone()),
+                purchaser_email: paid_over_ssh.then(|| self.payment_info.email.clone()),
+                message: self.gift_message.trim().to_string(),
+            }),
+            subtotal_cents,
+            shipping_cents,
+            total_cents: subtotal_cents + shipping_cents,
+            currency: self.region.currency.clone(),
+            status: OrderStatus::Pending,
+            tracking_number: None,
+            carrier: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Copy the pending order's receipt to the clipboard (or save it to a file)
+    pub fn copy_receipt(&mut self) {
+        let Some(order) = &self.pending_order else {
+            return;
+        };
+        let file_name = format!("anora-receipt-{}.txt", order.short_id());
+        match clipboard::copy_or_save(&order.to_receipt_text(), &file_name) {
+            Ok(CopyOutcome::Clipboard) => self.notify(Severity::Success, t("notice.receipt_copied")),
+            Ok(CopyOutcome::File(path)) => {
+                self.notify(Severity::Success, t("notice.receipt_saved").replace("{path}", &path.display().to_string()))
+            }
+            Err(e) => self.notify(Severity::Error, format!("Failed to save receipt: {}", e)),
+        }
+    }
+
+    /// Text of the confirmation email for the pending order
+    pub fn email_preview(&self) -> Option<String> {
+        let order = self.pending_order.as_ref()?;
+        let to = if self.payment_info.email.is_empty() {
+            "(the email you enter at browser checkout)".to_string()
+        } else if self.privacy_mode {
+            "•".repeat(self.payment_info.email.chars().count())
+        } else {
+            self.payment_info.email.clone()
+        };
+        let name = if self.payment_info.name.is_empty() {
+            &self.shipping_address.name
+        } else {
+            &self.payment_info.name
+        };
+        Some(order.to_email_preview(&to, name))
+    }
+
+    /// Open/close the confirmation email preview
+    pub fn toggle_email_preview(&mut self) {
+        self.show_email_preview = !self.show_email_preview && self.pending_order.is_some();
+        self.email_preview_scroll = 0;
+    }
+
+    /// Open/close the read-only cart overlay
+    pub fn toggle_cart_peek(&mut self) {
+        self.show_cart_peek = !self.show_cart_peek;
+    }
+
+    /// Open/close the keyboard help overlay
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+    }
+
+    /// Scroll the email preview
+    pub fn scroll_email_preview(&mut self, delta: i16) {
+        self.email_preview_scroll = self.email_preview_scroll.saturating_add_signed(delta);
+    }
+
+    /// Toggle masking of sensitive fields
+    pub fn toggle_privacy_mode(&mut self) {
+        self.privacy_mode = !self.privacy_mode;
+        self.notify(Severity::Info, t(if self.privacy_mode { "notice.privacy_on" } else { "notice.privacy_off" }));
+    }
+
+    /// Cheapest in-stock product that would push the cart over the free-shipping threshold
+    /// Only suggested when the cart is within a small gap of the threshold
+    pub fn free_shipping_suggestion(&self) -> Option<&Product> {
+        let gap = self.amount_to_free_shipping();
+        if self.cart.is_empty() || gap == 0 || gap > FREE_SHIPPING_NUDGE_GAP_CENTS {
+            return None;
+        }
+        self.products
+            .iter()
+            .filter(|p| p.in_stock && p.product_type != ProductType::Subscription)
+            .filter(|p| p.price_cents >= gap)
+            .filter(|p| {
+                // Skip products already at their per-order limit
+                let in_cart = self.cart.items.iter().find(|i| i.product.id == p.id).map_or(0, |i| i.quantity);
+                p.clamp_quantity(in_cart + 1) > in_cart
+            })
+            .min_by_key(|p| p.price_cents)
+    }
+
+    /// Add the free-shipping suggestion to the cart
+    pub fn add_free_shipping_suggestion(&mut self) {
+        if let Some(product) = self.free_shipping_suggestion().cloned() {
+            self.notify(Severity::Success, format!("added {} — free shipping unlocked", product.name));
+            self.cart.add_item(product, 1);
+        }
+    }
+
+    /// Switch between a regular order and a gift shipped to someone else
+    pub fn toggle_gift_order(&mut self) {
+        self.gift_order = !self.gift_order;
+        self.notify(Severity::Info, t(if self.gift_order { "notice.gift_on" } else { "notice.gift_off" }));
+    }
+
+    /// Toggle the "subscribe and save" upsell on the selected cart line
+    pub fn toggle_selected_subscription(&mut self) {
+        if let Some(item) = self.cart.items.get(self.cart_item_index) {
+            let id = item.product.id;
+            self.cart.toggle_subscribe(id);
+        }
+    }
+
+    /// Check the shipping form: every required field filled in
+    pub fn validate_shipping(&self) -> Result<(), String> {
+        match self.get_empty_shipping_field() {
+            Some(field) => Err(t("validation.empty").replace("{field}", field)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check the ssh payment form: every field filled in, a card number that passes the
+    /// Luhn check, and an expiry that's a real month and not in the past
+    pub fn validate_payment(&self) -> Result<(), String> {
+        if let Some(field) = self.get_empty_payment_field() {
+            return Err(t("validation.empty").replace("{field}", field));
+        }
+        if !self.payment_info.is_email_valid() {
+            return Err(t("validation.email").to_string());
+        }
+        if !self.payment_info.is_card_valid() {
+            return Err(t("validation.card_number").to_string());
+        }
+        if self.payment_info.expiry_month_number().is_none() {
+            return Err(t("validation.expiry_month").to_string());
+        }
+        if self.payment_info.is_expired_at(Utc::now()) {
+            return Err(t("validation.card_expired").to_string());
+        }
+        Ok(())
     }
 
     /// Get the first empty shipping field name, if any
     fn get_empty_shipping_field(&self) -> Option<&'static str> {
         if self.shipping_address.name.is_empty() {
-            return Some("name");
+            return Some(t("field.name"));
         }
         if self.shipping_address.street_1.is_empty() {
-            return Some("street");
+            return Some(t("field.street"));
         }
         if self.shipping_address.city.is_empty() {
-            return Some("city");
+            return Some(t("field.city"));
         }
         if self.shipping_address.country.is_empty() {
-            return Some("country");
+            return Some(t("field.country"));
         }
         if self.shipping_address.phone.is_empty() {
-            return Some("phone");
+            return Some(t("field.phone"));
         }
         if self.shipping_address.postal_code.is_empty() {
-            return Some("postal code");
+            return Some(t("field.postal_code"));
         }
         None
     }
@@ -679,64 +2495,108 @@ impl App {
     /// Get the first empty payment field name, if any
     fn get_empty_payment_field(&self) -> Option<&'static str> {
         if self.payment_info.name.is_empty() {
-            return Some("name");
+            return Some(t("field.name"));
         }
         if self.payment_info.email.is_empty() {
-            return Some("email");
+            return Some(t("field.email"));
         }
         if self.payment_info.card_number.is_empty() {
-            return Some("card number");
-        }
-        if self.payment_info.expiry_month.is_empty() {
-            return Some("expiry month");
+            return Some(t("field.card_number"));
         }
-        if self.payment_info.expiry_year.is_empty() {
-            return Some("expiry year");
+        if self.payment_info.expiry_month.is_empty() || self.payment_info.expiry_year.is_empty() {
+            return Some(t("field.expiry_short"));
         }
         if self.payment_info.cvv.is_empty() {
-            return Some("cvv");
+            return Some(t("field.cvv_short"));
         }
         None
     }
 
-    /// Clear the notification
-    #[allow(dead_code)]
-    pub fn clear_notification(&mut self) {
-        self.notification = None;
+    /// Queue a notification that expires after a few seconds
+    /// Repeating the newest message restarts its timer rather than stacking a copy
+    pub fn notify(&mut self, severity: Severity, message: impl Into<String>) {
+        let expires_at = Some(self.clock.since_epoch() + NOTIFICATION_DURATION);
+        self.push_notification(Notification { message: message.into(), severity, expires_at });
+    }
+
+    /// Queue a prompt that stays until dismissed, for prompts with their own lifetime
+    fn notify_until_dismissed(&mut self, severity: Severity, message: &str) {
+        self.push_notification(Notification { message: message.to_string(), severity, expires_at: None });
+    }
+
+    fn push_notification(&mut self, notification: Notification) {
+        if self.notifications.back().is_some_and(|n| n.message == notification.message) {
+            self.notifications.pop_back();
+        }
+        self.notifications.push_back(notification);
+        while self.notifications.len() > MAX_NOTIFICATIONS {
+            self.notifications.pop_front();
+        }
+    }
+
+    /// The newest notification's message
+    #[cfg(test)]
+    pub fn notification(&self) -> Option<&str> {
+        self.notifications.back().map(|n| n.message.as_str())
+    }
+
+    /// Drop notifications whose time is up
+    pub fn check_notifications(&mut self) {
+        let now = self.clock.since_epoch();
+        self.notifications.retain(|n| n.expires_at.is_none_or(|at| now < at));
+    }
+
+    /// Remove a specific message, e.g. a prompt that was answered
+    pub fn dismiss_notification(&mut self, message: &str) {
+        self.notifications.retain(|n| n.message != message);
+    }
+
+    /// Drop every queued notification
+    pub fn clear_notifications(&mut self) {
+        self.notifications.clear();
     }
 
     /// Go back in checkout flow
     pub fn prev_checkout_step(&mut self) {
         // Clear notification when going back
-        self.notification = None;
+        self.clear_notifications();
 
         self.checkout_step = match self.checkout_step {
             CheckoutStep::Cart => {
-                self.current_tab = Tab::Shop;
+                self.switch_tab(Tab::Shop);
                 CheckoutStep::Cart
             }
             CheckoutStep::Shipping => {
                 match self.shipping_mode {
                     ShippingMode::AddNewAddress => {
-                        // Go back to address selection
+                        // Go back to address selection, dropping any edit
+                        self.editing_address_id = None;
                         self.shipping_mode = ShippingMode::SelectAddress;
                         self.active_input = InputField::None;
                         CheckoutStep::Shipping
                     }
                     ShippingMode::SelectAddress => {
                         // Go back to cart
+                        self.clear_undo();
                         self.active_input = InputField::None;
                         CheckoutStep::Cart
                     }
                 }
             }
-            CheckoutStep::Payment => {
+            CheckoutStep::Payment if self.gift_order => {
+                self.payment_method = None;
+                self.active_input = InputField::GiftMessage;
+                CheckoutStep::Gift
+            }
+            CheckoutStep::Gift | CheckoutStep::Payment => {
                 self.payment_method = None;
                 self.shipping_mode = ShippingMode::SelectAddress;
                 self.active_input = InputField::None;
                 CheckoutStep::Shipping
             }
             CheckoutStep::Confirmation => {
+                self.pending_order = None;
+                self.order_confirm_armed = false;
                 self.active_input = if self.payment_method == Some(PaymentMethod::Ssh) {
                     InputField::PaymentName
                 } else {
@@ -761,7 +2621,17 @@ impl App {
         };
     }
 
+    /// q: quit straight away with an empty cart, otherwise ask first
+    pub fn request_quit(&mut self) {
+        if self.cart.is_empty() || self.confirm_quit {
+            self.quit();
+        } else {
+            self.confirm_quit = true;
+        }
+    }
+
     pub fn quit(&mut self) {
+        self.persist_cart();
         self.running = false;
     }
 }
@@ -771,3 +2641,1234 @@ impl Default for App {
         Self::new()
     }
 }
+
+/// Byte offset of the `chars`-th character, or the end of `s`
+fn byte_index(s: &str, chars: usize) -> usize {
+    s.char_indices().nth(chars).map_or(s.len(), |(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::MockDatabase;
+
+    /// Let a background product fetch finish and take in its result
+    async fn finish_background_load(app: &mut App) {
+        while app.loading == LoadingState::Loading {
+            tokio::task::yield_now().await;
+            app.apply_updates();
+        }
+    }
+
+    fn app_with_cart() -> App {
+        let mut app = App::for_test();
+        app.products = vec![Product::sample("segfault", 2200)];
+        app.current_tab = Tab::Cart;
+        app.cart.add_item(app.products[0].clone(), 1);
+        app
+    }
+
+    #[test]
+    fn shop_filter_narrows_navigation_and_esc_restores_the_list() {
+        let mut app = App::for_test();
+        app.products = vec![
+            Product::sample("segfault", 2200),
+            Product::sample("404", 1800),
+            Product::sample("null pointer", 2000),
+        ];
+        app.selected_product_index = 1;
+
+        app.open_shop_filter();
+        "ul".chars().for_each(|c| app.push_shop_filter(c));
+        // "404" is hidden, so the selection moves to the first match
+        assert_eq!(app.visible_product_indices(), vec![0, 2]);
+        assert_eq!(app.selected_product_index, 0);
+        app.next_product();
+        assert_eq!(app.selected_product_index, 2);
+        app.next_product();
+        assert_eq!(app.selected_product_index, 0);
+
+        // No matches: nothing is selected, so nothing can be added
+        app.push_shop_filter('z');
+        assert!(app.selected_product().is_none());
+        app.add_to_cart();
+        assert!(app.cart.is_empty());
+
+        app.clear_shop_filter();
+        assert!(!app.shop_filter_active);
+        assert_eq!(app.visible_product_indices().len(), 3);
+        assert!(app.selected_product().is_some());
+    }
+
+    #[test]
+    fn only_one_time_products_have_a_quantity() {
+        let mut app = App::for_test();
+        let mut subscription = Product::sample("cron", 4000);
+        subscription.product_type = ProductType::Subscription;
+        app.products = vec![Product::sample("segfault", 2200), subscription];
+
+        assert!(!app.selected_is_subscription());
+        app.next_product();
+        assert!(app.selected_is_subscription());
+    }
+
+    #[test]
+    fn typed_quantity_is_clamped_and_applied_on_add() {
+        let mut app = App::for_test();
+        app.products = vec![Product::sample("segfault", 2200), Product::sample("404", 1800)];
+
+        "123".chars().for_each(|c| app.push_quantity_digit(c));
+        assert_eq!(app.quantity_input, "12");
+        app.pop_quantity_digit();
+        app.push_quantity_digit('4');
+        app.add_to_cart();
+        assert_eq!(app.cart.items[0].quantity, 14);
+        assert!(app.quantity_input.is_empty());
+
+        // Zero is clamped up to one
+        app.push_quantity_digit('0');
+        app.add_to_cart();
+        assert_eq!(app.cart.items[0].quantity, 15);
+
+        // Moving to another product drops the pending digits
+        app.push_quantity_digit('7');
+        app.next_product();
+        assert!(app.quantity_input.is_empty());
+    }
+
+    #[test]
+    fn description_collapses_when_moving_to_another_product() {
+        let mut app = App::for_test();
+        app.products = vec![Product::sample("segfault", 2200), Product::sample("404", 1800)];
+        app.description_expanded = true;
+        app.next_product();
+        assert!(!app.description_expanded);
+
+        app.description_expanded = true;
+        app.prev_product();
+        assert!(!app.description_expanded);
+    }
+
+    #[test]
+    fn amount_to_free_shipping_counts_down_to_the_threshold() {
+        let mut app = App::for_test();
+        app.region.free_shipping_threshold = 40;
+
+        app.cart.add_item(Product::sample("segfault", 2200), 1);
+        assert_eq!(app.amount_to_free_shipping(), 1800);
+
+        app.cart.add_item(Product::sample("404", 1800), 1);
+        assert_eq!(app.amount_to_free_shipping(), 0);
+
+        app.cart.add_item(Product::sample("null pointer", 2000), 1);
+        assert_eq!(app.amount_to_free_shipping(), 0);
+
+        app.region.free_shipping_threshold = 0;
+        app.cart.clear();
+        assert_eq!(app.amount_to_free_shipping(), 0);
+    }
+
+    #[test]
+    fn crossing_the_free_shipping_threshold_briefly_shows_unlocked() {
+        let mut app = app_with_cart();
+        app.region.free_shipping_threshold = 30;
+        app.check_free_shipping();
+        assert!(!app.free_shipping_just_unlocked());
+
+        app.increment_selected_item();
+        app.check_free_shipping();
+        assert!(app.free_shipping_just_unlocked());
+
+        // Reverts once the moment has passed, and doesn't restart while still qualified
+        app.clock = AnimationClock::Fixed(FREE_SHIPPING_UNLOCKED_DURATION);
+        app.check_free_shipping();
+        assert!(!app.free_shipping_just_unlocked());
+    }
+
+    #[tokio::test]
+    async fn empty_cart_stays_on_cart_step() {
+        let mut app = App::for_test();
+        app.next_checkout_step().await;
+        assert_eq!(app.checkout_step, CheckoutStep::Cart);
+    }
+
+    #[tokio::test]
+    async fn new_address_must_be_filled_before_payment() {
+        let mut app = app_with_cart();
+        app.next_checkout_step().await;
+        assert_eq!(app.checkout_step, CheckoutStep::Shipping);
+
+        // No saved addresses, so the only option is "add new address"
+        app.select_address_option();
+        assert_eq!(app.shipping_mode, ShippingMode::AddNewAddress);
+        assert_eq!(app.active_input, InputField::Name);
+
+        app.next_checkout_step().await;
+        assert_eq!(app.checkout_step, CheckoutStep::Shipping);
+        assert!(app.notification().is_some_and(|n| n.ends_with("can't be empty")));
+    }
+
+    #[tokio::test]
+    async fn saved_address_goes_straight_to_payment_and_back() {
+        let mut app = app_with_cart();
+        app.saved_addresses = vec![SavedAddress::from_shipping(&ShippingAddress::default(), "test")];
+        app.next_checkout_step().await;
+        app.select_address_option();
+        assert_eq!(app.checkout_step, CheckoutStep::Payment);
+
+        app.prev_checkout_step();
+        assert_eq!(app.checkout_step, CheckoutStep::Shipping);
+        assert_eq!(app.shipping_mode, ShippingMode::SelectAddress);
+        app.prev_checkout_step();
+        assert_eq!(app.checkout_step, CheckoutStep::Cart);
+        app.prev_checkout_step();
+        assert_eq!(app.current_tab, Tab::Shop);
+    }
+
+    #[tokio::test]
+    async fn browser_payment_reaches_confirmation_with_order_snapshot() {
+        let mut app = app_with_cart();
+        app.checkout_step = CheckoutStep::Payment;
+        app.payment_option_index = 1;
+        app.select_payment_method();
+        assert_eq!(app.payment_method, Some(PaymentMethod::Browser));
+
+        app.next_checkout_step().await;
+        assert_eq!(app.checkout_step, CheckoutStep::Confirmation);
+        assert_eq!(app.pending_order.as_ref().map(|o| o.items.len()), Some(1));
+
+        app.prev_checkout_step();
+        assert_eq!(app.checkout_step, CheckoutStep::Payment);
+        assert!(app.pending_order.is_none());
+    }
+
+    #[tokio::test]
+    async fn load_products_keeps_region_and_section_order() {
+        let db = MockDatabase::default();
+        {
+            let mut state = db.state();
+            let mut other = Product::sample("other region", 1000);
+            other.region_id = "uz".to_string();
+            let mut original = Product::sample("original", 1000);
+            original.category = ProductCategory::Originals;
+            state.products = vec![original, other, Product::sample("featured", 1000)];
+        }
+        let mut app = App::for_test_with(db);
+
+        app.load_products().await.unwrap();
+        let names: Vec<_> = app.products.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["featured", "original"]);
+        assert_eq!(app.loading, LoadingState::Idle);
+    }
+
+    #[tokio::test]
+    async fn reloading_the_catalog_reprices_and_flags_dropped_cart_lines() {
+        let db = MockDatabase::default();
+        let segfault = Product::sample("segfault", 2200);
+        let nil = Product::sample("nil", 1800);
+        let mut app = App::for_test_with(db.clone());
+        app.cart.add_item(segfault.clone(), 1);
+        app.cart.add_item(nil.clone(), 1);
+
+        let mut repriced = segfault.clone();
+        repriced.price_cents = 2400;
+        db.state().products = vec![repriced];
+        app.load_products().await.unwrap();
+        assert!(app.cart.items[0].price_changed);
+        assert_eq!(app.cart.items[0].product.price_cents, 2400);
+        assert!(app.cart.items[1].unavailable);
+        assert_eq!(app.notification(), Some("no longer available: nil"));
+        assert_eq!(
+            app.try_next_checkout_step().await,
+            Err("remove what's no longer available first: nil".to_string())
+        );
+
+        // Back in the catalog, the line is orderable again
+        db.state().products.push(nil);
+        app.refresh_products();
+        finish_background_load(&mut app).await;
+        assert!(!app.cart.items[1].unavailable);
+        assert_eq!(app.try_next_checkout_step().await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn load_products_failure_empties_the_shop() {
+        let db = MockDatabase::default();
+        db.state().offline = true;
+        let mut app = App::for_test_with(db);
+        app.products = vec![Product::sample("stale", 1000)];
+
+        app.load_products().await.unwrap();
+        assert!(app.products.is_empty());
+        assert_eq!(app.loading, LoadingState::Error);
+    }
+
+    #[tokio::test]
+    async fn new_address_is_saved_for_this_user() {
+        let db = MockDatabase::default();
+        let mut app = App::for_test_with(db.clone());
+        app.shipping_address = ShippingAddress {
+            name: "ada".to_string(),
+            street_1: "1 loop st".to_string(),
+            city: "tashkent".to_string(),
+            country: "uz".to_string(),
+            phone: "123".to_string(),
+            postal_code: "100000".to_string(),
+            ..ShippingAddress::default()
+        };
+
+        app.save_address_to_db().await.unwrap();
+        // Saving the same address again is a no-op
+        app.save_address_to_db().await.unwrap();
+
+        assert_eq!(app.saved_addresses.len(), 1);
+        assert!(app.saved_addresses[0].id.is_some());
+        let state = db.state();
+        assert_eq!(state.addresses.len(), 1);
+        assert_eq!(state.addresses[0].user_fingerprint, app.identity.fingerprint);
+    }
+
+    #[tokio::test]
+    async fn placing_an_order_starts_upsold_subscriptions() {
+        let db = MockDatabase::default();
+        let mut app = App::for_test_with(db.clone());
+        let mut product = Product::sample("segfault", 2200);
+        product.subscription_discount_pct = 10;
+        app.cart.add_item(product.clone(), 1);
+        app.cart.toggle_subscribe(product.id);
+        app.checkout_step = CheckoutStep::Confirmation;
+        app.order_confirm_armed = true;
+
+        app.next_checkout_step().await;
+        assert!(app.cart.is_empty());
+        assert_eq!(app.subscriptions.len(), 1);
+        assert_eq!(db.state().subscriptions[0].product_id, product.id);
+    }
+
+    #[tokio::test]
+    async fn subscriptions_pause_resume_and_revert_on_error() {
+        let db = MockDatabase::default();
+        let mut app = App::for_test_with(db.clone());
+        let subscription = |name: &str| Subscription {
+            id: uuid::Uuid::new_v4(),
+            user_id: app.identity.user_id(),
+            product_id: uuid::Uuid::new_v4(),
+            product_name: name.to_string(),
+            status: SubscriptionStatus::Active,
+            next_delivery: None,
+            created_at: Utc::now(),
+        };
+        let subscriptions = vec![subscription("segfault"), subscription("404")];
+        db.state().subscriptions = subscriptions.clone();
+        app.subscriptions = subscriptions;
+
+        // j walks the list before moving on to the next section
+        app.account_section = AccountSection::Subscriptions;
+        app.next_account_item();
+        assert_eq!(app.subscription_index, 1);
+        app.toggle_selected_subscription_pause().await;
+        assert_eq!(app.subscriptions[1].status, SubscriptionStatus::Paused);
+        assert_eq!(db.state().subscriptions[1].status, SubscriptionStatus::Paused);
+
+        db.state().offline = true;
+        app.toggle_selected_subscription_pause().await;
+        assert_eq!(app.subscriptions[1].status, SubscriptionStatus::Paused);
+        assert!(app.notification().is_some_and(|n| n.starts_with("couldn't update")));
+
+        db.state().offline = false;
+        app.cancel_selected_subscription().await;
+        assert_eq!(db.state().subscriptions[1].status, SubscriptionStatus::Cancelled);
+        app.next_account_item();
+        assert_eq!(app.account_section, AccountSection::Faq);
+    }
+
+    #[tokio::test]
+    async fn opening_account_loads_history_once_per_ttl() {
+        let db = MockDatabase::default();
+        let mut app = App::for_test_with(db.clone());
+        app.cart.add_item(Product::sample("segfault", 2200), 1);
+        db.state().orders.push(app.build_order());
+
+        app.open_account().await;
+        assert_eq!(app.current_tab, Tab::Account);
+        assert_eq!(app.orders.len(), 1);
+
+        // A reopen within the TTL is served from the cache
+        db.state().orders.push(app.build_order());
+        app.current_tab = Tab::Home;
+        app.open_account().await;
+        assert_eq!(app.orders.len(), 1);
+    }
+
+    #[test]
+    fn order_detail_shows_tracking_only_once_shipped() {
+        let mut app = app_with_cart();
+        let mut order = app.build_order();
+        order.tracking_number = Some("1Z999AA10123456784".to_string());
+        order.carrier = Some("UPS".to_string());
+        app.orders = vec![order];
+        app.current_tab = Tab::Account;
+
+        app.open_order_detail();
+        assert!(app.show_order_detail);
+        assert_eq!(app.selected_order().unwrap().tracking_line(), None);
+
+        app.orders[0].status = OrderStatus::Shipped;
+        assert_eq!(
+            app.selected_order().unwrap().tracking_line().as_deref(),
+            Some("UPS 1Z999AA10123456784")
+        );
+
+        app.next_account_section();
+        assert!(!app.show_order_detail);
+    }
+
+    #[tokio::test]
+    async fn failing_to_load_account_history_is_reported() {
+        let db = MockDatabase::default();
+        db.state().offline = true;
+        let mut app = App::for_test_with(db);
+
+        app.open_account().await;
+        assert!(app.notification().is_some_and(|n| n.starts_with("Failed to load")));
+        assert!(app.orders.is_empty());
+    }
+
+    #[tokio::test]
+    async fn placing_an_order_saves_it_to_order_history() {
+        let db = MockDatabase::default();
+        let mut app = App::for_test_with(db.clone());
+        app.cart.add_item(Product::sample("segfault", 2200), 1);
+        app.checkout_step = CheckoutStep::Confirmation;
+        app.order_confirm_armed = true;
+        // The order matches the receipt shown on the confirmation screen
+        let pending = app.build_order();
+        app.pending_order = Some(pending.clone());
+
+        app.try_next_checkout_step().await.unwrap();
+        assert_eq!(db.state().orders.len(), 1);
+        assert_eq!(db.state().orders[0].id, pending.id);
+        assert!(app.pending_order.is_none());
+        assert_eq!(db.state().orders[0].user_id, app.identity.user_id());
+        assert_eq!(db.state().orders[0].total_cents, 3000);
+        assert_eq!(app.orders.len(), 1);
+
+        // The thank-you screen names the created order until a key leaves it for Home
+        assert_eq!(app.placed_order.as_ref().map(|o| o.id), Some(app.orders[0].id));
+        app.dismiss_placed_order();
+        assert!(app.placed_order.is_none());
+        assert_eq!(app.current_tab, Tab::Home);
+    }
+
+    #[tokio::test]
+    async fn failed_order_keeps_the_cart_on_confirmation() {
+        let db = MockDatabase::default();
+        let mut app = App::for_test_with(db.clone());
+        app.cart.add_item(Product::sample("segfault", 2200), 1);
+        app.current_tab = Tab::Cart;
+        app.checkout_step = CheckoutStep::Confirmation;
+        app.order_confirm_armed = true;
+        db.state().offline = true;
+        app.pending_order = Some(app.build_order());
+
+        app.next_checkout_step().await;
+        assert!(app.notification().is_some_and(|n| n.starts_with("couldn't place your order") && n.ends_with(")")));
+        assert!(app.pending_order.is_some());
+        assert_eq!(app.checkout_step, CheckoutStep::Confirmation);
+        assert_eq!(app.current_tab, Tab::Cart);
+        assert!(!app.cart.is_empty());
+        assert!(app.orders.is_empty());
+    }
+
+    #[tokio::test]
+    async fn placed_order_celebrates_for_a_moment() {
+        let mut app = app_with_cart();
+        app.checkout_step = CheckoutStep::Confirmation;
+        app.order_confirm_armed = true;
+        app.next_checkout_step().await;
+        assert_eq!(app.celebration_elapsed(), Some(Duration::ZERO));
+
+        app.clock = AnimationClock::Fixed(CELEBRATION_DURATION);
+        assert_eq!(app.celebration_elapsed(), None);
+
+        let mut quiet = app_with_cart();
+        quiet.toggle_celebrate_orders();
+        assert!(!App::load_settings(&quiet.store).celebrate_orders);
+        quiet.checkout_step = CheckoutStep::Confirmation;
+        quiet.order_confirm_armed = true;
+        quiet.next_checkout_step().await;
+        assert_eq!(quiet.celebration_elapsed(), None);
+    }
+
+    #[tokio::test]
+    async fn region_change_that_shrinks_the_shop_keeps_selection_valid() {
+        let db = MockDatabase::default();
+        {
+            let mut state = db.state();
+            let global = Region::default();
+            let small = Region { id: "uz".to_string(), ..Region::default() };
+            let mut only = Product::sample("only", 1000);
+            only.region_id = small.id.clone();
+            state.products = vec![
+                Product::sample("a", 1000),
+                Product::sample("b", 1000),
+                Product::sample("c", 1000),
+                only,
+            ];
+            state.regions = vec![global, small];
+        }
+        let mut app = App::for_test_with(db);
+        app.load_initial_data().await.unwrap();
+        assert_eq!(app.products.len(), 3);
+
+        app.selected_product_index = 2;
+        app.cycle_region();
+        assert_eq!(app.loading, LoadingState::Loading);
+        assert!(app.products.is_empty());
+        finish_background_load(&mut app).await;
+        assert_eq!(app.loading, LoadingState::Idle);
+        assert_eq!(app.products.len(), 1);
+        assert_eq!(app.selected_product_index, 0);
+
+        // Reloading from cache without a region reset also clamps
+        app.selected_product_index = 5;
+        app.load_products().await.unwrap();
+        assert_eq!(app.selected_product_index, 0);
+    }
+
+    #[tokio::test]
+    async fn duplicated_address_is_saved_as_a_new_entry() {
+        let db = MockDatabase::default();
+        let mut app = app_with_cart();
+        app.db = Arc::new(db.clone());
+        let original = ShippingAddress {
+            name: "ada".to_string(),
+            street_1: "1 loop st".to_string(),
+            city: "tashkent".to_string(),
+            country: "uz".to_string(),
+            phone: "123".to_string(),
+            postal_code: "100000".to_string(),
+            ..ShippingAddress::default()
+        };
+        app.saved_addresses = vec![SavedAddress::from_shipping(&original, &app.identity.fingerprint)];
+        app.checkout_step = CheckoutStep::Shipping;
+
+        app.duplicate_selected_address();
+        assert_eq!(app.shipping_mode, ShippingMode::AddNewAddress);
+        assert_eq!(app.shipping_address.street_1, "1 loop st");
+
+        app.shipping_address.street_2 = "apt 2".to_string();
+        app.next_checkout_step().await;
+        assert_eq!(app.checkout_step, CheckoutStep::Payment);
+        assert_eq!(app.saved_addresses.len(), 2);
+        assert_eq!(db.state().addresses.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn editing_a_saved_address_updates_it_in_place() {
+        let db = MockDatabase::default();
+        let mut app = App::for_test_with(db.clone());
+        let address = ShippingAddress {
+            name: "ada".to_string(),
+            street_1: "1 loop st".to_string(),
+            city: "tashknet".to_string(),
+            country: "uz".to_string(),
+            phone: "123".to_string(),
+            postal_code: "100000".to_string(),
+            ..ShippingAddress::default()
+        };
+        let saved = db
+            .save_address(&SavedAddress::from_shipping(&address, &app.identity.fingerprint))
+            .await
+            .unwrap();
+        app.saved_addresses = vec![saved.clone()];
+        app.cart.add_item(Product::sample("segfault", 2200), 1);
+        app.checkout_step = CheckoutStep::Shipping;
+
+        app.edit_selected_address();
+        assert_eq!(app.shipping_mode, ShippingMode::AddNewAddress);
+        assert_eq!(app.shipping_address.city, "tashknet");
+        app.shipping_address.city = "tashkent".to_string();
+        app.next_checkout_step().await;
+
+        assert_eq!(app.checkout_step, CheckoutStep::Payment);
+        assert_eq!(app.saved_addresses.len(), 1);
+        assert_eq!(app.saved_addresses[0].id, saved.id);
+        assert_eq!(app.saved_addresses[0].city, "tashkent");
+        let state = db.state();
+        assert_eq!(state.addresses.len(), 1);
+        assert_eq!(state.addresses[0].city, "tashkent");
+    }
+
+    #[test]
+    fn duplicate_respects_the_address_cap() {
+        let mut app = App::for_test();
+        app.saved_addresses = vec![SavedAddress::from_shipping(&ShippingAddress::default(), "test"); MAX_SAVED_ADDRESSES];
+        app.checkout_step = CheckoutStep::Shipping;
+
+        app.duplicate_selected_address();
+        assert_eq!(app.shipping_mode, ShippingMode::SelectAddress);
+        assert!(app.notification().is_some());
+    }
+
+    #[tokio::test]
+    async fn region_change_flags_cart_items_from_other_regions() {
+        let mut app = app_with_cart();
+        let mut local = Product::sample("local", 1000);
+        local.region_id = "uz".to_string();
+        app.cart.add_item(local.clone(), 1);
+
+        let uzbekistan = Region { id: "uz".to_string(), name: "Uzbekistan".to_string(), ..Region::default() };
+        app.change_region(uzbekistan);
+        assert_eq!(app.region_conflicts.len(), 1);
+        let prompt = app.region_conflict_prompt().unwrap();
+        assert!(prompt.contains("segfault") && !prompt.contains("local"));
+        // Nothing is dropped until the user decides
+        assert_eq!(app.cart.items.len(), 2);
+
+        app.remove_region_conflicts();
+        assert_eq!(app.cart.items.len(), 1);
+        assert_eq!(app.cart.items[0].product.id, local.id);
+        assert!(app.region_conflicts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn preferred_region_is_picked_by_code_after_regions_load() {
+        let db = MockDatabase::default();
+        db.state().regions = vec![
+            Region { id: "global".to_string(), code: "Global".to_string(), ..Region::default() },
+            Region { id: "uz".to_string(), code: "UZ".to_string(), ..Region::default() },
+        ];
+        let mut app = App::for_test_with(db.clone());
+        app.preferred_region = Some("uz".to_string());
+        app.start_initial_load().await;
+        assert_eq!(app.region.id, "uz");
+
+        let mut app = App::for_test_with(db);
+        app.preferred_region = Some("mars".to_string());
+        app.start_initial_load().await;
+        assert_eq!(app.region.id, "global");
+        assert!(app.notification().is_some_and(|n| n.starts_with("no region mars")));
+    }
+
+    #[tokio::test]
+    async fn rapid_region_cycling_is_debounced() {
+        let mut app = App::for_test();
+        app.regions = vec![
+            Region { id: "global".to_string(), ..Region::default() },
+            Region { id: "uz".to_string(), ..Region::default() },
+            Region { id: "kz".to_string(), ..Region::default() },
+        ];
+        app.region = app.regions[0].clone();
+
+        app.cycle_region();
+        app.cycle_region();
+        assert_eq!(app.region.id, "uz");
+
+        // Once the window has passed, r works again
+        app.last_region_cycle = Instant::now().checked_sub(REGION_CYCLE_DEBOUNCE);
+        app.cycle_region();
+        assert_eq!(app.region.id, "kz");
+    }
+
+    #[tokio::test]
+    async fn kept_region_conflicts_stay_in_the_cart() {
+        let mut app = app_with_cart();
+        app.change_region(Region { id: "uz".to_string(), ..Region::default() });
+        app.keep_region_conflicts();
+        assert_eq!(app.cart.items.len(), 1);
+        assert!(app.region_conflict_prompt().is_none());
+    }
+
+    #[tokio::test]
+    async fn loading_cue_clears_once_the_retry_succeeds() {
+        let db = MockDatabase::default();
+        db.state().offline = true;
+        let mut app = App::for_test_with(db.clone());
+
+        // First attempt fails and schedules a retry: keys are held off meanwhile
+        app.start_initial_load().await;
+        assert!(app.is_loading());
+        app.notify_loading();
+
+        db.state().offline = false;
+        app.next_load_retry = Some(Instant::now());
+        app.check_load_retry().await;
+        assert!(!app.is_loading());
+        // The first attempt's errors expire on their own; the cue goes at once
+        assert!(app.notifications.iter().all(|n| n.message != t("notice.still_loading")));
+    }
+
+    #[tokio::test]
+    async fn home_enter_always_responds() {
+        let db = MockDatabase::default();
+        db.state().products = vec![Product::sample("segfault", 2200)];
+        let mut app = App::for_test_with(db.clone());
+
+        // Nothing loaded for this region
+        app.enter_shop_from_home().await;
+        assert!(app.notification().is_some_and(|n| n.contains("change region")));
+
+        // Still fetching after a region switch
+        app.loading = LoadingState::Loading;
+        app.enter_shop_from_home().await;
+        assert_eq!(app.notification(), Some("still loading…"));
+
+        // A failed load is retried, which now succeeds
+        app.loading = LoadingState::Idle;
+        app.load_failed = true;
+        app.enter_shop_from_home().await;
+        assert!(!app.load_failed);
+        assert_eq!(app.products.len(), 1);
+
+        app.enter_shop_from_home().await;
+        assert_eq!(app.current_tab, Tab::Shop);
+    }
+
+    #[test]
+    fn input_beyond_the_field_cap_is_rejected() {
+        let mut app = App::for_test();
+        app.active_input = InputField::PostalCode;
+        for c in "1234567890123456789".chars() {
+            app.handle_input_char(c);
+        }
+        assert_eq!(app.shipping_address.postal_code, "1234567890123456");
+
+        // Multi-byte characters count once each
+        app.active_input = InputField::City;
+        for _ in 0..60 {
+            app.handle_input_char('ş');
+        }
+        assert_eq!(app.shipping_address.city.chars().count(), 56);
+    }
+
+    #[test]
+    fn arrows_move_the_caret_within_a_field() {
+        let mut app = App::for_test();
+        app.active_input = InputField::Street1;
+        for c in "1 Mn Şt".chars() {
+            app.handle_input_char(c);
+        }
+        app.move_input_cursor(-4);
+        app.handle_input_char('a');
+        assert_eq!(app.shipping_address.street_1, "1 Man Şt");
+        assert_eq!(app.input_cursor(), 4);
+
+        app.input_cursor_to(true);
+        app.handle_input_backspace();
+        app.input_cursor_to(false);
+        app.handle_input_backspace();
+        app.handle_input_char('#');
+        assert_eq!(app.shipping_address.street_1, "#1 Man Ş");
+
+        // Another field starts with the caret at its end
+        app.active_input = InputField::City;
+        app.handle_input_char('x');
+        app.active_input = InputField::Street1;
+        assert_eq!(app.input_cursor(), 8);
+    }
+
+    #[test]
+    fn pasting_follows_each_fields_rules() {
+        let mut app = App::for_test();
+        app.active_input = InputField::CardNumber;
+        app.handle_paste("4242 4242 4242 4242 99\n");
+        assert_eq!(app.payment_info.card_number, "4242424242424242");
+
+        app.active_input = InputField::Expiry;
+        app.handle_paste("12/27");
+        assert_eq!(app.payment_info.expiry_display(), "12/27");
+
+        app.active_input = InputField::Street1;
+        app.handle_paste("1 Main St\r\n");
+        assert_eq!(app.shipping_address.street_1, "1 Main St");
+
+        // Nothing lands outside a field
+        app.active_input = InputField::None;
+        app.handle_paste("ignored");
+        assert_eq!(app.shipping_address.street_1, "1 Main St");
+    }
+
+    #[test]
+    fn quitting_with_a_cart_asks_first() {
+        let mut app = App::for_test();
+        app.request_quit();
+        assert!(!app.running);
+
+        let mut app = app_with_cart();
+        app.request_quit();
+        assert!(app.running && app.confirm_quit);
+        app.request_quit();
+        assert!(!app.running);
+    }
+
+    #[test]
+    fn regrouping_keeps_the_selected_product() {
+        let mut app = App::for_test();
+        let mut light = Product::sample("a light one", 2000);
+        light.roast_level = Some(RoastLevel::Light);
+        app.products = vec![Product::sample("segfault", 2200), light];
+        app.selected_product_index = 1;
+
+        app.toggle_shop_grouping();
+        assert_eq!(app.products[0].name, "a light one");
+        assert_eq!(app.selected_product_index, 0);
+        app.toggle_shop_grouping();
+        assert_eq!(app.selected_product().unwrap().name, "a light one");
+    }
+
+    #[test]
+    fn favorites_are_saved_per_identity_and_filter_the_shop() {
+        let mut app = App::for_test();
+        app.products = vec![Product::sample("segfault", 2200), Product::sample("404", 1800)];
+        app.toggle_favorites_only();
+        assert!(!app.favorites_only);
+
+        app.selected_product_index = 1;
+        app.toggle_favorite();
+        app.toggle_favorites_only();
+        assert_eq!(app.visible_product_indices(), vec![1]);
+
+        let mut restored = App::for_test();
+        restored.store = app.store.clone();
+        restored.load_favorites();
+        assert!(restored.favorites.contains(&app.products[1].id));
+
+        // Unfavoriting the last one drops the file
+        app.toggle_favorite();
+        assert!(app.store.read("favorites-00000000.json").is_none());
+    }
+
+    #[test]
+    fn last_decrement_asks_before_removing() {
+        let mut app = app_with_cart();
+        app.cart.items[0].quantity = 2;
+        app.decrement_selected_item();
+        assert_eq!(app.cart.items[0].quantity, 1);
+
+        app.decrement_selected_item();
+        assert_eq!(app.confirm_remove, Some(app.cart.items[0].product.id));
+        assert_eq!(app.cart.total_items(), 1);
+
+        app.decrement_selected_item();
+        assert!(app.cart.is_empty());
+        assert_eq!(app.confirm_remove, None);
+    }
+
+    #[tokio::test]
+    async fn refresh_bypasses_the_product_cache() {
+        let db = crate::db::MockDatabase::default();
+        db.state().products = vec![Product::sample("segfault", 2200)];
+        let mut app = App::for_test_with(db.clone());
+        app.load_products().await.unwrap();
+
+        db.state().products.push(Product::sample("404", 1800));
+        app.load_products().await.unwrap();
+        assert_eq!(app.products.len(), 1);
+
+        // The fetch runs in the background, so keys keep working meanwhile
+        app.refresh_products();
+        assert!(app.is_fetching_products() && !app.is_loading());
+        finish_background_load(&mut app).await;
+        assert_eq!(app.products.len(), 2);
+    }
+
+    #[test]
+    fn splash_length_follows_the_env_and_no_splash() {
+        let mut app = App::for_test();
+        app.configure_splash(None, false);
+        assert!(app.show_splash);
+        assert_eq!(app.splash_duration, Duration::from_secs(DEFAULT_SPLASH_SECS));
+
+        app.configure_splash(Some("2"), false);
+        assert!(app.show_splash);
+        assert_eq!(app.splash_duration, Duration::from_secs(2));
+
+        // Garbage falls back to the default; 0 and --no-splash skip it
+        app.configure_splash(Some("soon"), false);
+        assert_eq!(app.splash_duration, Duration::from_secs(DEFAULT_SPLASH_SECS));
+        app.configure_splash(Some("0"), false);
+        assert!(!app.show_splash);
+        app.configure_splash(Some("2"), true);
+        assert!(!app.show_splash);
+    }
+
+    #[tokio::test]
+    async fn leaving_the_tab_drops_the_address_undo() {
+        let mut app = app_with_cart();
+        let address = SavedAddress::from_shipping(&ShippingAddress::default(), "test");
+        app.checkout_step = CheckoutStep::Shipping;
+
+        app.saved_addresses = vec![address.clone()];
+        app.remove_selected_address().await;
+        assert!(app.undo_deleted_address.is_some());
+        app.switch_tab(Tab::Shop);
+        assert!(app.undo_deleted_address.is_none());
+        assert!(app.notification().is_none());
+
+        app.saved_addresses = vec![address];
+        app.remove_selected_address().await;
+        app.go_back();
+        assert_eq!(app.current_tab, Tab::Cart);
+        assert!(app.undo_deleted_address.is_none());
+    }
+
+    #[test]
+    fn length_counter_appears_near_the_cap() {
+        assert_eq!(InputField::Name.length_counter(50), None);
+        assert_eq!(InputField::Name.length_counter(52), Some("52/64".to_string()));
+        assert_eq!(InputField::CardNumber.length_counter(16), None);
+    }
+
+    #[test]
+    fn cart_is_saved_on_change_and_restored_per_identity() {
+        let mut app = app_with_cart();
+        app.check_cart_saved();
+        assert!(app.store.read("cart-00000000.json").is_some());
+
+        // Restored by the same identity
+        let mut restored = App::for_test();
+        restored.store = app.store.clone();
+        restored.restore_cart();
+        assert_eq!(restored.cart.total_items(), 1);
+
+        // Another identity on the same machine starts empty
+        let mut other = App::for_test();
+        other.store = app.store.clone();
+        other.identity.short_id = "ffffffff".to_string();
+        other.restore_cart();
+        assert!(other.cart.is_empty());
+
+        // Emptying the cart drops the file; a malformed file is ignored
+        app.cart.clear();
+        app.check_cart_saved();
+        assert!(app.store.read("cart-00000000.json").is_none());
+        app.store.write("cart-00000000.json", "{not json").unwrap();
+        restored.cart.clear();
+        restored.restore_cart();
+        assert!(restored.cart.is_empty());
+    }
+
+    #[tokio::test]
+    async fn stale_cart_restored_before_products_load_is_validated_once_they_do() {
+        let db = MockDatabase::default();
+        let segfault = Product::sample("segfault", 2200);
+        let nil = Product::sample("nil", 1800);
+        let mut cart = Cart::new();
+        cart.add_item(segfault.clone(), 1);
+        cart.add_item(nil, 1);
+        let mut saved = PersistedCart::new(cart);
+        saved.saved_at -= chrono::Duration::days(DEFAULT_CART_MAX_AGE_DAYS + 1);
+
+        let mut app = App::for_test_with(db.clone());
+        app.store.write("cart-00000000.json", &serde_json::to_string(&saved).unwrap()).unwrap();
+        app.restore_cart();
+        assert_eq!(app.cart.items.len(), 2);
+
+        db.state().products = vec![segfault];
+        app.load_products().await.unwrap();
+        assert_eq!(app.cart.items.len(), 1);
+        assert_eq!(app.cart.items[0].product.name, "segfault");
+        assert!(app.notification().unwrap().contains("removed 1 unavailable"));
+    }
+
+    #[test]
+    fn notifications_stack_and_expire_on_their_own() {
+        let mut app = App::for_test();
+        app.notify(Severity::Error, "couldn't restore address");
+        app.notify(Severity::Success, "address saved");
+        app.notify(Severity::Success, "address saved");
+        assert_eq!(app.notifications.len(), 2);
+        assert_eq!(app.notification(), Some("address saved"));
+
+        app.request_reset();
+        app.clock = AnimationClock::Fixed(NOTIFICATION_DURATION);
+        app.check_notifications();
+        // Only the prompt outlives the timeout, until it's answered
+        assert_eq!(app.notification(), Some(t("reset.prompt")));
+        app.cancel_reset();
+        assert_eq!(app.notification(), None);
+    }
+
+    #[test]
+    fn b_goes_back_through_visited_tabs() {
+        let mut app = App::for_test();
+        app.switch_tab(Tab::Shop);
+        app.switch_tab(Tab::Shop);
+        app.switch_tab(Tab::Cart);
+        app.switch_tab(Tab::Shop);
+        assert_eq!(app.tab_history, vec![Tab::Home, Tab::Shop, Tab::Cart]);
+
+        app.go_back();
+        assert_eq!(app.current_tab, Tab::Cart);
+        app.go_back();
+        assert_eq!(app.current_tab, Tab::Shop);
+        app.go_back();
+        assert_eq!(app.current_tab, Tab::Home);
+        // Nothing left to go back to
+        app.go_back();
+        assert_eq!(app.current_tab, Tab::Home);
+    }
+
+    #[test]
+    fn reset_wipes_the_store_and_local_state() {
+        let mut app = app_with_cart();
+        app.identity = SshIdentity::generated(&app.store);
+        let wiped_identity = app.identity.fingerprint.clone();
+        app.persist_cart();
+        app.toggle_confirm_before_order();
+        app.request_reset();
+        assert!(app.confirm_reset);
+
+        app.reset_local_data();
+        assert!(app.cart.is_empty());
+        assert!(!app.confirm_reset);
+        let cleared = app.notification().unwrap();
+        assert!(cleared.contains("machine-id") && cleared.contains("settings.json"), "{cleared}");
+        assert!(app.store.read(&app.cart_file()).is_none());
+
+        // Preferences are back to their defaults, and the generated id is a fresh one
+        assert_eq!(app.settings, Settings::default());
+        assert_ne!(app.identity.fingerprint, wiped_identity);
+        app.toggle_confirm_before_order();
+        app.toggle_confirm_before_order();
+        assert_eq!(App::load_settings(&app.store), Settings::default());
+    }
+
+    #[tokio::test]
+    async fn gift_orders_bill_the_purchaser_and_ship_to_the_recipient() {
+        let mut app = app_with_cart();
+        app.toggle_gift_order();
+        let recipient = ShippingAddress { name: "ada".to_string(), ..ShippingAddress::default() };
+        app.saved_addresses = vec![SavedAddress::from_shipping(&recipient, "test")];
+        app.checkout_step = CheckoutStep::Shipping;
+
+        // A saved address leads to the gift message before payment, so browser payers get it too
+        app.select_address_option();
+        assert_eq!(app.checkout_step, CheckoutStep::Gift);
+        assert_eq!(app.active_input, InputField::GiftMessage);
+        app.handle_paste("happy birthday\r\nlove, grace");
+        assert_eq!(app.gift_message, "happy birthday\nlove, grace");
+        app.next_checkout_step().await;
+        assert_eq!(app.checkout_step, CheckoutStep::Payment);
+        app.prev_checkout_step();
+        assert_eq!(app.checkout_step, CheckoutStep::Gift);
+        app.next_checkout_step().await;
+
+        app.payment_method = Some(PaymentMethod::Ssh);
+        app.payment_info = PaymentInfo {
+            name: "grace".to_string(),
+            email: "grace@example.com".to_string(),
+            card_number: "4242424242424242".to_string(),
+            expiry_month: "12".to_string(),
+            expiry_year: "2030".to_string(),
+            cvv: "123".to_string(),
+        };
+        app.next_checkout_step().await;
+        let order = app.pending_order.as_ref().unwrap();
+        assert_eq!(order.shipping_address.name, "ada");
+        let gift = order.gift.as_ref().unwrap();
+        assert_eq!(gift.purchaser_name.as_deref(), Some("grace"));
+        assert_eq!(gift.purchaser_email.as_deref(), Some("grace@example.com"));
+        assert!(order.to_receipt_text().contains("gift message: happy birthday\nlove, grace"));
+
+        // Stored in the orders table's flat gift columns, and read back from them
+        let row = serde_json::to_value(order).unwrap();
+        assert_eq!(row["is_gift"], true);
+        assert_eq!(row["purchaser_email"], "grace@example.com");
+        assert_eq!(row["gift_message"], "happy birthday\nlove, grace");
+        assert!(row.get("gift").is_none());
+        let stored: Order = serde_json::from_value(row).unwrap();
+        assert_eq!(stored.gift.as_ref(), Some(gift));
+
+        // Browser checkout collects the purchaser itself, so none is stored
+        app.payment_method = Some(PaymentMethod::Browser);
+        let gift = app.build_order().gift.unwrap();
+        assert_eq!((gift.purchaser_name, gift.purchaser_email), (None, None));
+        app.payment_method = Some(PaymentMethod::Ssh);
+
+        // Other fields stay on one line
+        app.active_input = InputField::PaymentName;
+        app.payment_info.name.clear();
+        app.handle_paste("grace\nhopper");
+        assert_eq!(app.payment_info.name, "gracehopper");
+
+        // Regular orders keep the single-address flow
+        app.prev_checkout_step();
+        app.toggle_gift_order();
+        app.next_checkout_step().await;
+        let order = app.pending_order.as_ref().unwrap();
+        assert!(order.gift.is_none());
+        assert_eq!(serde_json::to_value(order).unwrap()["is_gift"], false);
+        assert_eq!(InputField::Cvv.next_payment(), InputField::PaymentName);
+    }
+
+    #[tokio::test]
+    async fn checkout_can_be_driven_without_a_terminal() {
+        let mut app = app_with_cart();
+        app.try_next_checkout_step().await.unwrap();
+        assert_eq!(app.checkout_step, CheckoutStep::Shipping);
+
+        // "add new address" is the only option with nothing saved
+        app.select_address_option();
+        assert_eq!(
+            app.try_next_checkout_step().await,
+            Err("name can't be empty".to_string())
+        );
+        assert_eq!(app.notification(), None);
+        app.shipping_address = ShippingAddress {
+            name: "ada".to_string(),
+            street_1: "1 loop st".to_string(),
+            city: "tashkent".to_string(),
+            country: "uz".to_string(),
+            phone: "123".to_string(),
+            postal_code: "100000".to_string(),
+            ..ShippingAddress::default()
+        };
+        app.try_next_checkout_step().await.unwrap();
+        assert_eq!(app.checkout_step, CheckoutStep::Payment);
+
+        app.payment_method = Some(PaymentMethod::Ssh);
+        assert_eq!(app.validate_payment(), Err("name can't be empty".to_string()));
+        app.payment_info = PaymentInfo {
+            name: "ada".to_string(),
+            email: "ada@example.com".to_string(),
+            card_number: "4242424242424224".to_string(),
+            expiry_month: "12".to_string(),
+            expiry_year: "2030".to_string(),
+            cvv: "123".to_string(),
+        };
+        assert_eq!(app.try_next_checkout_step().await, Err("card number is invalid".to_string()));
+        app.payment_info.card_number = "4242424242424242".to_string();
+        app.payment_info.expiry_year = "2020".to_string();
+        assert_eq!(app.validate_payment(), Err("card has expired".to_string()));
+        app.payment_info = PaymentInfo {
+            name: "ada".to_string(),
+            email: "ada@example.com".to_string(),
+            card_number: "4242424242424242".to_string(),
+            expiry_month: "12".to_string(),
+            expiry_year: "2030".to_string(),
+            cvv: "123".to_string(),
+        };
+        app.try_next_checkout_step().await.unwrap();
+        assert_eq!(app.checkout_step, CheckoutStep::Confirmation);
+        assert!(app.pending_order.is_some());
+
+        // The first enter only arms the order by default; the second places it
+        app.try_next_checkout_step().await.unwrap();
+        assert!(app.order_confirm_armed);
+        assert!(!app.cart.is_empty());
+        app.try_next_checkout_step().await.unwrap();
+        assert!(app.cart.is_empty());
+        assert_eq!(
+            app.try_next_checkout_step().await,
+            Err("your cart is empty".to_string())
+        );
+    }
+
+    #[test]
+    fn dismissed_notices_stay_dismissed_until_reset() {
+        let mut app = App::for_test();
+        app.notify_once(NOTICE_FALLBACK_IDENTITY, "no SSH key found");
+        assert!(app.notice.is_some());
+
+        app.dismiss_notice();
+        assert!(app.notice.is_none());
+        // A later launch reads the dismissal back from the store
+        app.settings = App::load_settings(&app.store);
+        app.notify_once(NOTICE_FALLBACK_IDENTITY, "no SSH key found");
+        assert!(app.notice.is_none());
+
+        app.reset_dismissed_notices();
+        app.notify_once(NOTICE_FALLBACK_IDENTITY, "no SSH key found");
+        assert!(app.notice.is_some());
+    }
+
+    #[tokio::test]
+    async fn single_enter_places_the_order_when_confirmation_is_off() {
+        let mut app = app_with_cart();
+        app.toggle_confirm_before_order();
+        assert_eq!(App::load_settings(&app.store), app.settings);
+        app.checkout_step = CheckoutStep::Confirmation;
+
+        app.try_next_checkout_step().await.unwrap();
+        assert!(app.cart.is_empty());
+        assert!(app.placed_order.is_some());
+    }
+
+    #[tokio::test]
+    async fn emptying_the_cart_mid_checkout_returns_to_the_cart() {
+        let mut app = app_with_cart();
+        app.checkout_step = CheckoutStep::Payment;
+        app.payment_method = Some(PaymentMethod::Browser);
+        app.cart.clear();
+
+        app.next_checkout_step().await;
+        assert_eq!(app.checkout_step, CheckoutStep::Cart);
+        assert!(app.pending_order.is_none());
+        assert_eq!(app.notification(), Some("your cart is empty"));
+
+        // Same guard from the main loop, e.g. a stale cart pruned on the confirmation step
+        let mut app = app_with_cart();
+        app.checkout_step = CheckoutStep::Confirmation;
+        app.cart.clear();
+        app.check_empty_checkout();
+        assert_eq!(app.checkout_step, CheckoutStep::Cart);
+    }
+
+    #[test]
+    fn removing_the_selected_cart_line_drops_its_whole_quantity() {
+        let mut app = app_with_cart();
+        app.cart.add_item(Product::sample("404", 1800), 1);
+        app.cart.items[0].quantity = 12;
+        app.cart_item_index = 1;
+
+        app.remove_selected_item();
+        assert_eq!(app.cart.items.len(), 1);
+        assert_eq!(app.cart.items[0].quantity, 12);
+        assert_eq!(app.cart_item_index, 0);
+
+        app.remove_selected_item();
+        assert!(app.cart.is_empty());
+    }
+
+    #[test]
+    fn filtering_away_the_selected_product_resets_its_quantity() {
+        let mut app = App::for_test();
+        app.products = vec![Product::sample("segfault", 2200), Product::sample("404", 1800)];
+        app.selected_product_index = 1;
+        app.product_quantity = 3;
+        app.quantity_input = "3".to_string();
+        app.description_expanded = true;
+
+        for c in "seg".chars() {
+            app.push_shop_filter(c);
+        }
+        assert_eq!(app.selected_product_index, 0);
+        assert_eq!(app.product_quantity, 1);
+        assert!(app.quantity_input.is_empty());
+        assert!(!app.description_expanded);
+    }
+
+    #[test]
+    fn clamp_selection_handles_empty_lists() {
+        let mut app = App::for_test();
+        app.selected_product_index = 4;
+        app.cart_item_index = 2;
+        app.address_select_index = 3;
+        app.payment_option_index = 7;
+
+        app.clamp_selection();
+        assert_eq!(app.selected_product_index, 0);
+        assert_eq!(app.cart_item_index, 0);
+        assert_eq!(app.address_select_index, 0);
+        assert_eq!(app.payment_option_index, 1);
+    }
+
+    #[test]
+    fn fixed_clock_pins_blink_state() {
+        assert!(AnimationClock::Fixed(Duration::from_millis(200)).blink_on());
+        assert!(!AnimationClock::Fixed(Duration::from_millis(700)).blink_on());
+        assert!(AnimationClock::Fixed(Duration::from_millis(1000)).blink_on());
+    }
+}
@@ -0,0 +1,44 @@
+use base64::Engine;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Where copied text ended up
+pub enum CopyOutcome {
+    /// Sent to the terminal clipboard
+    Clipboard,
+    /// No clipboard available - written to this file instead
+    File(PathBuf),
+}
+
+/// Copy text to the clipboard, falling back to a file when no clipboard is available
+pub fn copy_or_save(text: &str, file_name: &str) -> io::Result<CopyOutcome> {
+    if clipboard_available() && copy_osc52(text).is_ok() {
+        return Ok(CopyOutcome::Clipboard);
+    }
+    save_to_file(text, file_name).map(CopyOutcome::File)
+}
+
+/// Write text to a file in the user's home directory (or the temp dir)
+pub fn save_to_file(text: &str, file_name: &str) -> io::Result<PathBuf> {
+    let dir = dirs::home_dir().unwrap_or_else(std::env::temp_dir);
+    let path = dir.join(file_name);
+    fs::write(&path, text)?;
+    Ok(path)
+}
+
+/// OSC 52 works over SSH, but only on real terminals
+fn clipboard_available() -> bool {
+    match std::env::var("TERM") {
+        Ok(term) => !term.is_empty() && term != "dumb",
+        Err(_) => false,
+    }
+}
+
+/// Copy via the OSC 52 escape sequence (handled by the user's terminal emulator)
+fn copy_osc52(text: &str) -> io::Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()
+}
@@ -45,13 +45,12 @@ impl<T: Clone> Cache<T> {
     /// Check if cache has a valid (non-expired) entry
     #[allow(dead_code)]
     pub fn has(&self, key: &str) -> bool {
-        self.entries.get(key).map_or(false, |entry| {
+        self.entries.get(key).is_some_and(|entry| {
             Instant::now() < entry.expires_at
         })
     }
 
     /// Remove an entry from the cache
-    #[allow(dead_code)]
     pub fn invalidate(&mut self, key: &str) {
         self.entries.remove(key);
     }
@@ -63,19 +62,23 @@ impl<T: Clone> Cache<T> {
     }
 }
 
-/// Data cache for the application - caches products and regions
+/// Data cache for the application - caches products, regions and account history
 pub struct DataCache {
     pub products: Cache<Vec<crate::models::Product>>,
     pub regions: Cache<Vec<crate::models::Region>>,
+    pub orders: Cache<Vec<crate::models::Order>>,
+    pub subscriptions: Cache<Vec<crate::models::Subscription>>,
 }
 
 impl DataCache {
     /// Create a new data cache with default TTLs
-    /// Products: 5 minutes, Regions: 30 minutes
+    /// Products: 5 minutes, Regions: 30 minutes, Orders/Subscriptions: 1 minute
     pub fn new() -> Self {
         Self {
             products: Cache::new(300),  // 5 minutes
             regions: Cache::new(1800),  // 30 minutes
+            orders: Cache::new(60),  // 1 minute
+            subscriptions: Cache::new(60),  // 1 minute
         }
     }
 
@@ -89,6 +92,11 @@ impl DataCache {
         self.products.set(format!("products:{}", region_id), products);
     }
 
+    /// Forget a region's cached products, so the next load fetches them
+    pub fn invalidate_products(&mut self, region_id: &str) {
+        self.products.invalidate(&format!("products:{}", region_id));
+    }
+
     /// Get regions from cache
     pub fn get_regions(&self) -> Option<Vec<crate::models::Region>> {
         self.regions.get("regions")
@@ -98,6 +106,32 @@ impl DataCache {
     pub fn set_regions(&mut self, regions: Vec<crate::models::Region>) {
         self.regions.set("regions".to_string(), regions);
     }
+
+    /// Get a user's loaded order history from cache
+    pub fn get_orders(&self, user_id: &str) -> Option<Vec<crate::models::Order>> {
+        self.orders.get(&format!("orders:{}", user_id))
+    }
+
+    /// Cache a user's loaded order history
+    pub fn set_orders(&mut self, user_id: &str, orders: Vec<crate::models::Order>) {
+        self.orders.set(format!("orders:{}", user_id), orders);
+    }
+
+    /// Get a user's subscriptions from cache
+    pub fn get_subscriptions(&self, user_id: &str) -> Option<Vec<crate::models::Subscription>> {
+        self.subscriptions.get(&format!("subscriptions:{}", user_id))
+    }
+
+    /// Cache a user's subscriptions
+    pub fn set_subscriptions(&mut self, user_id: &str, subscriptions: Vec<crate::models::Subscription>) {
+        self.subscriptions.set(format!("subscriptions:{}", user_id), subscriptions);
+    }
+
+    /// Forget a user's cached orders and subscriptions, e.g. after placing an order
+    pub fn invalidate_account(&mut self, user_id: &str) {
+        self.orders.invalidate(&format!("orders:{}", user_id));
+        self.subscriptions.invalidate(&format!("subscriptions:{}", user_id));
+    }
 }
 
 impl Default for DataCache {
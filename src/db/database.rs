@@ -0,0 +1,51 @@
+use crate::models::{Order, Product, Region, SavedAddress, Subscription, SubscriptionStatus};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Backend operations the app depends on
+/// Implemented by `SupabaseClient` in production and `MockDatabase` in tests
+#[async_trait]
+pub trait Database: Send + Sync {
+    /// Fetch all products (optionally filtered by region)
+    async fn get_products(&self, region_id: Option<&str>) -> Result<Vec<Product>>;
+
+    /// Products whose name contains `query` (case-insensitive), optionally in one region
+    #[allow(dead_code)]
+    async fn search_products(&self, query: &str, region_id: Option<&str>) -> Result<Vec<Product>>;
+
+    /// Fetch all available regions
+    async fn get_regions(&self) -> Result<Vec<Region>>;
+
+    /// Fetch a page of orders for a user, newest first
+    async fn get_orders(&self, user_id: &str, limit: usize, offset: usize) -> Result<Vec<Order>>;
+
+    /// Fetch subscriptions for a user
+    #[allow(dead_code)]
+    async fn get_subscriptions(&self, user_id: &str) -> Result<Vec<Subscription>>;
+
+    /// Create a new order
+    #[allow(dead_code)]
+    async fn create_order(&self, order: &Order) -> Result<Order>;
+
+    /// Create a new subscription
+    async fn create_subscription(&self, subscription: &Subscription) -> Result<Subscription>;
+
+    /// Pause, resume or cancel a subscription
+    async fn update_subscription_status(&self, id: &uuid::Uuid, status: SubscriptionStatus) -> Result<()>;
+
+    /// Whether the backend is reachable
+    #[allow(dead_code)]
+    async fn health_check(&self) -> Result<bool>;
+
+    /// Fetch saved addresses for a user (by SSH fingerprint)
+    async fn get_saved_addresses(&self, user_fingerprint: &str) -> Result<Vec<SavedAddress>>;
+
+    /// Save a new address for a user
+    async fn save_address(&self, address: &SavedAddress) -> Result<SavedAddress>;
+
+    /// Update a saved address in place, matched by its ID
+    async fn update_address(&self, address: &SavedAddress) -> Result<SavedAddress>;
+
+    /// Delete a saved address by ID
+    async fn delete_address(&self, address_id: &uuid::Uuid) -> Result<()>;
+}
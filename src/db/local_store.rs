@@ -0,0 +1,96 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Small file store in the app's data directory
+/// Used for state that should survive restarts but doesn't belong in Supabase
+#[derive(Debug, Clone)]
+pub struct LocalStore {
+    dir: PathBuf,
+}
+
+impl LocalStore {
+    /// Store in the platform data dir (e.g. ~/.local/share/anora)
+    /// Falls back to the temp dir when there's no home/data dir (containers, minimal envs)
+    pub fn new() -> Self {
+        let base = dirs::data_dir().unwrap_or_else(std::env::temp_dir);
+        Self::at(base.join("anora"))
+    }
+
+    /// Store rooted at a specific directory
+    pub fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The store directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of a file in the store
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    /// Read a file, if it exists
+    pub fn read(&self, name: &str) -> Option<String> {
+        fs::read_to_string(self.path(name)).ok()
+    }
+
+    /// Write a file, creating the store directory if needed
+    pub fn write(&self, name: &str, contents: &str) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(name), contents)
+    }
+
+    /// Delete everything in the store, returning the names of the removed files
+    /// (a missing store directory is not an error)
+    pub fn clear(&self) -> io::Result<Vec<String>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            result => result?,
+        };
+        let mut removed = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                fs::remove_dir_all(entry.path())?;
+            } else {
+                fs::remove_file(entry.path())?;
+            }
+            removed.push(entry.file_name().to_string_lossy().to_string());
+        }
+        removed.sort();
+        Ok(removed)
+    }
+
+    /// Remove a file (missing files are not an error)
+    pub fn remove(&self, name: &str) -> io::Result<()> {
+        match fs::remove_file(self.path(name)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for LocalStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clear_removes_everything_and_reports_it() {
+        let store = LocalStore::at(std::env::temp_dir().join(format!("anora-test-{}", uuid::Uuid::new_v4())));
+        assert_eq!(store.clear().unwrap(), Vec::<String>::new());
+
+        store.write("cart.json", "{}").unwrap();
+        store.write("machine-id", "abc").unwrap();
+        assert_eq!(store.clear().unwrap(), ["cart.json", "machine-id"]);
+        assert!(store.read("cart.json").is_none());
+    }
+}
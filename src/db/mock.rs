@@ -0,0 +1,146 @@
+use super::Database;
+use crate::models::{Order, Product, Region, SavedAddress, Subscription, SubscriptionStatus};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Backend contents held by a `MockDatabase`
+#[derive(Debug, Default)]
+pub struct MockState {
+    pub products: Vec<Product>,
+    pub regions: Vec<Region>,
+    pub orders: Vec<Order>,
+    pub subscriptions: Vec<Subscription>,
+    pub addresses: Vec<SavedAddress>,
+    /// Make every call fail, like an unreachable backend
+    pub offline: bool,
+}
+
+/// In-memory database for tests
+/// Clones share state, so a test can keep a handle after giving one to the `App`
+#[derive(Debug, Clone, Default)]
+pub struct MockDatabase {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockDatabase {
+    pub fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap()
+    }
+
+    fn online(&self) -> Result<MutexGuard<'_, MockState>> {
+        let state = self.state();
+        if state.offline {
+            Err(anyhow!("mock database is offline"))
+        } else {
+            Ok(state)
+        }
+    }
+}
+
+#[async_trait]
+impl Database for MockDatabase {
+    async fn get_products(&self, region_id: Option<&str>) -> Result<Vec<Product>> {
+        Ok(self
+            .online()?
+            .products
+            .iter()
+            .filter(|p| p.in_stock && region_id.is_none_or(|region| p.region_id == region))
+            .cloned()
+            .collect())
+    }
+
+    async fn search_products(&self, query: &str, region_id: Option<&str>) -> Result<Vec<Product>> {
+        let query = query.to_lowercase();
+        Ok(self
+            .get_products(region_id)
+            .await?
+            .into_iter()
+            .filter(|p| p.name.to_lowercase().contains(&query))
+            .collect())
+    }
+
+    async fn get_regions(&self) -> Result<Vec<Region>> {
+        Ok(self.online()?.regions.clone())
+    }
+
+    async fn get_orders(&self, user_id: &str, limit: usize, offset: usize) -> Result<Vec<Order>> {
+        Ok(self
+            .online()?
+            .orders
+            .iter()
+            .filter(|o| o.user_id.to_string() == user_id)
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_subscriptions(&self, user_id: &str) -> Result<Vec<Subscription>> {
+        Ok(self
+            .online()?
+            .subscriptions
+            .iter()
+            .filter(|s| s.user_id.to_string() == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn create_order(&self, order: &Order) -> Result<Order> {
+        self.online()?.orders.push(order.clone());
+        Ok(order.clone())
+    }
+
+    async fn create_subscription(&self, subscription: &Subscription) -> Result<Subscription> {
+        self.online()?.subscriptions.push(subscription.clone());
+        Ok(subscription.clone())
+    }
+
+    async fn update_subscription_status(&self, id: &uuid::Uuid, status: SubscriptionStatus) -> Result<()> {
+        let mut state = self.online()?;
+        let subscription = state
+            .subscriptions
+            .iter_mut()
+            .find(|s| s.id == *id)
+            .ok_or_else(|| anyhow!("no such subscription"))?;
+        subscription.status = status;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        Ok(!self.state().offline)
+    }
+
+    async fn get_saved_addresses(&self, user_fingerprint: &str) -> Result<Vec<SavedAddress>> {
+        Ok(self
+            .online()?
+            .addresses
+            .iter()
+            .filter(|a| a.user_fingerprint == user_fingerprint)
+            .cloned()
+            .collect())
+    }
+
+    async fn save_address(&self, address: &SavedAddress) -> Result<SavedAddress> {
+        let mut saved = address.clone();
+        saved.id = Some(uuid::Uuid::new_v4());
+        self.online()?.addresses.push(saved.clone());
+        Ok(saved)
+    }
+
+    async fn update_address(&self, address: &SavedAddress) -> Result<SavedAddress> {
+        let mut state = self.online()?;
+        let existing = state
+            .addresses
+            .iter_mut()
+            .find(|a| a.id.is_some() && a.id == address.id)
+            .ok_or_else(|| anyhow!("no such address"))?;
+        *existing = address.clone();
+        Ok(address.clone())
+    }
+
+    async fn delete_address(&self, address_id: &uuid::Uuid) -> Result<()> {
+        self.online()?.addresses.retain(|a| a.id != Some(*address_id));
+        Ok(())
+    }
+}
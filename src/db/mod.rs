@@ -1,7 +1,15 @@
 mod cache;
+mod database;
+mod local_store;
+#[cfg(test)]
+mod mock;
 mod ssh_identity;
 mod supabase;
 
 pub use cache::DataCache;
+pub use database::Database;
+pub use local_store::LocalStore;
+#[cfg(test)]
+pub use mock::MockDatabase;
 pub use ssh_identity::SshIdentity;
 pub use supabase::SupabaseClient;
@@ -1,7 +1,15 @@
 use base64::Engine;
 use sha2::{Digest, Sha256};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use super::LocalStore;
+
+/// systemd/dbus machine id (Linux)
+const MACHINE_ID_PATH: &str = "/etc/machine-id";
+/// Generated machine id persisted in the local store
+const PERSISTED_ID_FILE: &str = "machine-id";
 
 /// SSH-based user identity
 /// Uses the user's SSH public key fingerprint as a unique identifier
@@ -9,8 +17,7 @@ use std::path::PathBuf;
 pub struct SshIdentity {
     /// SHA256 fingerprint of the SSH public key
     pub fingerprint: String,
-    /// Short version for display (first 8 chars)
-    #[allow(dead_code)]
+    /// Short version for display and per-identity file names (first 8 chars)
     pub short_id: String,
 }
 
@@ -41,7 +48,7 @@ impl SshIdentity {
         
         // SSH public key format: "type base64-key comment"
         // We hash the base64 key part for the fingerprint
-        let parts: Vec<&str> = content.trim().split_whitespace().collect();
+        let parts: Vec<&str> = content.split_whitespace().collect();
         if parts.len() < 2 {
             return None;
         }
@@ -64,17 +71,45 @@ impl SshIdentity {
     /// Generate a fallback identity based on machine-specific data
     /// Used when no SSH key is available
     pub fn fallback_identity() -> Self {
-        // Use username + home directory as fallback identifier
+        Self::fallback_in(&LocalStore::new())
+    }
+
+    /// Fallback identity whose generated id (if it comes to that) lives in `store`
+    pub fn fallback_in(store: &LocalStore) -> Self {
         let username = std::env::var("USER")
             .or_else(|_| std::env::var("USERNAME"))
             .unwrap_or_else(|_| "anonymous".to_string());
-        
-        // Use home directory path as a machine-specific identifier
-        let home_path = dirs::home_dir()
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_else(|| "unknown".to_string());
 
-        let combined = format!("{}@{}", username, home_path);
+        Self::fallback_from(&username, dirs::home_dir(), Path::new(MACHINE_ID_PATH), store)
+    }
+
+    /// The SSH key identity, else the fallback for this store; re-run after the store is wiped
+    pub fn resolve(store: &LocalStore) -> Self {
+        Self::from_ssh_key().unwrap_or_else(|| Self::fallback_in(store))
+    }
+
+    /// Identity from a generated id alone, as on a machine with no home dir or machine id
+    #[cfg(test)]
+    pub fn generated(store: &LocalStore) -> Self {
+        Self::fallback_from("test", None, Path::new(""), store)
+    }
+
+    /// Fallback identity from explicit sources
+    /// Prefers the home directory path, then the OS machine id, then a generated id
+    /// persisted in the local store, so the identity stays stable across runs
+    fn fallback_from(
+        username: &str,
+        home: Option<PathBuf>,
+        machine_id_path: &Path,
+        store: &LocalStore,
+    ) -> Self {
+        let machine = match home {
+            Some(home) => home.to_string_lossy().to_string(),
+            None => Self::machine_id(machine_id_path)
+                .unwrap_or_else(|| Self::persisted_machine_id(store)),
+        };
+
+        let combined = format!("{}@{}", username, machine);
         let hash = Sha256::digest(combined.as_bytes());
         let fingerprint = format!("{:x}", hash);
         let short_id = fingerprint[..8].to_string();
@@ -85,6 +120,34 @@ impl SshIdentity {
         }
     }
 
+    /// Read the OS machine id, if present
+    fn machine_id(path: &Path) -> Option<String> {
+        let id = fs::read_to_string(path).ok()?;
+        let id = id.trim();
+        (!id.is_empty()).then(|| id.to_string())
+    }
+
+    /// Read the generated machine id, creating and persisting one on first use
+    fn persisted_machine_id(store: &LocalStore) -> String {
+        if let Some(id) = store.read(PERSISTED_ID_FILE) {
+            let id = id.trim();
+            if !id.is_empty() {
+                return id.to_string();
+            }
+        }
+        let id = Uuid::new_v4().to_string();
+        let _ = store.write(PERSISTED_ID_FILE, &id);
+        id
+    }
+
+    /// Stable user id derived from the fingerprint (for orders and subscriptions)
+    pub fn user_id(&self) -> Uuid {
+        self.fingerprint
+            .get(..32)
+            .and_then(|hex| Uuid::try_parse(hex).ok())
+            .unwrap_or_else(Uuid::nil)
+    }
+
     /// Get identity - tries SSH key first, falls back to machine ID
     pub fn get_or_create() -> Self {
         Self::from_ssh_key().unwrap_or_else(Self::fallback_identity)
@@ -97,3 +160,58 @@ impl Default for SshIdentity {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("anora-test-{}", Uuid::new_v4()))
+    }
+
+    #[test]
+    fn no_home_dir_uses_os_machine_id() {
+        let dir = temp_dir();
+        let store = LocalStore::at(dir.join("store"));
+        fs::create_dir_all(&dir).unwrap();
+        let machine_id_path = dir.join("machine-id");
+        fs::write(&machine_id_path, "abc123\n").unwrap();
+
+        let first = SshIdentity::fallback_from("alice", None, &machine_id_path, &store);
+        let second = SshIdentity::fallback_from("alice", None, &machine_id_path, &store);
+        assert_eq!(first.fingerprint, second.fingerprint);
+        // The OS id is used directly, nothing gets persisted
+        assert!(store.read(PERSISTED_ID_FILE).is_none());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn no_home_dir_or_machine_id_persists_a_generated_id() {
+        let dir = temp_dir();
+        let store = LocalStore::at(dir.clone());
+        let missing = dir.join("no-such-machine-id");
+
+        let first = SshIdentity::fallback_from("alice", None, &missing, &store);
+        assert!(store.read(PERSISTED_ID_FILE).is_some());
+
+        // A later launch reads the persisted id back
+        let second = SshIdentity::fallback_from("alice", None, &missing, &LocalStore::at(dir.clone()));
+        assert_eq!(first.fingerprint, second.fingerprint);
+        assert_eq!(first.short_id, first.fingerprint[..8]);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn home_dir_identity_is_unchanged() {
+        let store = LocalStore::at(temp_dir());
+        let identity = SshIdentity::fallback_from(
+            "alice",
+            Some(PathBuf::from("/home/alice")),
+            Path::new("/nonexistent"),
+            &store,
+        );
+        let expected = format!("{:x}", Sha256::digest(b"alice@/home/alice"));
+        assert_eq!(identity.fingerprint, expected);
+    }
+}
@@ -1,9 +1,17 @@
 #![allow(dead_code)]
 
-use crate::models::{Order, Product, Region, SavedAddress, Subscription};
+use super::Database;
+use crate::models::{Order, Product, Region, SavedAddress, Subscription, SubscriptionStatus};
 use anyhow::{anyhow, Result};
-use reqwest::Client;
+use async_trait::async_trait;
+use reqwest::{Client, Response};
 use std::env;
+use std::time::Duration;
+
+/// Retries after the first attempt for a transient failure
+const MAX_RETRIES: u32 = 3;
+/// Wait before the first retry, doubled for each one after
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
 
 /// Supabase client for database operations
 pub struct SupabaseClient {
@@ -36,17 +44,71 @@ impl SupabaseClient {
         }
     }
 
+    /// Whether a project URL was provided; without one every request fails
+    pub fn is_configured(&self) -> bool {
+        !self.base_url.is_empty()
+    }
+
     fn rest_url(&self, table: &str) -> String {
         format!("{}/rest/v1/{}", self.base_url, table)
     }
 
+    /// A user's most recent saved addresses
+    fn saved_addresses_url(&self, user_fingerprint: &str) -> String {
+        format!(
+            "{}?user_fingerprint=eq.{}&order=created_at.desc&limit=3",
+            self.rest_url("saved_addresses"),
+            encode(user_fingerprint)
+        )
+    }
+
+    /// Case-insensitive name search, with the same region and stock filters as `get_products`
+    fn search_url(&self, query: &str, region_id: Option<&str>) -> String {
+        let mut url = format!(
+            "{}?name=ilike.*{}*&in_stock=eq.true&order=category.asc,name.asc",
+            self.rest_url("products"),
+            encode(query)
+        );
+        if let Some(region) = region_id {
+            url.push_str(&format!("&region_id=eq.{}", encode(region)));
+        }
+        url
+    }
+
+    /// GET `url`, retrying connection errors and 5xx responses with exponential backoff
+    /// 4xx responses come back as they are: repeating the request won't change them
+    async fn send_with_retry(&self, url: &str) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .client
+                .get(url)
+                .header("apikey", &self.api_key)
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .send()
+                .await;
+            let transient = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(e) => e.is_connect() || e.is_timeout(),
+            };
+            if !transient || attempt == MAX_RETRIES {
+                return Ok(result?);
+            }
+            tokio::time::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt)).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl Database for SupabaseClient {
     /// Fetch all products (optionally filtered by region)
-    pub async fn get_products(&self, region_id: Option<&str>) -> Result<Vec<Product>> {
+    async fn get_products(&self, region_id: Option<&str>) -> Result<Vec<Product>> {
         let url = if let Some(region) = region_id {
             format!(
                 "{}?region_id=eq.{}&in_stock=eq.true&order=category.asc,name.asc",
                 self.rest_url("products"),
-                region
+                encode(region)
             )
         } else {
             format!(
@@ -55,13 +117,7 @@ impl SupabaseClient {
             )
         };
 
-        let response = self
-            .client
-            .get(&url)
-            .header("apikey", &self.api_key)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await?;
+        let response = self.send_with_retry(&url).await?;
 
         if response.status().is_success() {
             let products: Vec<Product> = response.json().await?;
@@ -73,17 +129,25 @@ impl SupabaseClient {
         }
     }
 
+    /// Search products by name (PostgREST `ilike`)
+    async fn search_products(&self, query: &str, region_id: Option<&str>) -> Result<Vec<Product>> {
+        let response = self.send_with_retry(&self.search_url(query, region_id)).await?;
+
+        if response.status().is_success() {
+            let products: Vec<Product> = response.json().await?;
+            Ok(products)
+        } else {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            Err(anyhow!("Failed to search products: {} - {}", status, body))
+        }
+    }
+
     /// Fetch all available regions
-    pub async fn get_regions(&self) -> Result<Vec<Region>> {
+    async fn get_regions(&self) -> Result<Vec<Region>> {
         let url = format!("{}?order=name.asc", self.rest_url("regions"));
 
-        let response = self
-            .client
-            .get(&url)
-            .header("apikey", &self.api_key)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await?;
+        let response = self.send_with_retry(&url).await?;
 
         if response.status().is_success() {
             let regions: Vec<Region> = response.json().await?;
@@ -95,56 +159,50 @@ impl SupabaseClient {
         }
     }
 
-    /// Fetch orders for a user
-    pub async fn get_orders(&self, user_id: &str) -> Result<Vec<Order>> {
+    /// Fetch a page of orders for a user, newest first
+    async fn get_orders(&self, user_id: &str, limit: usize, offset: usize) -> Result<Vec<Order>> {
         let url = format!(
-            "{}?user_id=eq.{}&order=created_at.desc",
+            "{}?user_id=eq.{}&order=created_at.desc&limit={}&offset={}",
             self.rest_url("orders"),
-            user_id
+            encode(user_id),
+            limit,
+            offset
         );
 
-        let response = self
-            .client
-            .get(&url)
-            .header("apikey", &self.api_key)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await?;
+        let response = self.send_with_retry(&url).await?;
 
         if response.status().is_success() {
             let orders: Vec<Order> = response.json().await?;
             Ok(orders)
         } else {
-            Ok(Vec::new())
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            Err(anyhow!("Failed to fetch orders: {} - {}", status, body))
         }
     }
 
     /// Fetch subscriptions for a user
-    pub async fn get_subscriptions(&self, user_id: &str) -> Result<Vec<Subscription>> {
+    async fn get_subscriptions(&self, user_id: &str) -> Result<Vec<Subscription>> {
         let url = format!(
             "{}?user_id=eq.{}&order=created_at.desc",
             self.rest_url("subscriptions"),
-            user_id
+            encode(user_id)
         );
 
-        let response = self
-            .client
-            .get(&url)
-            .header("apikey", &self.api_key)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
-            .await?;
+        let response = self.send_with_retry(&url).await?;
 
         if response.status().is_success() {
             let subscriptions: Vec<Subscription> = response.json().await?;
             Ok(subscriptions)
         } else {
-            Ok(Vec::new())
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            Err(anyhow!("Failed to fetch subscriptions: {} - {}", status, body))
         }
     }
 
     /// Create a new order
-    pub async fn create_order(&self, order: &Order) -> Result<Order> {
+    async fn create_order(&self, order: &Order) -> Result<Order> {
         let url = self.rest_url("orders");
 
         let response = self
@@ -169,7 +227,7 @@ impl SupabaseClient {
     }
 
     /// Create a new subscription
-    pub async fn create_subscription(&self, subscription: &Subscription) -> Result<Subscription> {
+    async fn create_subscription(&self, subscription: &Subscription) -> Result<Subscription> {
         let url = self.rest_url("subscriptions");
 
         let response = self
@@ -200,36 +258,51 @@ impl SupabaseClient {
         }
     }
 
-    /// Health check
-    pub async fn health_check(&self) -> Result<bool> {
-        let url = format!("{}/rest/v1/", self.base_url);
+    /// Set a subscription's status by ID
+    async fn update_subscription_status(&self, id: &uuid::Uuid, status: SubscriptionStatus) -> Result<()> {
+        let url = format!(
+            "{}?id=eq.{}",
+            self.rest_url("subscriptions"),
+            encode(&id.to_string())
+        );
 
         let response = self
             .client
-            .get(&url)
+            .patch(&url)
             .header("apikey", &self.api_key)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&serde_json::json!({ "status": status }))
             .send()
             .await?;
 
-        Ok(response.status().is_success())
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            Err(anyhow!("Failed to update subscription: {} - {}", status, body))
+        }
     }
 
-    /// Fetch saved addresses for a user (by SSH fingerprint)
-    pub async fn get_saved_addresses(&self, user_fingerprint: &str) -> Result<Vec<SavedAddress>> {
-        let url = format!(
-            "{}?user_fingerprint=eq.{}&order=created_at.desc&limit=3",
-            self.rest_url("saved_addresses"),
-            user_fingerprint
-        );
+    /// Health check
+    async fn health_check(&self) -> Result<bool> {
+        let url = format!("{}/rest/v1/", self.base_url);
 
         let response = self
             .client
             .get(&url)
             .header("apikey", &self.api_key)
-            .header("Authorization", format!("Bearer {}", self.api_key))
             .send()
             .await?;
 
+        Ok(response.status().is_success())
+    }
+
+    /// Fetch saved addresses for a user (by SSH fingerprint)
+    async fn get_saved_addresses(&self, user_fingerprint: &str) -> Result<Vec<SavedAddress>> {
+        let response = self.send_with_retry(&self.saved_addresses_url(user_fingerprint)).await?;
+
         if response.status().is_success() {
             let addresses: Vec<SavedAddress> = response.json().await?;
             Ok(addresses)
@@ -240,7 +313,7 @@ impl SupabaseClient {
     }
 
     /// Save a new address for a user
-    pub async fn save_address(&self, address: &SavedAddress) -> Result<SavedAddress> {
+    async fn save_address(&self, address: &SavedAddress) -> Result<SavedAddress> {
         let url = self.rest_url("saved_addresses");
 
         let response = self
@@ -264,12 +337,42 @@ impl SupabaseClient {
         }
     }
 
+    /// Update a saved address by ID
+    async fn update_address(&self, address: &SavedAddress) -> Result<SavedAddress> {
+        let id = address.id.ok_or_else(|| anyhow!("Can't update an address that was never saved"))?;
+        let url = format!(
+            "{}?id=eq.{}",
+            self.rest_url("saved_addresses"),
+            encode(&id.to_string())
+        );
+
+        let response = self
+            .client
+            .patch(&url)
+            .header("apikey", &self.api_key)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .header("Prefer", "return=representation")
+            .json(address)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let updated: Vec<SavedAddress> = response.json().await?;
+            Ok(updated.into_iter().next().unwrap_or_else(|| address.clone()))
+        } else {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            Err(anyhow!("Failed to update address: {} - {}", status, body))
+        }
+    }
+
     /// Delete a saved address by ID
-    pub async fn delete_address(&self, address_id: &uuid::Uuid) -> Result<()> {
+    async fn delete_address(&self, address_id: &uuid::Uuid) -> Result<()> {
         let url = format!(
             "{}?id=eq.{}",
             self.rest_url("saved_addresses"),
-            address_id
+            encode(&address_id.to_string())
         );
 
         let response = self
@@ -299,3 +402,87 @@ impl Default for SupabaseClient {
         })
     }
 }
+
+/// Percent-encode a value for a query string; only RFC 3986 unreserved characters pass as-is
+/// Every value interpolated into a PostgREST filter goes through this, so a `.`, `&` or space
+/// can't end the filter early or start a new parameter
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one canned HTTP response per connection, in order
+    async fn serve(statuses: Vec<&'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for status in statuses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0; 1024];
+                let _ = socket.read(&mut buf).await;
+                let body = if status.starts_with("200") { "[]" } else { "" };
+                let response = format!(
+                    "HTTP/1.1 {status}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    #[test]
+    fn search_queries_are_encoded() {
+        let client = SupabaseClient::with_credentials("https://db".to_string(), "key".to_string());
+        assert_eq!(
+            client.search_url("dark mode & co", Some("uz")),
+            "https://db/rest/v1/products?name=ilike.*dark%20mode%20%26%20co*\
+             &in_stock=eq.true&order=category.asc,name.asc&region_id=eq.uz"
+        );
+    }
+
+    #[test]
+    fn filter_values_are_encoded() {
+        let client = SupabaseClient::with_credentials("https://db".to_string(), "key".to_string());
+        assert_eq!(
+            client.saved_addresses_url("SHA256:ab+c/d=e&f g"),
+            "https://db/rest/v1/saved_addresses?user_fingerprint=eq.SHA256%3Aab%2Bc%2Fd%3De%26f%20g\
+             &order=created_at.desc&limit=3"
+        );
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_retried() {
+        let url = serve(vec!["503 Service Unavailable", "500 Internal Server Error", "200 OK"]).await;
+        let client = SupabaseClient::with_credentials(url, "key".to_string());
+        assert!(client.get_regions().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let url = serve(vec!["404 Not Found", "200 OK"]).await;
+        let client = SupabaseClient::with_credentials(url, "key".to_string());
+        assert!(client.get_regions().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn account_fetches_report_error_statuses() {
+        let url = serve(vec!["401 Unauthorized", "403 Forbidden"]).await;
+        let client = SupabaseClient::with_credentials(url, "key".to_string());
+        let orders = client.get_orders("user", 10, 0).await.unwrap_err();
+        assert!(orders.to_string().contains("401"), "{orders}");
+        let subscriptions = client.get_subscriptions("user").await.unwrap_err();
+        assert!(subscriptions.to_string().contains("403"), "{subscriptions}");
+    }
+}
@@ -1,18 +1,32 @@
-use crate::app::{App, CheckoutStep, InputField, ShippingMode, Tab};
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crate::app::{AccountSection, App, CheckoutStep, InputField, ShippingMode, Tab, MAX_PRODUCT_QUANTITY};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use std::time::Duration;
 
 pub async fn handle_events(app: &mut App) -> anyhow::Result<bool> {
     if event::poll(Duration::from_millis(100))? {
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                handle_key_event(app, key).await;
-            }
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => handle_key_event(app, key).await,
+            Event::Mouse(mouse) => handle_mouse_event(app, mouse).await,
+            Event::Paste(text) => app.handle_paste(&text),
+            _ => {}
         }
     }
     Ok(app.running)
 }
 
+/// Clicks switch tabs and pick products; the wheel moves the current list's selection
+async fn handle_mouse_event(app: &mut App, mouse: MouseEvent) {
+    if !app.accepts_mouse() {
+        return;
+    }
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => app.click_at(mouse.column, mouse.row).await,
+        MouseEventKind::ScrollDown => app.scroll_selection(true).await,
+        MouseEventKind::ScrollUp => app.scroll_selection(false).await,
+        _ => {}
+    }
+}
+
 async fn handle_key_event(app: &mut App, key: KeyEvent) {
     // During splash screen, any key skips it (except quit)
     if app.show_splash {
@@ -24,35 +38,180 @@ async fn handle_key_event(app: &mut App, key: KeyEvent) {
         return;
     }
 
+    // "What's new" overlay: any key dismisses it
+    if app.show_whats_new {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit(),
+            _ => app.dismiss_whats_new(),
+        }
+        return;
+    }
+
+    // Email preview overlay: scroll or close
+    if app.show_email_preview {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => app.scroll_email_preview(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.scroll_email_preview(1),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit(),
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('e') | KeyCode::Char('q') => {
+                app.toggle_email_preview();
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    // Cart peek overlay: read-only, closed with v or esc
+    if app.show_cart_peek {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit(),
+            KeyCode::Esc | KeyCode::Char('v') | KeyCode::Char('q') => app.toggle_cart_peek(),
+            _ => {}
+        }
+        return;
+    }
+
+    // Keyboard help overlay: read-only, closed with ? or esc
+    if app.show_help {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit(),
+            KeyCode::Esc | KeyCode::Char('?') => app.toggle_help(),
+            _ => {}
+        }
+        return;
+    }
+
+    // Startup data still loading (or backing off before a retry): quit, help and an early retry only
+    if app.is_loading() {
+        match key.code {
+            KeyCode::Char('q') => app.quit(),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit(),
+            KeyCode::Char('R') => app.retry_initial_load().await,
+            KeyCode::Char('?') => app.toggle_help(),
+            _ => app.notify_loading(),
+        }
+        return;
+    }
+
+    // Quit prompt: q again quits, anything else cancels
+    if app.confirm_quit {
+        match key.code {
+            KeyCode::Char('q') => app.request_quit(),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit(),
+            _ => app.confirm_quit = false,
+        }
+        return;
+    }
+
+    // Thank-you screen after an order: any key goes Home
+    if app.placed_order.is_some() {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit(),
+            _ => app.dismiss_placed_order(),
+        }
+        return;
+    }
+
+    // Cart line removal prompt: - again removes it, anything else keeps it
+    if app.confirm_remove.is_some() {
+        match key.code {
+            KeyCode::Char('-') | KeyCode::Char('_') => app.decrement_selected_item(),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit(),
+            _ => app.confirm_remove = None,
+        }
+        return;
+    }
+
+    // Local data reset prompt: y wipes, anything else cancels
+    if app.confirm_reset {
+        match key.code {
+            KeyCode::Char('y') => app.reset_local_data(),
+            _ => app.cancel_reset(),
+        }
+        return;
+    }
+
+    // Subscription cancel prompt: y cancels it, anything else keeps it
+    if app.confirm_cancel_subscription {
+        match key.code {
+            KeyCode::Char('y') => app.cancel_selected_subscription().await,
+            _ => app.keep_subscription(),
+        }
+        return;
+    }
+
+    // Privacy mode works everywhere, including while typing
+    if key.code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        app.toggle_privacy_mode();
+        return;
+    }
+
+    // Typing a shop filter: enter keeps it, esc clears it
+    if app.shop_filter_active {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit(),
+            KeyCode::Char(c) => app.push_shop_filter(c),
+            KeyCode::Backspace => app.pop_shop_filter(),
+            KeyCode::Enter => app.close_shop_filter(),
+            KeyCode::Esc => app.clear_shop_filter(),
+            _ => {}
+        }
+        return;
+    }
+
     // Handle input mode first
     if app.active_input != InputField::None {
         handle_input_mode(app, key).await;
         return;
     }
 
+    // Region-change prompt: remove or keep cart lines from another region, once nothing is being typed
+    if !app.region_conflicts.is_empty() {
+        match key.code {
+            KeyCode::Char('x') => {
+                app.remove_region_conflicts();
+                return;
+            }
+            KeyCode::Esc => {
+                app.keep_region_conflicts();
+                return;
+            }
+            _ => {}
+        }
+    }
+
     // Global shortcuts
     match key.code {
-        KeyCode::Char('q') => app.quit(),
+        KeyCode::Char('q') => app.request_quit(),
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit(),
-        KeyCode::Char('r') => {
-            // Cycle through regions instantly
-            app.cycle_region().await;
-        }
+        KeyCode::Char('r') => app.cycle_region(),
+        KeyCode::Char('R') => app.retry_initial_load().await,
+        KeyCode::Char('v') => app.toggle_cart_peek(),
+        KeyCode::Char('?') => app.toggle_help(),
+        KeyCode::Char('b') => app.go_back(),
+        KeyCode::Char('D') if app.notice.is_some() => app.dismiss_notice(),
         KeyCode::Char('s') => {
-            app.current_tab = Tab::Shop;
-        }
-        KeyCode::Char('a') => {
-            app.current_tab = Tab::Account;
+            app.switch_tab(Tab::Shop);
         }
+        KeyCode::Char('a') => app.open_account().await,
         KeyCode::Char('c') => {
-            app.current_tab = Tab::Cart;
+            app.switch_tab(Tab::Cart);
+        }
+        // Number keys jump by tab position; in the shop plain digits type a quantity, so it takes alt+digit
+        KeyCode::Char('1'..='4') if app.current_tab != Tab::Shop || key.modifiers.contains(KeyModifiers::ALT) => {
+            match key.code {
+                KeyCode::Char('1') => app.switch_tab(Tab::Home),
+                KeyCode::Char('2') => app.switch_tab(Tab::Shop),
+                KeyCode::Char('3') => app.open_account().await,
+                _ => app.switch_tab(Tab::Cart),
+            }
         }
         _ => {
             // Tab-specific handling
             match app.current_tab {
                 Tab::Home => handle_home_keys(app, key).await,
                 Tab::Shop => handle_shop_keys(app, key).await,
-                Tab::Account => handle_account_keys(app, key),
+                Tab::Account => handle_account_keys(app, key).await,
                 Tab::Cart => handle_cart_keys(app, key).await,
             }
         }
@@ -60,16 +219,46 @@ async fn handle_key_event(app: &mut App, key: KeyEvent) {
 }
 
 async fn handle_input_mode(app: &mut App, key: KeyEvent) {
+    // Field-jump mode: a digit picks the field, anything else cancels
+    if app.field_jump {
+        match key.code {
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                app.jump_to_field(c.to_digit(10).unwrap_or(1) as usize);
+            }
+            _ => app.field_jump = false,
+        }
+        return;
+    }
+
     match key.code {
+        KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.field_jump = true;
+        }
         KeyCode::Char(c) => {
             app.handle_input_char(c);
         }
         KeyCode::Backspace => {
             app.handle_input_backspace();
         }
+        KeyCode::Left => {
+            app.move_input_cursor(-1);
+        }
+        KeyCode::Right => {
+            app.move_input_cursor(1);
+        }
+        KeyCode::Home => {
+            app.input_cursor_to(false);
+        }
+        KeyCode::End => {
+            app.input_cursor_to(true);
+        }
         KeyCode::Tab => {
             app.next_input_field();
         }
+        // alt+enter breaks the line in the gift message
+        KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => {
+            app.handle_input_char('\n');
+        }
         KeyCode::Enter => {
             app.next_checkout_step().await;
         }
@@ -81,13 +270,8 @@ async fn handle_input_mode(app: &mut App, key: KeyEvent) {
 }
 
 async fn handle_home_keys(app: &mut App, key: KeyEvent) {
-    match key.code {
-        KeyCode::Enter | KeyCode::Char('s') => {
-            if !app.products.is_empty() {
-                app.current_tab = Tab::Shop;
-            }
-        }
-        _ => {}
+    if key.code == KeyCode::Enter {
+        app.enter_shop_from_home().await;
     }
 }
 
@@ -95,12 +279,29 @@ async fn handle_shop_keys(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Up | KeyCode::Char('k') => app.prev_product(),
         KeyCode::Down | KeyCode::Char('j') => app.next_product(),
+        // Subscriptions have no quantity: say so rather than silently ignoring the key
+        KeyCode::Char('+' | '=' | '-' | '_' | '0'..='9') if app.selected_is_subscription() => {
+            app.notify_no_quantity();
+        }
         KeyCode::Char('+') | KeyCode::Char('=') => {
-            app.product_quantity = (app.product_quantity + 1).min(99);
+            app.cancel_quantity_input();
+            app.product_quantity = (app.product_quantity + 1).min(MAX_PRODUCT_QUANTITY);
         }
         KeyCode::Char('-') | KeyCode::Char('_') => {
+            app.cancel_quantity_input();
             app.product_quantity = (app.product_quantity - 1).max(1);
         }
+        // Typed quantity: digits accumulate until enter adds to cart, esc cancels
+        KeyCode::Char(c) if c.is_ascii_digit() => app.push_quantity_digit(c),
+        KeyCode::Backspace => app.pop_quantity_digit(),
+        KeyCode::Esc if !app.quantity_input.is_empty() => app.cancel_quantity_input(),
+        KeyCode::Char('m') => app.description_expanded = !app.description_expanded,
+        KeyCode::Char('/') => app.open_shop_filter(),
+        KeyCode::Char('g') => app.toggle_shop_grouping(),
+        KeyCode::Char('f') => app.toggle_favorite(),
+        KeyCode::Char('F') => app.toggle_favorites_only(),
+        KeyCode::F(5) => app.refresh_products(),
+        KeyCode::Esc if !app.shop_filter.is_empty() => app.clear_shop_filter(),
         KeyCode::Enter => {
             // Add to cart or subscribe
             app.add_to_cart();
@@ -109,10 +310,45 @@ async fn handle_shop_keys(app: &mut App, key: KeyEvent) {
     }
 }
 
-fn handle_account_keys(app: &mut App, key: KeyEvent) {
+async fn handle_account_keys(app: &mut App, key: KeyEvent) {
+    if app.show_order_detail {
+        match key.code {
+            KeyCode::Esc => app.show_order_detail = false,
+            KeyCode::Up | KeyCode::Char('k') => app.scroll_orders(-1).await,
+            KeyCode::Down | KeyCode::Char('j') => app.scroll_orders(1).await,
+            KeyCode::Char('t') => app.copy_tracking_number(),
+            _ => {}
+        }
+        return;
+    }
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') => app.prev_account_section(),
-        KeyCode::Down | KeyCode::Char('j') => app.next_account_section(),
+        KeyCode::Up => app.prev_account_section(),
+        KeyCode::Down => app.next_account_section(),
+        KeyCode::Char('k') => app.prev_account_item(),
+        KeyCode::Char('j') => app.next_account_item(),
+        KeyCode::PageDown | KeyCode::Char('J') if app.account_section == AccountSection::OrderHistory => {
+            app.scroll_orders(1).await;
+        }
+        KeyCode::PageUp | KeyCode::Char('K') if app.account_section == AccountSection::OrderHistory => {
+            app.scroll_orders(-1).await;
+        }
+        KeyCode::Enter if app.account_section == AccountSection::OrderHistory => app.open_order_detail(),
+        KeyCode::Enter if app.account_section == AccountSection::Settings => {
+            app.toggle_confirm_before_order();
+        }
+        KeyCode::Char('o') if app.account_section == AccountSection::Settings => {
+            app.toggle_celebrate_orders();
+        }
+        KeyCode::Char('w') if app.account_section == AccountSection::Settings => {
+            app.reset_dismissed_notices();
+        }
+        KeyCode::Char('p') if app.account_section == AccountSection::Subscriptions => {
+            app.toggle_selected_subscription_pause().await;
+        }
+        KeyCode::Char('x') if app.account_section == AccountSection::Subscriptions => {
+            app.request_subscription_cancel();
+        }
+        KeyCode::Char('X') => app.request_reset(),
         _ => {}
     }
 }
@@ -123,27 +359,17 @@ async fn handle_cart_keys(app: &mut App, key: KeyEvent) {
             match key.code {
                 KeyCode::Up | KeyCode::Char('k') => app.prev_cart_item(),
                 KeyCode::Down | KeyCode::Char('j') => app.next_cart_item(),
-                KeyCode::Char('+') | KeyCode::Char('=') => {
-                    if let Some(item) = app.cart.items.get(app.cart_item_index) {
-                        let id = item.product.id;
-                        app.cart.increment_item(id);
-                    }
-                }
-                KeyCode::Char('-') | KeyCode::Char('_') => {
-                    if let Some(item) = app.cart.items.get(app.cart_item_index) {
-                        let id = item.product.id;
-                        app.cart.decrement_item(id);
-                        // Reset index if item was removed
-                        if app.cart_item_index >= app.cart.items.len() && !app.cart.items.is_empty() {
-                            app.cart_item_index = app.cart.items.len() - 1;
-                        }
-                    }
-                }
+                KeyCode::Char('+') | KeyCode::Char('=') => app.increment_selected_item(),
+                KeyCode::Char('-') | KeyCode::Char('_') => app.decrement_selected_item(),
+                KeyCode::Char('x') | KeyCode::Delete => app.remove_selected_item(),
+                KeyCode::Char('S') => app.toggle_selected_subscription(),
+                KeyCode::Char('f') => app.add_free_shipping_suggestion(),
+                KeyCode::Char('g') => app.toggle_gift_order(),
                 KeyCode::Enter | KeyCode::Char('c') => {
                     app.next_checkout_step().await;
                 }
                 KeyCode::Esc => {
-                    app.current_tab = Tab::Shop;
+                    app.switch_tab(Tab::Shop);
                 }
                 _ => {}
             }
@@ -156,6 +382,18 @@ async fn handle_cart_keys(app: &mut App, key: KeyEvent) {
                 KeyCode::Backspace | KeyCode::Delete | KeyCode::Char('x') => {
                     app.remove_selected_address().await;
                 }
+                KeyCode::Char('u') => app.undo_address_deletion().await,
+                KeyCode::Char('d') => app.duplicate_selected_address(),
+                KeyCode::Char('e') => app.edit_selected_address(),
+                KeyCode::Esc => app.prev_checkout_step(),
+                _ => {}
+            }
+        }
+        CheckoutStep::Confirmation => {
+            match key.code {
+                KeyCode::Enter => app.next_checkout_step().await,
+                KeyCode::Char('y') => app.copy_receipt(),
+                KeyCode::Char('e') => app.toggle_email_preview(),
                 KeyCode::Esc => app.prev_checkout_step(),
                 _ => {}
             }
@@ -171,11 +409,149 @@ async fn handle_cart_keys(app: &mut App, key: KeyEvent) {
         }
         _ => {
             // Input mode is handled separately
-            match key.code {
-                KeyCode::Esc => app.prev_checkout_step(),
-                _ => {}
+            if key.code == KeyCode::Esc {
+                app.prev_checkout_step();
             }
         }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::MockDatabase;
+    use crate::models::{Product, ProductType, Region, Subscription, SubscriptionStatus};
+    use crossterm::event::KeyEventState;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    #[tokio::test]
+    async fn typing_x_into_a_field_leaves_region_conflicts_alone() {
+        let mut app = App::for_test();
+        let segfault = Product::sample("segfault", 2200);
+        app.cart.add_item(segfault.clone(), 1);
+        app.region = Region { id: "uz".to_string(), ..Region::default() };
+        app.region_conflicts = vec![segfault.id];
+
+        app.current_tab = Tab::Cart;
+        app.checkout_step = CheckoutStep::Shipping;
+        app.active_input = InputField::Street1;
+        handle_key_event(&mut app, press(KeyCode::Char('x'))).await;
+        assert_eq!(app.shipping_address.street_1, "x");
+        assert_eq!(app.cart.items.len(), 1);
+
+        // Esc still leaves the field rather than answering the prompt
+        handle_key_event(&mut app, press(KeyCode::Esc)).await;
+        assert_eq!(app.region_conflicts.len(), 1);
+
+        app.active_input = InputField::None;
+        handle_key_event(&mut app, press(KeyCode::Char('x'))).await;
+        assert!(app.cart.is_empty());
+    }
+
+    #[tokio::test]
+    async fn help_and_retry_work_while_a_startup_retry_waits() {
+        let db = MockDatabase::default();
+        db.state().offline = true;
+        let mut app = App::for_test_with(db.clone());
+        app.start_initial_load().await;
+        assert!(app.is_loading());
+
+        handle_key_event(&mut app, press(KeyCode::Char('s'))).await;
+        assert_eq!(app.notification(), Some("still loading…"));
+
+        handle_key_event(&mut app, press(KeyCode::Char('?'))).await;
+        assert!(app.show_help);
+        handle_key_event(&mut app, press(KeyCode::Esc)).await;
+        assert!(!app.show_help);
+
+        // R skips the rest of the backoff
+        db.state().offline = false;
+        db.state().products.push(Product::sample("segfault", 2200));
+        handle_key_event(&mut app, press(KeyCode::Char('R'))).await;
+        assert!(!app.is_loading());
+        assert_eq!(app.products.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn shop_switches_tabs_with_alt_digits() {
+        let mut app = App::for_test();
+        app.products = vec![Product::sample("segfault", 2200)];
+        app.current_tab = Tab::Shop;
+
+        handle_key_event(&mut app, press(KeyCode::Char('4'))).await;
+        assert_eq!(app.current_tab, Tab::Shop);
+        assert_eq!(app.quantity_input, "4");
+
+        let alt_4 = KeyEvent { modifiers: KeyModifiers::ALT, ..press(KeyCode::Char('4')) };
+        handle_key_event(&mut app, alt_4).await;
+        assert_eq!(app.current_tab, Tab::Cart);
+    }
+
+    #[tokio::test]
+    async fn keys_work_while_a_region_switch_fetches_in_the_background() {
+        let mut app = App::for_test();
+        app.current_tab = Tab::Shop;
+        app.change_region(Region { id: "uz".to_string(), ..Region::default() });
+        assert!(app.is_fetching_products());
+        assert!(!app.is_loading());
+
+        handle_key_event(&mut app, press(KeyCode::Char('c'))).await;
+        assert_eq!(app.current_tab, Tab::Cart);
+        assert!(app.notification().is_none());
+    }
+
+    #[tokio::test]
+    async fn cancelling_a_subscription_asks_first() {
+        let db = MockDatabase::default();
+        let mut app = App::for_test_with(db.clone());
+        let subscription = Subscription {
+            id: uuid::Uuid::new_v4(),
+            user_id: app.identity.user_id(),
+            product_id: uuid::Uuid::new_v4(),
+            product_name: "segfault".to_string(),
+            status: SubscriptionStatus::Active,
+            next_delivery: None,
+            created_at: chrono::Utc::now(),
+        };
+        db.state().subscriptions = vec![subscription.clone()];
+        app.subscriptions = vec![subscription];
+        app.current_tab = Tab::Account;
+        app.account_section = AccountSection::Subscriptions;
+
+        // Any key but y keeps it
+        handle_key_event(&mut app, press(KeyCode::Char('x'))).await;
+        assert!(app.confirm_cancel_subscription);
+        handle_key_event(&mut app, press(KeyCode::Char('n'))).await;
+        assert!(!app.confirm_cancel_subscription);
+        assert_eq!(db.state().subscriptions[0].status, SubscriptionStatus::Active);
+
+        handle_key_event(&mut app, press(KeyCode::Char('x'))).await;
+        handle_key_event(&mut app, press(KeyCode::Char('y'))).await;
+        assert!(!app.confirm_cancel_subscription);
+        assert_eq!(db.state().subscriptions[0].status, SubscriptionStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn quantity_keys_leave_a_subscription_alone() {
+        let mut app = App::for_test();
+        let mut subscription = Product::sample("cron", 4000);
+        subscription.product_type = ProductType::Subscription;
+        app.products = vec![subscription];
+        app.current_tab = Tab::Shop;
+
+        for key in ['+', '-', '7'] {
+            handle_key_event(&mut app, press(KeyCode::Char(key))).await;
+        }
+        assert_eq!(app.product_quantity, 1);
+        assert!(app.quantity_input.is_empty());
+        assert_eq!(app.notification(), Some("subscriptions don't have a quantity — enter to subscribe"));
+    }
+}
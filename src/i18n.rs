@@ -0,0 +1,466 @@
+//! Lightweight UI string localization
+//! Strings are looked up by key in bundled locale tables; missing keys fall back to English.
+
+use std::sync::OnceLock;
+
+/// Supported UI locales
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Uz,
+}
+
+impl Locale {
+    /// Parse a locale tag like "uz", "uz_UZ.UTF-8" or "en-US" (unknown tags are English)
+    pub fn from_tag(tag: &str) -> Self {
+        if tag.to_lowercase().starts_with("uz") {
+            Locale::Uz
+        } else {
+            Locale::En
+        }
+    }
+
+    /// Locale from ANORA_LANG, then the standard LC_ALL / LANG variables
+    fn detect() -> Self {
+        ["ANORA_LANG", "LC_ALL", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .map(|value| Self::from_tag(&value))
+            .unwrap_or(Locale::En)
+    }
+
+    fn table(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Uz => UZ,
+        }
+    }
+}
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// Current UI locale (detected from the environment on first use)
+/// Tests always render in English so snapshots don't depend on the host's LANG
+pub fn locale() -> Locale {
+    *LOCALE.get_or_init(|| if cfg!(test) { Locale::En } else { Locale::detect() })
+}
+
+/// Translate a UI string key for the current locale
+pub fn t(key: &'static str) -> &'static str {
+    translate(locale(), key)
+}
+
+/// Translate a key for a specific locale, falling back to English, then the key itself
+pub fn translate(locale: Locale, key: &'static str) -> &'static str {
+    lookup(locale.table(), key)
+        .or_else(|| lookup(EN, key))
+        .unwrap_or(key)
+}
+
+fn lookup(table: &'static [(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+const EN: &[(&str, &str)] = &[
+    // Home
+    ("home.welcome", "welcome to ANORA Labs"),
+    ("home.browse", "press 's' to browse the shop"),
+    ("home.no_products", "no products available for this region"),
+    ("home.change_region", "press 'r' to change region"),
+    ("home.check_back", "check back soon"),
+    ("loading", "loading…"),
+    ("home.load_failed", "couldn't load the shop"),
+    ("home.retry", "press 'R' to retry"),
+    // Header / checkout steps
+    ("tab.shop", "shop"),
+    ("tab.account", "account"),
+    ("tab.cart", "cart"),
+    ("step.cart", "cart"),
+    ("step.shipping", "shipping"),
+    ("step.payment", "payment"),
+    ("step.confirmation", "confirmation"),
+    // Footer
+    ("footer.free_shipping", "free shipping on {code} orders over {amount}"),
+    ("footer.free_shipping_unlocked", "🎉 free shipping unlocked"),
+    ("footer.regions_unavailable", "couldn't load regions — showing the default region"),
+    ("footer.dismiss_notice", "  (D to dismiss)"),
+    ("hint.back", "back"),
+    ("hint.items", "items"),
+    ("hint.qty", "qty"),
+    ("hint.checkout", "checkout"),
+    ("hint.addresses", "addresses"),
+    ("hint.remove", "remove"),
+    ("hint.select", "select"),
+    ("hint.fields", "fields"),
+    ("hint.next", "next"),
+    ("hint.continue", "continue"),
+    ("hint.jump", "jump"),
+    ("hint.new_line", "new line"),
+    ("hint.confirm_order", "confirm order"),
+    ("hint.copy_receipt", "copy receipt"),
+    ("hint.orders", "orders"),
+    ("hint.copy_tracking", "copy tracking"),
+    ("hint.email_preview", "email preview"),
+    ("hint.products", "products"),
+    ("hint.cart", "cart"),
+    ("hint.quit", "quit"),
+    ("hint.navigate", "navigate"),
+    ("hint.undo", "undo"),
+    ("hint.duplicate", "duplicate"),
+    ("hint.edit", "edit"),
+    ("hint.subscriptions", "subscriptions"),
+    ("hint.pause", "pause/resume"),
+    ("hint.cancel", "cancel"),
+    ("hint.reset", "reset local data"),
+    ("hint.gift", "send as gift"),
+    ("hint.gift_off", "not a gift"),
+    ("hint.filter", "filter"),
+    ("hint.group", "group"),
+    ("hint.peek", "peek at cart"),
+    ("hint.apply", "apply"),
+    ("hint.clear", "clear"),
+    ("hint.tabs", "jump to tab"),
+    ("hint.shop_account_cart", "shop, account, cart"),
+    ("hint.privacy", "hide personal details"),
+    ("hint.help", "keys"),
+    ("hint.retry_load", "retry loading"),
+    ("hint.dismiss", "dismiss warning"),
+    ("hint.open_shop", "open the shop"),
+    ("hint.add_to_cart", "add to cart"),
+    ("hint.type_qty", "type a quantity"),
+    ("hint.more", "more/less"),
+    ("hint.favorite", "favorite"),
+    ("hint.favorites_only", "favorites only"),
+    ("hint.refresh", "refresh"),
+    ("hint.order_details", "order details"),
+    ("hint.page", "page"),
+    ("hint.subscribe", "subscribe instead"),
+    ("hint.add_suggestion", "add suggestion"),
+    ("hint.payment_methods", "payment methods"),
+    ("help.here", "here"),
+    ("help.everywhere", "everywhere"),
+    ("help.close", "?/esc close"),
+    // Shop
+    ("shop.favorites", "favorites"),
+    ("shop.show_all", " · F all"),
+    ("shop.no_matches", "no matches"),
+    ("shop.sold_out", "sold out"),
+    ("shop.limit", "limit {n} per order"),
+    ("shop.qty", "qty "),
+    ("shop.qty_help", "  enter add · esc cancel"),
+    ("shop.more", "…more (m)"),
+    ("shop.less", "less (m)"),
+    // Forms
+    ("field.name", "name"),
+    ("field.street", "street"),
+    ("field.street_2", "street 2 (optional)"),
+    ("field.city", "city"),
+    ("field.state", "state (optional)"),
+    ("field.country", "country"),
+    ("field.phone", "phone"),
+    ("field.postal_code", "postal code"),
+    ("field.email", "email"),
+    ("field.card_number", "card number"),
+    ("field.expiry", "expiry (MM/YY)"),
+    ("field.cvv", "cvv (3 digits)"),
+    ("field.gift_message", "gift message (optional)"),
+    ("field.expiry_short", "expiry"),
+    ("field.cvv_short", "cvv"),
+    ("validation.empty", "{field} can't be empty"),
+    ("validation.email", "email doesn't look right"),
+    ("validation.card_number", "card number is invalid"),
+    ("validation.expiry_month", "expiry month must be 01-12"),
+    ("validation.card_expired", "card has expired"),
+    // Cart / checkout
+    ("cart.empty", "Your cart is empty."),
+    ("shipping.select", "select shipping address"),
+    ("gift.title", "add a note for the recipient"),
+    ("shipping.add_new", "add new address"),
+    ("payment.select", "select payment method"),
+    ("payment.via_ssh", "add payment information via ssh"),
+    ("payment.via_browser", "add payment information via browser"),
+    ("payment.scan", "scan or copy to enter payment information"),
+    ("summary.subtotal", "subtotal: "),
+    ("summary.shipping", "shipping: "),
+    ("summary.total", "total: "),
+    ("summary.items", "items: "),
+    ("summary.free", "free"),
+    ("cart.item", "item"),
+    ("cart.items", "items"),
+    ("cart.confirm_remove", "remove? - again to confirm, any key to keep"),
+    ("cart.unavailable", " (no longer available)"),
+    ("cart.price_updated", " (price updated)"),
+    ("cart.free_shipping_gap", "add {amount} for free shipping"),
+    ("cart.free_shipping_suggestion", "add {name} for {price} to get free shipping  "),
+    ("cart.subscribe", "subscribe -{n}%"),
+    ("cart.subscribed", "↻ subscribed -{n}%"),
+    ("summary.subscriptions", "subscriptions: "),
+    ("summary.saving", " (saving {amount})"),
+    ("preview.help", "↑/↓ scroll   esc close — preview only, nothing is sent"),
+    ("confirm.title", "order confirmation"),
+    ("peek.title", "your cart"),
+    ("peek.empty", "empty"),
+    ("peek.close", "v/esc close"),
+    ("quit.confirm", "you have {n} items in your cart — press q again to quit, any key to cancel"),
+    ("confirm.shipping_to", "shipping to: "),
+    ("order.tracking", "tracking: "),
+    ("order.copy_tracking", "press t to copy the tracking number"),
+    ("order.placed", "order #{id} placed — thank you"),
+    ("order.placed_continue", "press any key to continue"),
+    ("confirm.billed_to", "billed to: "),
+    ("confirm.billed_at_browser", "you, at browser checkout"),
+    ("confirm.press_enter", "press enter to confirm your order"),
+    ("confirm.press_again", "press enter again to place your order, esc to go back"),
+    // Account
+    ("account.order_history", "order history"),
+    ("account.subscriptions", "subscriptions"),
+    ("account.faq", "faq"),
+    ("account.about", "about"),
+    ("account.whats_new", "what's new"),
+    ("account.settings", "settings"),
+    ("settings.confirm_before_order", "ask before placing an order"),
+    ("settings.confirm_before_order_help", "press enter twice on the confirmation step — enter to toggle"),
+    ("settings.celebrate_orders", "celebrate placed orders"),
+    ("settings.celebrate_orders_help", "a moment of confetti after checkout — o to toggle"),
+    ("settings.reset_notices", "show dismissed warnings again"),
+    ("account.no_orders", "no orders found"),
+    ("account.no_subscriptions", "no active subscriptions"),
+    ("whats_new.continue", "press any key to continue"),
+    // Notifications
+    ("notice.address_deleted", "address deleted — press u to undo"),
+    ("notice.load_failed", "failed to load — press R to retry"),
+    ("notice.still_loading", "still loading…"),
+    ("reset.prompt", "wipe local data (cart, settings, device id)? y to confirm"),
+    ("subscription.cancel_prompt", "cancel this subscription? y to confirm, any other key keeps it"),
+    ("notice.no_favorites", "no favorites yet — press f on a product to add it"),
+    ("notice.unknown_region", "no region {code} — showing {name} instead"),
+    ("notice.no_products", "no products in {name} yet — press r to change region"),
+    ("notice.region_conflict", "not sold in {name}: {items} — x remove, esc keep"),
+    ("notice.subscription_quantity", "subscriptions don't have a quantity — enter to subscribe"),
+    ("notice.privacy_on", "privacy mode on"),
+    ("notice.privacy_off", "privacy mode off"),
+    ("notice.gift_on", "gift order — enter the recipient's address, billed to you"),
+    ("notice.gift_off", "gift order off"),
+    ("notice.no_tracking", "no tracking number yet"),
+    ("notice.tracking_copied", "tracking number copied to clipboard"),
+    ("notice.tracking_saved", "tracking number saved to {path}"),
+    ("notice.receipt_copied", "receipt copied to clipboard"),
+    ("notice.receipt_saved", "receipt saved to {path}"),
+    ("notice.nothing_to_clear", "nothing to clear"),
+    ("notice.cleared", "cleared {items}"),
+];
+
+const UZ: &[(&str, &str)] = &[
+    // Home
+    ("home.welcome", "ANORA Labs'ga xush kelibsiz"),
+    ("home.browse", "do'konni ko'rish uchun 's' ni bosing"),
+    ("home.no_products", "bu hudud uchun mahsulotlar yo'q"),
+    ("home.change_region", "hududni o'zgartirish uchun 'r' ni bosing"),
+    ("home.check_back", "tez orada qaytib ko'ring"),
+    ("loading", "yuklanmoqda…"),
+    ("home.load_failed", "do'konni yuklab bo'lmadi"),
+    ("home.retry", "qayta urinish uchun 'R' ni bosing"),
+    // Header / checkout steps
+    ("tab.shop", "do'kon"),
+    ("tab.account", "hisob"),
+    ("tab.cart", "savat"),
+    ("step.cart", "savat"),
+    ("step.shipping", "yetkazish"),
+    ("step.payment", "to'lov"),
+    ("step.confirmation", "tasdiqlash"),
+    // Footer
+    ("footer.free_shipping", "{code} bo'ylab {amount} dan ortiq buyurtmalarga bepul yetkazish"),
+    ("footer.free_shipping_unlocked", "🎉 bepul yetkazish ochildi"),
+    ("footer.regions_unavailable", "hududlarni yuklab bo'lmadi — standart hudud ko'rsatilmoqda"),
+    ("footer.dismiss_notice", "  (yopish uchun D)"),
+    ("hint.back", "orqaga"),
+    ("hint.items", "mahsulotlar"),
+    ("hint.qty", "soni"),
+    ("hint.checkout", "rasmiylashtirish"),
+    ("hint.addresses", "manzillar"),
+    ("hint.remove", "o'chirish"),
+    ("hint.select", "tanlash"),
+    ("hint.fields", "maydonlar"),
+    ("hint.next", "keyingi"),
+    ("hint.continue", "davom etish"),
+    ("hint.jump", "o'tish"),
+    ("hint.new_line", "yangi qator"),
+    ("hint.confirm_order", "buyurtmani tasdiqlash"),
+    ("hint.copy_receipt", "chekni nusxalash"),
+    ("hint.orders", "buyurtmalar"),
+    ("hint.copy_tracking", "kuzatuv raqamini nusxalash"),
+    ("hint.email_preview", "xat namunasi"),
+    ("hint.products", "mahsulotlar"),
+    ("hint.cart", "savat"),
+    ("hint.quit", "chiqish"),
+    ("hint.navigate", "harakatlanish"),
+    ("hint.undo", "bekor qilish"),
+    ("hint.duplicate", "nusxalash"),
+    ("hint.edit", "tahrirlash"),
+    ("hint.subscriptions", "obunalar"),
+    ("hint.pause", "to'xtatish/davom ettirish"),
+    ("hint.cancel", "bekor qilish"),
+    ("hint.reset", "mahalliy ma'lumotlarni tozalash"),
+    ("hint.gift", "sovg'a qilish"),
+    ("hint.gift_off", "sovg'a emas"),
+    ("hint.filter", "saralash"),
+    ("hint.group", "guruhlash"),
+    ("hint.peek", "savatga qarash"),
+    ("hint.apply", "qo'llash"),
+    ("hint.clear", "tozalash"),
+    ("hint.tabs", "bo'limga o'tish"),
+    ("hint.shop_account_cart", "do'kon, hisob, savat"),
+    ("hint.privacy", "shaxsiy ma'lumotlarni yashirish"),
+    ("hint.help", "tugmalar"),
+    ("hint.retry_load", "qayta yuklash"),
+    ("hint.dismiss", "ogohlantirishni yopish"),
+    ("hint.open_shop", "do'konni ochish"),
+    ("hint.add_to_cart", "savatga qo'shish"),
+    ("hint.type_qty", "miqdorni yozish"),
+    ("hint.more", "ko'proq/kamroq"),
+    ("hint.favorite", "sevimli"),
+    ("hint.favorites_only", "faqat sevimlilar"),
+    ("hint.refresh", "yangilash"),
+    ("hint.order_details", "buyurtma tafsilotlari"),
+    ("hint.page", "sahifa"),
+    ("hint.subscribe", "obunaga aylantirish"),
+    ("hint.add_suggestion", "tavsiyani qo'shish"),
+    ("hint.payment_methods", "to'lov usullari"),
+    ("help.here", "shu yerda"),
+    ("help.everywhere", "hamma joyda"),
+    ("help.close", "?/esc yopish"),
+    // Shop
+    ("shop.favorites", "sevimlilar"),
+    ("shop.show_all", " · F hammasi"),
+    ("shop.no_matches", "hech narsa topilmadi"),
+    ("shop.sold_out", "tugagan"),
+    ("shop.limit", "har buyurtmaga {n} tagacha"),
+    ("shop.qty", "soni "),
+    ("shop.qty_help", "  enter qo'shish · esc bekor qilish"),
+    ("shop.more", "…ko'proq (m)"),
+    ("shop.less", "kamroq (m)"),
+    // Forms
+    ("field.name", "ism"),
+    ("field.street", "ko'cha"),
+    ("field.street_2", "ko'cha 2 (ixtiyoriy)"),
+    ("field.city", "shahar"),
+    ("field.state", "viloyat (ixtiyoriy)"),
+    ("field.country", "mamlakat"),
+    ("field.phone", "telefon"),
+    ("field.postal_code", "pochta indeksi"),
+    ("field.card_number", "karta raqami"),
+    ("field.expiry", "amal qilish muddati (OO/YY)"),
+    ("field.cvv", "cvv (3 raqam)"),
+    ("field.gift_message", "sovg'a xabari (ixtiyoriy)"),
+    ("field.expiry_short", "amal qilish muddati"),
+    ("field.cvv_short", "cvv"),
+    ("validation.empty", "{field} bo'sh bo'lishi mumkin emas"),
+    ("validation.email", "email noto'g'ri ko'rinadi"),
+    ("validation.card_number", "karta raqami noto'g'ri"),
+    ("validation.expiry_month", "oy 01-12 oralig'ida bo'lishi kerak"),
+    ("validation.card_expired", "karta muddati o'tgan"),
+    // Cart / checkout
+    ("cart.empty", "Savatingiz bo'sh."),
+    ("shipping.select", "yetkazish manzilini tanlang"),
+    ("gift.title", "qabul qiluvchi uchun xabar qoldiring"),
+    ("shipping.add_new", "yangi manzil qo'shish"),
+    ("payment.select", "to'lov usulini tanlang"),
+    ("payment.via_ssh", "to'lov ma'lumotlarini ssh orqali kiritish"),
+    ("payment.via_browser", "to'lov ma'lumotlarini brauzer orqali kiritish"),
+    ("payment.scan", "to'lov ma'lumotlarini kiritish uchun skanerlang yoki nusxalang"),
+    ("summary.subtotal", "oraliq jami: "),
+    ("summary.shipping", "yetkazish: "),
+    ("summary.total", "jami: "),
+    ("summary.items", "mahsulotlar: "),
+    ("summary.free", "bepul"),
+    ("cart.item", "ta mahsulot"),
+    ("cart.items", "ta mahsulot"),
+    ("cart.confirm_remove", "o'chirilsinmi? tasdiqlash uchun yana -, qoldirish uchun istalgan tugma"),
+    ("cart.unavailable", " (endi mavjud emas)"),
+    ("cart.price_updated", " (narx yangilandi)"),
+    ("cart.free_shipping_gap", "bepul yetkazish uchun yana {amount} qo'shing"),
+    ("cart.free_shipping_suggestion", "bepul yetkazish uchun {name} ({price}) qo'shing  "),
+    ("cart.subscribe", "obuna -{n}%"),
+    ("cart.subscribed", "↻ obuna -{n}%"),
+    ("summary.subscriptions", "obunalar: "),
+    ("summary.saving", " ({amount} tejaladi)"),
+    ("preview.help", "↑/↓ aylantirish   esc yopish — faqat ko'rinish, hech narsa yuborilmaydi"),
+    ("confirm.title", "buyurtmani tasdiqlash"),
+    ("peek.title", "savatingiz"),
+    ("peek.empty", "bo'sh"),
+    ("peek.close", "v/esc yopish"),
+    ("quit.confirm", "savatingizda {n} ta mahsulot bor — chiqish uchun yana q bosing, bekor qilish uchun istalgan tugma"),
+    ("confirm.shipping_to", "yetkazish manzili: "),
+    ("order.tracking", "kuzatuv: "),
+    ("order.copy_tracking", "kuzatuv raqamini nusxalash uchun t ni bosing"),
+    ("order.placed", "#{id} buyurtma qabul qilindi — rahmat"),
+    ("order.placed_continue", "davom etish uchun istalgan tugmani bosing"),
+    ("confirm.billed_to", "to'lovchi: "),
+    ("confirm.billed_at_browser", "siz, brauzerdagi to'lovda"),
+    ("confirm.press_enter", "buyurtmani tasdiqlash uchun enter ni bosing"),
+    ("confirm.press_again", "buyurtma berish uchun enter ni yana bosing, orqaga esc"),
+    // Account
+    ("account.order_history", "buyurtmalar tarixi"),
+    ("account.subscriptions", "obunalar"),
+    ("account.faq", "savollar"),
+    ("account.about", "biz haqimizda"),
+    ("account.whats_new", "yangiliklar"),
+    ("account.settings", "sozlamalar"),
+    ("settings.confirm_before_order", "buyurtmadan oldin so'rash"),
+    ("settings.confirm_before_order_help", "tasdiqlash bosqichida enterni ikki marta bosing — enter bilan o'zgartiring"),
+    ("settings.celebrate_orders", "buyurtmani nishonlash"),
+    ("settings.celebrate_orders_help", "to'lovdan keyin bir lahza konfetti — o bilan o'zgartiring"),
+    ("settings.reset_notices", "yopilgan ogohlantirishlarni qayta ko'rsatish"),
+    ("account.no_orders", "buyurtmalar topilmadi"),
+    ("account.no_subscriptions", "faol obunalar yo'q"),
+    ("whats_new.continue", "davom etish uchun istalgan tugmani bosing"),
+    // Notifications
+    ("notice.address_deleted", "manzil o'chirildi — qaytarish uchun u ni bosing"),
+    ("notice.load_failed", "yuklab bo'lmadi — qayta urinish uchun R ni bosing"),
+    ("notice.still_loading", "hali yuklanmoqda…"),
+    ("reset.prompt", "mahalliy ma'lumotlar (savat, sozlamalar, qurilma id) o'chirilsinmi? tasdiqlash uchun y"),
+    ("subscription.cancel_prompt", "obuna bekor qilinsinmi? tasdiqlash uchun y, qoldirish uchun istalgan boshqa tugma"),
+    ("notice.no_favorites", "hali sevimlilar yo'q — qo'shish uchun mahsulotda f ni bosing"),
+    ("notice.unknown_region", "{code} hududi yo'q — o'rniga {name} ko'rsatilmoqda"),
+    ("notice.no_products", "{name} da hali mahsulotlar yo'q — hududni o'zgartirish uchun r ni bosing"),
+    ("notice.region_conflict", "{name} da sotilmaydi: {items} — x o'chirish, esc qoldirish"),
+    ("notice.subscription_quantity", "obunalarda miqdor yo'q — obuna bo'lish uchun enter"),
+    ("notice.privacy_on", "maxfiylik rejimi yoqildi"),
+    ("notice.privacy_off", "maxfiylik rejimi o'chirildi"),
+    ("notice.gift_on", "sovg'a buyurtma — qabul qiluvchining manzilini kiriting, to'lov sizdan"),
+    ("notice.gift_off", "sovg'a buyurtma o'chirildi"),
+    ("notice.no_tracking", "hali kuzatuv raqami yo'q"),
+    ("notice.tracking_copied", "kuzatuv raqami nusxalandi"),
+    ("notice.tracking_saved", "kuzatuv raqami {path} ga saqlandi"),
+    ("notice.receipt_copied", "chek nusxalandi"),
+    ("notice.receipt_saved", "chek {path} ga saqlandi"),
+    ("notice.nothing_to_clear", "tozalanadigan narsa yo'q"),
+    ("notice.cleared", "{items} tozalandi"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_keys_fall_back_to_english_then_key() {
+        // "field.email" is intentionally not in the Uzbek table
+        assert_eq!(translate(Locale::Uz, "field.email"), "email");
+        assert_eq!(translate(Locale::Uz, "no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn locale_tags_are_parsed() {
+        assert_eq!(Locale::from_tag("uz_UZ.UTF-8"), Locale::Uz);
+        assert_eq!(Locale::from_tag("en_US.UTF-8"), Locale::En);
+        assert_eq!(Locale::from_tag("C"), Locale::En);
+    }
+
+    #[test]
+    fn every_translated_key_exists_in_english() {
+        for (key, _) in UZ {
+            assert!(lookup(EN, key).is_some(), "{} missing from EN", key);
+        }
+    }
+}
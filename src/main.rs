@@ -1,12 +1,15 @@
 mod app;
+mod clipboard;
 mod db;
 mod events;
+mod i18n;
 mod models;
 mod ui;
 
 use app::{App, Tab};
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    cursor::Show,
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -29,18 +32,40 @@ async fn main() -> anyhow::Result<()> {
     // Load environment variables
     let _ = dotenvy::dotenv();
 
+    // `anora --reset`: wipe local data (cart, settings, generated device id) and exit
+    if std::env::args().skip(1).any(|arg| arg == "--reset") {
+        let store = db::LocalStore::new();
+        match store.clear() {
+            Ok(removed) if removed.is_empty() => println!("nothing to clear in {}", store.dir().display()),
+            Ok(removed) => println!("cleared {} from {}", removed.join(", "), store.dir().display()),
+            Err(e) => eprintln!("couldn't clear {}: {e}", store.dir().display()),
+        }
+        return Ok(());
+    }
+
+    // Put the terminal back before a panic message prints, so it's readable and usable
+    install_panic_hook();
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    // Bracketed paste delivers a pasted card number or address as one event
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Create app and run
     let mut app = App::new();
+    // `anora --no-splash`: go straight to the shop front
+    let no_splash = std::env::args().skip(1).any(|arg| arg == "--no-splash");
+    app.configure_splash(std::env::var("ANORA_SPLASH_SECS").ok().as_deref(), no_splash);
+    // `anora --region=<code>`: start in that region instead of the first one
+    app.preferred_region = std::env::args().skip(1).find_map(|arg| arg.strip_prefix("--region=").map(str::to_string));
     
-    // Load initial data (regions + products) from Supabase
-    let _ = app.load_initial_data().await;
+    // Load initial data (regions + products) from Supabase, retried during the splash
+    app.start_initial_load().await;
+    app.restore_cart();
+    app.load_favorites();
 
     let result = run_app(&mut terminal, &mut app).await;
 
@@ -49,7 +74,8 @@ async fn main() -> anyhow::Result<()> {
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     )?;
     terminal.show_cursor()?;
 
@@ -67,6 +93,13 @@ async fn run_app(
     while app.running {
         // Check if splash screen should transition
         app.check_splash_timeout();
+        app.check_undo_timeout();
+        app.check_notifications();
+        app.check_empty_checkout();
+        app.check_free_shipping();
+        app.check_cart_saved();
+        app.check_load_retry().await;
+        app.apply_updates();
         
         terminal.draw(|f| render(f, app))?;
         events::handle_events(app).await?;
@@ -87,16 +120,21 @@ fn render(f: &mut Frame, app: &App) {
     // Calculate centered area with max dimensions
     let area = centered_rect(full_area, MAX_WIDTH, MAX_HEIGHT);
 
+    // Click targets are re-recorded as this frame draws them
+    app.click_targets.borrow_mut().clear();
+
     // During splash, render only the splash screen (no header/footer)
     if app.show_splash {
         ui::render_home(f, area, app);
+        app.theme.adapt_buffer(f.buffer_mut());
         return;
     }
 
     // Main layout: header, body, footer
+    // The body absorbs any shortfall so the footer's nav hints never get squeezed out
     let chunks = Layout::vertical([
         Constraint::Length(3),  // Header
-        Constraint::Min(10),    // Body
+        Constraint::Min(0),     // Body
         Constraint::Length(3),  // Footer
     ])
     .split(area);
@@ -111,9 +149,13 @@ fn render(f: &mut Frame, app: &App) {
     let padded_body = pad_area(body_area, 2, 1);
 
     match app.current_tab {
+        _ if app.is_loading() => ui::render_loading(f, padded_body, app),
+        // A background fetch only replaces the views that list products
+        Tab::Home | Tab::Shop if app.is_fetching_products() => ui::render_loading(f, padded_body, app),
         Tab::Home => ui::render_home(f, padded_body, app),
         Tab::Shop => ui::render_shop(f, padded_body, app),
         Tab::Account => ui::render_account(f, padded_body, app),
+        Tab::Cart if app.placed_order.is_some() => ui::render_order_placed(f, padded_body, app),
         Tab::Cart => {
             // Render checkout header for cart
             let cart_chunks = Layout::vertical([
@@ -127,8 +169,40 @@ fn render(f: &mut Frame, app: &App) {
         }
     }
 
-    // Render footer
+    // Render footer, with older notifications stacked just above it
     ui::render_footer(f, chunks[2], app);
+    ui::render_notification_stack(f, chunks[1], app);
+
+    // Overlays render last, on top of everything
+    ui::render_celebration(f, chunks[1], app);
+    if app.show_cart_peek {
+        ui::render_cart_peek(f, area, app);
+    }
+    if app.show_help {
+        ui::render_help(f, chunks[1], app);
+    }
+    if app.show_email_preview {
+        ui::render_email_preview(f, area, app);
+    }
+    if app.show_whats_new {
+        ui::render_whats_new(f, area, &app.theme);
+    }
+    if app.confirm_quit {
+        ui::render_quit_confirm(f, chunks[1], app);
+    }
+
+    // Map the RGB palette down on terminals without true color
+    app.theme.adapt_buffer(f.buffer_mut());
+}
+
+/// Restore the terminal (raw mode, alternate screen, cursor) on panic, then run the default hook
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste, Show);
+        default_hook(info);
+    }));
 }
 
 /// Create a centered rect with max dimensions
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
-use super::Product;
+use super::{Product, Region};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -9,6 +10,15 @@ pub struct CartItem {
     pub id: Uuid,
     pub product: Product,
     pub quantity: i32,
+    /// Line converted to a recurring subscription via the checkout upsell
+    #[serde(default)]
+    pub subscribe: bool,
+    /// Catalog price differs from the price when the item was added
+    #[serde(default)]
+    pub price_changed: bool,
+    /// Product was missing from the region's catalog when it last loaded
+    #[serde(default)]
+    pub unavailable: bool,
 }
 
 impl CartItem {
@@ -17,15 +27,36 @@ impl CartItem {
             id: Uuid::new_v4(),
             product,
             quantity,
+            subscribe: false,
+            price_changed: false,
+            unavailable: false,
         }
     }
 
+    pub fn unit_price_cents(&self) -> i32 {
+        if self.subscribe {
+            self.product.subscription_price_cents(self.quantity)
+        } else {
+            self.product.unit_price_cents(self.quantity)
+        }
+    }
+
+    /// Whether a quantity price tier is lowering this line's unit price
+    pub fn has_tier_discount(&self) -> bool {
+        self.product.unit_price_cents(self.quantity) < self.product.price_cents
+    }
+
     pub fn total_cents(&self) -> i32 {
-        self.product.price_cents * self.quantity
+        self.unit_price_cents() * self.quantity
+    }
+
+    /// Amount saved on this line versus the flat price (subscription and tier discounts)
+    pub fn savings_cents(&self) -> i32 {
+        (self.product.price_cents - self.unit_price_cents()) * self.quantity
     }
 
-    pub fn total_display(&self) -> String {
-        format!("${}", self.total_cents() / 100)
+    pub fn total_display(&self, region: &Region) -> String {
+        region.format_whole(self.total_cents())
     }
 }
 
@@ -39,13 +70,20 @@ impl Cart {
         Self { items: Vec::new() }
     }
 
-    pub fn add_item(&mut self, product: Product, quantity: i32) {
+    /// Add units of a product, capped at its per-order limit
+    /// Returns the limit when it capped the line
+    pub fn add_item(&mut self, product: Product, quantity: i32) -> Option<i32> {
         // Check if product already exists in cart
-        if let Some(item) = self.items.iter_mut().find(|i| i.product.id == product.id) {
-            item.quantity += quantity;
-        } else {
-            self.items.push(CartItem::new(product, quantity));
+        let existing = self.items.iter().position(|i| i.product.id == product.id);
+        let current = existing.map_or(0, |i| self.items[i].quantity);
+        let capped = product.clamp_quantity(current + quantity);
+        let limit = product.quantity_cap().filter(|_| capped < current + quantity);
+        match existing {
+            Some(i) => self.items[i].quantity = capped,
+            None if capped > 0 => self.items.push(CartItem::new(product, capped)),
+            None => {}
         }
+        limit
     }
 
     pub fn remove_item(&mut self, product_id: Uuid) {
@@ -62,9 +100,15 @@ impl Cart {
         }
     }
 
-    pub fn increment_item(&mut self, product_id: Uuid) {
-        if let Some(item) = self.items.iter_mut().find(|i| i.product.id == product_id) {
-            item.quantity += 1;
+    /// Add one unit to a line; returns the product's limit if it's already reached
+    pub fn increment_item(&mut self, product_id: Uuid) -> Option<i32> {
+        let item = self.items.iter_mut().find(|i| i.product.id == product_id)?;
+        let next = item.product.clamp_quantity(item.quantity + 1);
+        if next > item.quantity {
+            item.quantity = next;
+            None
+        } else {
+            item.product.quantity_cap()
         }
     }
 
@@ -78,6 +122,73 @@ impl Cart {
         }
     }
 
+    /// Update cart lines with the latest catalog data for `region_id` (price, stock, availability)
+    /// Lines from other regions are left to the region-conflict prompt
+    pub fn refresh_prices(&mut self, products: &[Product], region_id: &str) -> CatalogChanges {
+        let mut changes = CatalogChanges::default();
+        // An empty catalog hasn't loaded yet: there's nothing to compare against
+        if products.is_empty() {
+            return changes;
+        }
+        for item in &mut self.items {
+            match products.iter().find(|p| p.id == item.product.id) {
+                Some(latest) => {
+                    if latest.price_cents != item.product.price_cents {
+                        item.price_changed = true;
+                        changes.repriced.push(latest.name.clone());
+                    }
+                    item.unavailable = false;
+                    item.product = latest.clone();
+                }
+                None if item.product.region_id == region_id && !item.unavailable => {
+                    item.unavailable = true;
+                    changes.unavailable.push(item.product.name.clone());
+                }
+                None => {}
+            }
+        }
+        changes
+    }
+
+    /// Lines flagged as no longer in the catalog
+    pub fn unavailable_items(&self) -> impl Iterator<Item = &CartItem> {
+        self.items.iter().filter(|i| i.unavailable)
+    }
+
+    /// Remove items that are no longer in the catalog or out of stock
+    /// Returns how many items were removed
+    pub fn prune_unavailable(&mut self, products: &[Product]) -> usize {
+        let before = self.items.len();
+        self.items
+            .retain(|i| products.iter().any(|p| p.id == i.product.id && p.in_stock));
+        before - self.items.len()
+    }
+
+    /// Clear price-change flags once the customer has seen them
+    pub fn acknowledge_price_changes(&mut self) {
+        for item in &mut self.items {
+            item.price_changed = false;
+        }
+    }
+
+    /// Toggle the subscription upsell on a line (no-op if the product doesn't offer it)
+    pub fn toggle_subscribe(&mut self, product_id: Uuid) {
+        if let Some(item) = self.items.iter_mut().find(|i| i.product.id == product_id) {
+            if item.product.offers_subscription() {
+                item.subscribe = !item.subscribe;
+            }
+        }
+    }
+
+    /// Lines the customer chose to receive as a subscription
+    pub fn subscribed_items(&self) -> impl Iterator<Item = &CartItem> {
+        self.items.iter().filter(|i| i.subscribe)
+    }
+
+    pub fn savings_cents(&self) -> i32 {
+        self.items.iter().map(|i| i.savings_cents()).sum()
+    }
+
     pub fn total_items(&self) -> i32 {
         self.items.iter().map(|i| i.quantity).sum()
     }
@@ -86,8 +197,17 @@ impl Cart {
         self.items.iter().map(|i| i.total_cents()).sum()
     }
 
-    pub fn subtotal_display(&self) -> String {
-        format!("${}", self.subtotal_cents() / 100)
+    pub fn total_weight_oz(&self) -> i32 {
+        self.items.iter().map(|i| i.product.weight_oz * i.quantity).sum()
+    }
+
+    /// Shipping (cents) for this cart in the region
+    pub fn shipping_cents(&self, region: &Region) -> i32 {
+        region.shipping_cents(self.subtotal_cents(), self.total_weight_oz())
+    }
+
+    pub fn subtotal_display(&self, region: &Region) -> String {
+        region.format_cents(self.subtotal_cents())
     }
 
     pub fn is_empty(&self) -> bool {
@@ -99,3 +219,127 @@ impl Cart {
     }
 }
 
+/// What a catalog reload changed in the cart
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CatalogChanges {
+    /// Names of products whose price changed
+    pub repriced: Vec<String>,
+    /// Names of products that just left the catalog
+    pub unavailable: Vec<String>,
+}
+
+/// Cart saved between sessions, with the time it was saved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedCart {
+    pub saved_at: DateTime<Utc>,
+    pub cart: Cart,
+}
+
+impl PersistedCart {
+    pub fn new(cart: Cart) -> Self {
+        Self {
+            saved_at: Utc::now(),
+            cart,
+        }
+    }
+
+    /// Age of the saved cart in whole days
+    pub fn age_days(&self) -> i64 {
+        (Utc::now() - self.saved_at).num_days()
+    }
+
+    /// Whether the cart is older than the allowed maximum
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        Utc::now() - self.saved_at > max_age
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_tiers_is_flat_pricing() {
+        let item = CartItem::new(Product::sample("segfault", 2200), 5);
+        assert_eq!(item.total_cents(), 11000);
+        assert!(!item.has_tier_discount());
+    }
+
+    #[test]
+    fn tier_applies_from_its_minimum_quantity() {
+        let p = Product { price_tiers: vec![(3, 1800), (6, 1500)], ..Product::sample("segfault", 2000) };
+        assert_eq!(CartItem::new(p.clone(), 2).total_cents(), 4000);
+        assert_eq!(CartItem::new(p.clone(), 3).total_cents(), 5400);
+        assert_eq!(CartItem::new(p.clone(), 5).total_cents(), 9000);
+        assert_eq!(CartItem::new(p.clone(), 6).total_cents(), 9000);
+        assert_eq!(CartItem::new(p, 7).total_cents(), 10500);
+    }
+
+    #[test]
+    fn best_tier_wins_regardless_of_order() {
+        let p = Product { price_tiers: vec![(6, 1500), (3, 1800)], ..Product::sample("segfault", 2000) };
+        assert_eq!(CartItem::new(p, 6).unit_price_cents(), 1500);
+    }
+
+    #[test]
+    fn tier_never_raises_the_price() {
+        let p = Product { price_tiers: vec![(2, 2500)], ..Product::sample("segfault", 2000) };
+        assert_eq!(CartItem::new(p, 2).unit_price_cents(), 2000);
+    }
+
+    #[test]
+    fn adding_beyond_the_limit_clamps_the_line() {
+        let mut p = Product::sample("segfault", 2000);
+        p.max_per_order = Some(3);
+        let mut cart = Cart::new();
+
+        assert_eq!(cart.add_item(p.clone(), 2), None);
+        assert_eq!(cart.add_item(p.clone(), 2), Some(3));
+        assert_eq!(cart.items[0].quantity, 3);
+        assert_eq!(cart.increment_item(p.id), Some(3));
+        assert_eq!(cart.items[0].quantity, 3);
+    }
+
+    #[test]
+    fn stock_caps_below_the_per_order_limit() {
+        let mut p = Product::sample("segfault", 2000);
+        p.max_per_order = Some(5);
+        p.stock_quantity = Some(2);
+        let mut cart = Cart::new();
+
+        assert_eq!(cart.add_item(p.clone(), 3), Some(2));
+        assert_eq!(cart.items[0].quantity, 2);
+        assert_eq!(p.cap_message(2), "only 2 left");
+
+        p.stock_quantity = Some(0);
+        let mut cart = Cart::new();
+        assert_eq!(cart.add_item(p.clone(), 1), Some(0));
+        assert!(cart.is_empty());
+    }
+
+    #[test]
+    fn unlimited_products_just_add_up() {
+        let p = Product::sample("segfault", 2000);
+        let mut cart = Cart::new();
+        cart.add_item(p.clone(), 2);
+        assert_eq!(cart.increment_item(p.id), None);
+        assert_eq!(cart.items[0].quantity, 3);
+    }
+
+    #[test]
+    fn subtotal_keeps_the_cents() {
+        let mut cart = Cart::new();
+        cart.add_item(Product::sample("segfault", 2250), 1);
+        assert_eq!(cart.subtotal_display(&Region::default()), "$22.50");
+    }
+
+    #[test]
+    fn weight_adds_up_across_lines() {
+        let mut cart = Cart::new();
+        cart.add_item(Product::sample("segfault", 2000), 2);
+        let mut heavy = Product::sample("segfault", 2000);
+        heavy.weight_oz = 32;
+        cart.add_item(heavy, 1);
+        assert_eq!(cart.total_weight_oz(), 56);
+    }
+}
@@ -3,10 +3,12 @@ pub mod cart;
 pub mod order;
 pub mod user;
 pub mod region;
+pub mod settings;
 
 pub use product::*;
 pub use cart::*;
 pub use order::*;
 pub use user::*;
 pub use region::*;
+pub use settings::*;
 
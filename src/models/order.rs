@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use super::{CartItem, ShippingAddress};
+use super::{format_cents_in, CartItem, Region, ShippingAddress};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -27,31 +27,203 @@ impl std::fmt::Display for OrderStatus {
     }
 }
 
+/// Lean order line stored with an order
+/// Keeps only what the order needs, so orders don't embed (or depend on) the full product schema
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OrderLine {
+    pub product_id: Uuid,
+    pub name: String,
+    pub unit_price_cents: i32,
+    pub quantity: i32,
+    /// Chosen grind/variant (e.g. "whole beans")
+    pub variant: String,
+    #[serde(default)]
+    pub subscription: bool,
+}
+
+impl OrderLine {
+    pub fn total_cents(&self) -> i32 {
+        self.unit_price_cents * self.quantity
+    }
+}
+
+impl From<&CartItem> for OrderLine {
+    fn from(item: &CartItem) -> Self {
+        Self {
+            product_id: item.product.id,
+            name: item.product.name.clone(),
+            unit_price_cents: item.unit_price_cents(),
+            quantity: item.quantity,
+            variant: item.product.bean_type.clone(),
+            subscription: item.subscribe,
+        }
+    }
+}
+
+/// Purchaser contact and note for an order shipped to someone else
+/// The order's shipping address is the recipient's; the purchaser is unset when they
+/// pay at browser checkout, which collects their details itself
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GiftDetails {
+    pub purchaser_name: Option<String>,
+    pub purchaser_email: Option<String>,
+    pub message: String,
+}
+
+/// `Order::gift` as the flat columns of the orders table
+mod gift_columns {
+    use super::GiftDetails;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Default, Serialize, Deserialize)]
+    #[serde(default)]
+    struct GiftColumns {
+        is_gift: bool,
+        purchaser_name: Option<String>,
+        purchaser_email: Option<String>,
+        gift_message: Option<String>,
+    }
+
+    pub fn serialize<S: Serializer>(gift: &Option<GiftDetails>, serializer: S) -> Result<S::Ok, S::Error> {
+        let columns = gift.as_ref().map_or_else(GiftColumns::default, |gift| GiftColumns {
+            is_gift: true,
+            purchaser_name: gift.purchaser_name.clone(),
+            purchaser_email: gift.purchaser_email.clone(),
+            gift_message: Some(gift.message.clone()).filter(|m| !m.is_empty()),
+        });
+        columns.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<GiftDetails>, D::Error> {
+        let columns = GiftColumns::deserialize(deserializer)?;
+        Ok(columns.is_gift.then(|| GiftDetails {
+            purchaser_name: columns.purchaser_name,
+            purchaser_email: columns.purchaser_email,
+            message: columns.gift_message.unwrap_or_default(),
+        }))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
     pub id: Uuid,
     pub user_id: Uuid,
-    pub items: Vec<CartItem>,
+    pub items: Vec<OrderLine>,
     pub shipping_address: ShippingAddress,
+    /// Set when the order is a gift: billed to the purchaser, shipped to the recipient
+    #[serde(flatten, with = "gift_columns")]
+    pub gift: Option<GiftDetails>,
     pub subtotal_cents: i32,
     pub shipping_cents: i32,
     pub total_cents: i32,
+    /// Currency the amounts were charged in, from the region the order was placed in
+    #[serde(default = "default_currency")]
+    pub currency: String,
     pub status: OrderStatus,
+    /// Carrier tracking, set once the order ships
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracking_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carrier: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Orders saved before the currency was recorded were all placed in dollars
+fn default_currency() -> String {
+    Region::default().currency
+}
+
 impl Order {
+    /// An amount in the order's own currency, whichever region is selected now
+    pub fn format_cents(&self, cents: i32) -> String {
+        format_cents_in(&self.currency, cents)
+    }
+
     pub fn total_display(&self) -> String {
-        format!("${:.2}", self.total_cents as f64 / 100.0)
+        self.format_cents(self.total_cents)
     }
 
     pub fn subtotal_display(&self) -> String {
-        format!("${:.2}", self.subtotal_cents as f64 / 100.0)
+        self.format_cents(self.subtotal_cents)
     }
 
     pub fn shipping_display(&self) -> String {
-        format!("${:.2}", self.shipping_cents as f64 / 100.0)
+        self.format_cents(self.shipping_cents)
+    }
+
+    /// Tracking number worth showing: only once the order has actually left the roastery
+    pub fn tracking(&self) -> Option<&str> {
+        match self.status {
+            OrderStatus::Shipped | OrderStatus::Delivered => self.tracking_number.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// "UPS 1Z999AA10123456784", or just the number when the carrier is unknown
+    pub fn tracking_line(&self) -> Option<String> {
+        let number = self.tracking()?;
+        Some(match &self.carrier {
+            Some(carrier) => format!("{} {}", carrier, number),
+            None => number.to_string(),
+        })
+    }
+
+    /// Short order number for display (first 8 chars of the id)
+    pub fn short_id(&self) -> String {
+        self.id.to_string()[..8].to_string()
+    }
+
+    /// Estimated delivery date (roasted within a day, then ~4 days shipping)
+    pub fn estimated_delivery(&self) -> DateTime<Utc> {
+        self.created_at + chrono::Duration::days(5)
+    }
+
+    /// Preview of the confirmation email the backend sends for this order
+    pub fn to_email_preview(&self, to: &str, greeting_name: &str) -> String {
+        format!(
+            "to: {}\nsubject: your ANORA Labs order #{}\n\nhi {},\n\nthanks for your order! here's what's on its way:\n\n{}\nestimated delivery: {}\n\n— ANORA Labs\n",
+            to,
+            self.short_id(),
+            greeting_name,
+            self.to_receipt_text(),
+            self.estimated_delivery().format("%a, %b %-d"),
+        )
+    }
+
+    /// Plain-text receipt for copying or saving
+    pub fn to_receipt_text(&self) -> String {
+        let mut lines = vec![
+            "ANORA Labs receipt".to_string(),
+            format!("order #{}", self.short_id()),
+            format!("date {}", self.created_at.format("%Y-%m-%d %H:%M UTC")),
+            String::new(),
+        ];
+        for item in &self.items {
+            let label = format!("{} x {} ({})", item.quantity, item.name, item.variant);
+            let label = if item.subscription {
+                format!("{} [subscription]", label)
+            } else {
+                label
+            };
+            lines.push(format!("{:<40} {:>9}", label, self.format_cents(item.total_cents())));
+        }
+        lines.push(String::new());
+        lines.push(format!("{:<40} {:>9}", "subtotal", self.subtotal_display()));
+        lines.push(format!("{:<40} {:>9}", "shipping", self.shipping_display()));
+        lines.push(format!("{:<40} {:>9}", "total", self.total_display()));
+        lines.push(String::new());
+        lines.push(format!("ship to: {}", self.shipping_address.name));
+        lines.push(self.shipping_address.display_line());
+        if let Some(gift) = &self.gift {
+            if let Some(purchaser) = &gift.purchaser_name {
+                lines.push(format!("gift from: {}", purchaser));
+            }
+            if !gift.message.is_empty() {
+                lines.push(format!("gift message: {}", gift.message));
+            }
+        }
+        lines.join("\n") + "\n"
     }
 }
 
@@ -66,6 +238,24 @@ pub struct Subscription {
     pub created_at: DateTime<Utc>,
 }
 
+impl Subscription {
+    /// Next delivery relative to `now`: "ships today", "ships in 3 days", then "ships Feb 14"
+    /// a week or more out; nothing for paused or cancelled subscriptions
+    pub fn next_delivery_at(&self, now: DateTime<Utc>) -> Option<String> {
+        if self.status != SubscriptionStatus::Active {
+            return None;
+        }
+        let next = self.next_delivery?;
+        let days = (next.date_naive() - now.date_naive()).num_days();
+        Some(match days {
+            ..=0 => "ships today".to_string(),
+            1 => "ships tomorrow".to_string(),
+            2..=6 => format!("ships in {} days", days),
+            _ => format!("ships {}", next.format("%b %-d")),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum SubscriptionStatus {
@@ -84,3 +274,40 @@ impl std::fmt::Display for SubscriptionStatus {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn subscription(status: SubscriptionStatus, next_delivery: Option<DateTime<Utc>>) -> Subscription {
+        Subscription {
+            id: Uuid::nil(),
+            user_id: Uuid::nil(),
+            product_id: Uuid::nil(),
+            product_name: "segfault".to_string(),
+            status,
+            next_delivery,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn next_delivery_reads_relative_then_as_a_date() {
+        let now = Utc.with_ymd_and_hms(2026, 2, 11, 18, 0, 0).unwrap();
+        let at = |day: u32| Some(Utc.with_ymd_and_hms(2026, 2, day, 9, 0, 0).unwrap());
+        let active = |day| subscription(SubscriptionStatus::Active, at(day)).next_delivery_at(now);
+        assert_eq!(active(11).as_deref(), Some("ships today"));
+        assert_eq!(active(12).as_deref(), Some("ships tomorrow"));
+        assert_eq!(active(14).as_deref(), Some("ships in 3 days"));
+        assert_eq!(active(20).as_deref(), Some("ships Feb 20"));
+    }
+
+    #[test]
+    fn next_delivery_is_hidden_unless_active() {
+        let now = Utc::now();
+        let soon = Some(now + chrono::Duration::days(2));
+        assert_eq!(subscription(SubscriptionStatus::Paused, soon).next_delivery_at(now), None);
+        assert_eq!(subscription(SubscriptionStatus::Cancelled, soon).next_delivery_at(now), None);
+        assert_eq!(subscription(SubscriptionStatus::Active, None).next_delivery_at(now), None);
+    }
+}
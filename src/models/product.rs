@@ -1,11 +1,24 @@
-use serde::{Deserialize, Serialize};
+use super::Region;
+use crate::ui::Theme;
+use ratatui::style::Color;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum ProductCategory {
     Featured,
     Originals,
+    /// Any category this client doesn't know about yet
+    #[default]
+    #[serde(other)]
+    Other,
+}
+
+impl ProductCategory {
+    /// Display order of the shop sections
+    pub const ALL: [ProductCategory; 3] = [Self::Featured, Self::Originals, Self::Other];
 }
 
 impl std::fmt::Display for ProductCategory {
@@ -13,6 +26,7 @@ impl std::fmt::Display for ProductCategory {
         match self {
             ProductCategory::Featured => write!(f, "~ featured ~"),
             ProductCategory::Originals => write!(f, "~ originals ~"),
+            ProductCategory::Other => write!(f, "~ more ~"),
         }
     }
 }
@@ -25,6 +39,11 @@ pub enum RoastLevel {
     Dark,
 }
 
+impl RoastLevel {
+    /// Display order of the roast sections, lightest first
+    pub const ALL: [RoastLevel; 3] = [Self::Light, Self::Medium, Self::Dark];
+}
+
 impl std::fmt::Display for RoastLevel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -35,11 +54,24 @@ impl std::fmt::Display for RoastLevel {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum ProductType {
     Subscription,
     OneTime,
+    /// Any type this client doesn't know about yet (sold like a one-time product)
+    #[default]
+    #[serde(other)]
+    Other,
+}
+
+/// Deserialize a missing/null value as the type's default (the "other" bucket)
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,19 +81,145 @@ pub struct Product {
     pub slug: String,
     pub description: String,
     pub price_cents: i32,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub category: ProductCategory,
     pub roast_level: Option<RoastLevel>,
     pub weight_oz: i32,
     pub bean_type: String,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub product_type: ProductType,
     pub highlight_color: String,
     pub region_id: String,
     pub in_stock: bool,
+    /// Discount (percent) for buying this product as a recurring subscription; 0 = not offered
+    #[serde(default)]
+    pub subscription_discount_pct: i32,
+    /// Quantity price breaks as `(min_qty, price_cents)` per unit; empty = flat pricing
+    #[serde(default)]
+    pub price_tiers: Vec<(i32, i32)>,
+    /// Localized descriptions keyed by locale or region id
+    #[serde(default)]
+    pub translations: HashMap<String, String>,
+    /// Most units one customer can buy in a single order; None = no limit
+    #[serde(default)]
+    pub max_per_order: Option<i32>,
+    /// Units left to sell; None = not tracked (unlimited)
+    #[serde(default)]
+    pub stock_quantity: Option<i32>,
+    /// Average review score out of 5
+    #[serde(default)]
+    pub rating: Option<f32>,
+    #[serde(default)]
+    pub review_count: Option<i32>,
+}
+
+/// Five-star display of a 0-5 rating, rounded to the nearest whole star
+pub fn star_string(rating: f32) -> String {
+    let filled = rating.clamp(0.0, 5.0).round() as usize;
+    format!("{}{}", "★".repeat(filled), "☆".repeat(5 - filled))
 }
 
 impl Product {
-    pub fn price_display(&self) -> String {
-        format!("${}", self.price_cents / 100)
+    pub fn price_display(&self, region: &Region) -> String {
+        region.format_whole(self.price_cents)
+    }
+
+    /// Whether a one-time product can be converted to a subscription at checkout
+    pub fn offers_subscription(&self) -> bool {
+        self.product_type == ProductType::OneTime && self.subscription_discount_pct > 0
+    }
+
+    /// Per-unit price for a quantity, using the best applicable price tier
+    pub fn unit_price_cents(&self, quantity: i32) -> i32 {
+        self.price_tiers
+            .iter()
+            .filter(|(min_qty, _)| quantity >= *min_qty)
+            .map(|(_, price)| *price)
+            .fold(self.price_cents, i32::min)
+    }
+
+    /// Per-unit price for a quantity when bought as a subscription
+    pub fn subscription_price_cents(&self, quantity: i32) -> i32 {
+        self.unit_price_cents(quantity) * (100 - self.subscription_discount_pct.clamp(0, 100)) / 100
+    }
+
+    /// "buy 3 for $19.80 each" hints, cheapest-last
+    pub fn tier_hints(&self, region: &Region) -> Vec<String> {
+        let mut tiers: Vec<_> = self
+            .price_tiers
+            .iter()
+            .filter(|(_, price)| *price < self.price_cents)
+            .collect();
+        tiers.sort();
+        tiers
+            .into_iter()
+            .map(|(min_qty, price)| format!("buy {} for {} each", min_qty, region.format_cents(*price)))
+            .collect()
+    }
+
+    /// Most units one order can hold: the per-order limit or the stock left, whichever is lower
+    pub fn quantity_cap(&self) -> Option<i32> {
+        let stock = self.stock_quantity.map(|stock| stock.max(0));
+        match (self.max_per_order, stock) {
+            (Some(max), Some(stock)) => Some(max.min(stock)),
+            (max, stock) => max.or(stock),
+        }
+    }
+
+    /// Cap a quantity to this product's per-order limit and stock
+    pub fn clamp_quantity(&self, quantity: i32) -> i32 {
+        self.quantity_cap().map_or(quantity, |cap| quantity.min(cap))
+    }
+
+    /// Highlight color: `highlight_color` when it parses, else the name-based brand color
+    pub fn color(&self) -> Color {
+        Theme::product_color(self)
+    }
+
+    /// Stock is tracked and none are left
+    pub fn is_sold_out(&self) -> bool {
+        self.stock_quantity.is_some_and(|stock| stock <= 0)
+    }
+
+    /// Why the quantity stops at `cap`: "sold out", "only 2 left" or "limit 3 per order"
+    pub fn cap_message(&self, cap: i32) -> String {
+        if self.is_sold_out() {
+            format!("{} is sold out", self.name)
+        } else if self.max_per_order == Some(cap) {
+            format!("limit {} per order", cap)
+        } else {
+            format!("only {} left", cap)
+        }
+    }
+
+    /// Description for a region: its locale, then its id, then the default description
+    pub fn description_for(&self, region: &Region) -> &str {
+        region
+            .locale
+            .iter()
+            .chain(std::iter::once(&region.id))
+            .find_map(|key| self.translations.get(key).filter(|text| !text.is_empty()))
+            .unwrap_or(&self.description)
+    }
+
+    /// Case-insensitive substring match against the name, bean type and (regional) description
+    /// An empty query matches everything
+    pub fn matches_filter(&self, query: &str, region: &Region) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || [self.name.as_str(), self.bean_type.as_str(), self.description_for(region)]
+                .iter()
+                .any(|field| field.to_lowercase().contains(&query))
+    }
+
+    /// "★★★★☆ 4.2 (128)", or None when the product has no rating
+    pub fn rating_line(&self) -> Option<String> {
+        let rating = self.rating?;
+        let mut line = format!("{} {:.1}", star_string(rating), rating);
+        if let Some(count) = self.review_count {
+            line.push_str(&format!(" ({})", count));
+        }
+        Some(line)
     }
 
     pub fn details_line(&self) -> String {
@@ -72,3 +230,122 @@ impl Product {
         }
     }
 }
+
+#[cfg(test)]
+impl Product {
+    /// In-stock one-time featured product for tests
+    pub fn sample(name: &str, price_cents: i32) -> Self {
+        serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "name": name,
+            "slug": name,
+            "description": format!("{} description", name),
+            "price_cents": price_cents,
+            "category": "featured",
+            "roast_level": "dark",
+            "weight_oz": 12,
+            "bean_type": "whole beans",
+            "product_type": "one_time",
+            "highlight_color": "#ff8800",
+            "region_id": "global",
+            "in_stock": true,
+        }))
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `Product::sample` as a database row, with some columns replaced
+    fn sample_row(overrides: serde_json::Value) -> Product {
+        let mut row = serde_json::to_value(Product::sample("segfault", 2200)).unwrap();
+        for (column, value) in overrides.as_object().unwrap() {
+            row[column] = value.clone();
+        }
+        serde_json::from_value(row).unwrap()
+    }
+
+    #[test]
+    fn color_prefers_highlight_color_over_the_name() {
+        let mut product = Product::sample("segfault", 2200);
+        product.highlight_color = String::new();
+        assert_eq!(product.color(), Theme::BLUE);
+
+        product.highlight_color = "#ff24bd".to_string();
+        assert_eq!(product.color(), Color::Rgb(255, 36, 189));
+
+        product.highlight_color = "pink".to_string();
+        assert_eq!(product.color(), Theme::BLUE);
+    }
+
+    #[test]
+    fn unknown_category_and_type_fall_back_to_other() {
+        let product = sample_row(serde_json::json!({ "category": "limited_edition", "product_type": "bundle" }));
+        assert_eq!(product.category, ProductCategory::Other);
+        assert_eq!(product.product_type, ProductType::Other);
+    }
+
+    #[test]
+    fn null_category_falls_back_to_other() {
+        let product = sample_row(serde_json::json!({ "category": null }));
+        assert_eq!(product.category, ProductCategory::Other);
+        assert_eq!(product.product_type, ProductType::OneTime);
+    }
+
+    #[test]
+    fn description_falls_back_from_locale_to_region_to_default() {
+        let mut product = Product::sample("segfault", 2200);
+        product.description = "default".to_string();
+        product.translations.insert("uz".to_string(), "by region".to_string());
+        product.translations.insert("ru".to_string(), "by locale".to_string());
+
+        let mut region = Region { id: "uz".to_string(), ..Region::default() };
+        assert_eq!(product.description_for(&region), "by region");
+        region.locale = Some("ru".to_string());
+        assert_eq!(product.description_for(&region), "by locale");
+        assert_eq!(product.description_for(&Region::default()), "default");
+    }
+
+    #[test]
+    fn filter_matches_name_bean_type_and_description_case_insensitively() {
+        let product = Product::sample("Segfault", 2200);
+        let region = Region::default();
+        assert!(product.matches_filter("", &region));
+        assert!(product.matches_filter("SEG", &region));
+        assert!(product.matches_filter("whole", &region));
+        assert!(product.matches_filter("description", &region));
+        assert!(!product.matches_filter("decaf", &region));
+    }
+
+    #[test]
+    fn stars_round_to_the_nearest_whole_star() {
+        assert_eq!(star_string(0.0), "☆☆☆☆☆");
+        assert_eq!(star_string(2.4), "★★☆☆☆");
+        assert_eq!(star_string(2.5), "★★★☆☆");
+        assert_eq!(star_string(4.2), "★★★★☆");
+        assert_eq!(star_string(5.0), "★★★★★");
+        // Out-of-range scores are clamped
+        assert_eq!(star_string(7.0), "★★★★★");
+        assert_eq!(star_string(-1.0), "☆☆☆☆☆");
+    }
+
+    #[test]
+    fn rating_line_is_omitted_without_a_rating() {
+        let mut product = Product::sample("segfault", 2200);
+        assert_eq!(product.rating_line(), None);
+
+        product.rating = Some(4.2);
+        assert_eq!(product.rating_line().as_deref(), Some("★★★★☆ 4.2"));
+        product.review_count = Some(128);
+        assert_eq!(product.rating_line().as_deref(), Some("★★★★☆ 4.2 (128)"));
+    }
+
+    #[test]
+    fn known_values_still_deserialize() {
+        let product = sample_row(serde_json::json!({ "product_type": "subscription" }));
+        assert_eq!(product.category, ProductCategory::Featured);
+        assert_eq!(product.product_type, ProductType::Subscription);
+    }
+}
@@ -1,5 +1,22 @@
 use serde::{Deserialize, Serialize};
 
+/// Shipping (cents) on orders under the region's free-shipping threshold
+pub const FLAT_SHIPPING_CENTS: i32 = 800;
+
+/// Weight-based shipping: a base rate covering the first `included_oz`, plus a charge per ounce beyond it
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WeightShipping {
+    pub base_cents: i32,
+    pub included_oz: i32,
+    pub per_oz_cents: i32,
+}
+
+impl WeightShipping {
+    pub fn cost_cents(&self, weight_oz: i32) -> i32 {
+        self.base_cents + (weight_oz - self.included_oz).max(0) * self.per_oz_cents
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Region {
     pub id: String,
@@ -8,6 +25,12 @@ pub struct Region {
     pub flag: String,
     pub currency: String,
     pub free_shipping_threshold: i32,
+    /// Content locale for the region (e.g. "uz", "ru"); None = default content
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Charge by cart weight below the free threshold; None = flat rate
+    #[serde(default)]
+    pub weight_shipping: Option<WeightShipping>,
 }
 
 impl Default for Region {
@@ -19,6 +42,93 @@ impl Default for Region {
             flag: "🌎".to_string(),
             currency: "USD".to_string(),
             free_shipping_threshold: 40,
+            locale: None,
+            weight_shipping: None,
+        }
+    }
+}
+
+impl Region {
+    /// Subtotal (cents) from which shipping is free
+    pub fn free_shipping_threshold_cents(&self) -> i32 {
+        self.free_shipping_threshold * 100
+    }
+
+    /// Shipping (cents) on an order with this subtotal and weight: free from the threshold up,
+    /// otherwise the region's weight tier, or flat when it has none
+    pub fn shipping_cents(&self, subtotal_cents: i32, weight_oz: i32) -> i32 {
+        if subtotal_cents >= self.free_shipping_threshold_cents() {
+            0
+        } else {
+            self.weight_shipping
+                .map_or(FLAT_SHIPPING_CENTS, |tier| tier.cost_cents(weight_oz))
+        }
+    }
+
+    /// Format an amount in the region's currency, e.g. "$12.50", "€12.50", "12.50 so'm"
+    pub fn format_cents(&self, cents: i32) -> String {
+        format_cents_in(&self.currency, cents)
+    }
+
+    /// Whole units only, for list prices and badges, e.g. "$12"
+    pub fn format_whole(&self, cents: i32) -> String {
+        with_symbol(&self.currency, (cents / 100).to_string())
+    }
+}
+
+/// Format an amount in the given currency, for amounts fixed to one (like past orders)
+pub fn format_cents_in(currency: &str, cents: i32) -> String {
+    with_symbol(currency, format!("{:.2}", cents as f64 / 100.0))
+}
+
+/// Place the currency symbol where it's written for the currency; unknown codes trail
+fn with_symbol(currency: &str, amount: String) -> String {
+    match currency {
+        "USD" => format!("${}", amount),
+        "EUR" => format!("€{}", amount),
+        "GBP" => format!("£{}", amount),
+        "UZS" => format!("{} so'm", amount),
+        code => format!("{} {}", amount, code),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(currency: &str) -> Region {
+        Region {
+            currency: currency.to_string(),
+            ..Region::default()
         }
     }
+
+    #[test]
+    fn shipping_is_flat_below_the_threshold() {
+        let region = Region::default();
+        assert_eq!(region.shipping_cents(3999, 12), FLAT_SHIPPING_CENTS);
+        assert_eq!(region.shipping_cents(3999, 144), FLAT_SHIPPING_CENTS);
+        assert_eq!(region.shipping_cents(4000, 12), 0);
+    }
+
+    #[test]
+    fn weight_tier_charges_per_ounce_over_the_limit() {
+        let region = Region {
+            weight_shipping: Some(WeightShipping { base_cents: 600, included_oz: 24, per_oz_cents: 25 }),
+            ..Region::default()
+        };
+        assert_eq!(region.shipping_cents(2000, 12), 600);
+        assert_eq!(region.shipping_cents(2000, 24), 600);
+        assert_eq!(region.shipping_cents(2000, 36), 900);
+        assert_eq!(region.shipping_cents(4000, 36), 0);
+    }
+
+    #[test]
+    fn amounts_use_the_region_currency() {
+        assert_eq!(region("USD").format_cents(1250), "$12.50");
+        assert_eq!(region("EUR").format_cents(1250), "€12.50");
+        assert_eq!(region("GBP").format_whole(1250), "£12");
+        assert_eq!(region("UZS").format_whole(1250), "12 so'm");
+        assert_eq!(region("JPY").format_cents(1250), "12.50 JPY");
+    }
 }
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// User preferences kept in the local store between sessions
+/// Missing fields (older files) take their defaults
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Ask for a second enter on the confirmation step before the order is placed
+    pub confirm_before_order: bool,
+    /// Play the confetti after an order is placed
+    pub celebrate_orders: bool,
+    /// One-time warnings the user has dismissed, by notice key
+    pub dismissed_notices: BTreeSet<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            confirm_before_order: true,
+            celebrate_orders: true,
+            dismissed_notices: BTreeSet::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_use_the_safe_defaults() {
+        let settings: Settings = serde_json::from_str("{}").unwrap();
+        assert!(settings.confirm_before_order);
+        assert!(settings.celebrate_orders);
+
+        let settings: Settings = serde_json::from_str(r#"{"confirm_before_order": false}"#).unwrap();
+        assert!(!settings.confirm_before_order);
+    }
+}
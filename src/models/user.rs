@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -52,6 +52,22 @@ impl ShippingAddress {
         }
         parts.join(", ")
     }
+
+    /// Whether both name the same place, ignoring case and stray whitespace
+    /// (a different unit is a different address; name and phone don't matter)
+    pub fn is_same_place(&self, other: &ShippingAddress) -> bool {
+        self.place_key() == other.place_key()
+    }
+
+    fn place_key(&self) -> [String; 6] {
+        [&self.street_1, &self.street_2, &self.city, &self.state, &self.postal_code, &self.country]
+            .map(|field| normalize_field(field))
+    }
+}
+
+/// Trim, collapse internal whitespace and case-fold, so "123  Main St " matches "123 main st"
+fn normalize_field(field: &str) -> String {
+    field.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
 }
 
 /// Saved address for Supabase storage (includes user identification)
@@ -146,6 +162,90 @@ impl PaymentInfo {
             && !self.cvv.is_empty()
     }
 
+    /// Pragmatic email check (not RFC 5322): one `@`, something before it, and a dotted
+    /// domain after it, catching the obvious typos
+    pub fn is_email_valid(&self) -> bool {
+        let email = self.email.trim();
+        let Some((local, domain)) = email.split_once('@') else {
+            return false;
+        };
+        !local.is_empty()
+            && !domain.contains('@')
+            && !email.contains(char::is_whitespace)
+            && domain.split('.').count() > 1
+            && domain.split('.').all(|label| !label.is_empty())
+    }
+
+    /// Luhn checksum over the card number (13-16 digits), catching typos and transposed digits
+    pub fn is_card_valid(&self) -> bool {
+        let digits: Option<Vec<u32>> = self.card_number.chars().map(|c| c.to_digit(10)).collect();
+        let Some(digits) = digits.filter(|d| (13..=16).contains(&d.len())) else {
+            return false;
+        };
+        let sum: u32 = digits
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, &d)| match (i % 2 == 1, d * 2) {
+                (true, doubled) if doubled > 9 => doubled - 9,
+                (true, doubled) => doubled,
+                (false, _) => d,
+            })
+            .sum();
+        sum.is_multiple_of(10)
+    }
+
+    /// Expiry as typed into the combined field: "MM/YY", with the slash once the month is complete
+    pub fn expiry_display(&self) -> String {
+        if self.expiry_month.len() == 2 {
+            format!("{}/{}", self.expiry_month, self.expiry_year)
+        } else {
+            self.expiry_month.clone()
+        }
+    }
+
+    /// Type one character of an "MM/YY" (or "MMYY") expiry, rejecting anything that can't
+    /// lead to a real month; "4" becomes "04/" and "1/" becomes "01/"
+    pub fn push_expiry_char(&mut self, c: char) {
+        let month = &mut self.expiry_month;
+        match (month.len(), c) {
+            (0, '0' | '1') => month.push(c),
+            (0, '2'..='9') => *month = format!("0{c}"),
+            (1, '/') if month != "0" => month.insert(0, '0'),
+            (1, '0'..='9') => {
+                let typed = format!("{month}{c}");
+                if matches!(typed.parse::<u32>(), Ok(1..=12)) {
+                    *month = typed;
+                }
+            }
+            (2, '0'..='9') if self.expiry_year.len() < 2 => self.expiry_year.push(c),
+            _ => {}
+        }
+    }
+
+    /// Backspace in the combined expiry field; deleting past the slash removes the
+    /// month's last digit along with it
+    pub fn pop_expiry_char(&mut self) {
+        if self.expiry_year.pop().is_none() {
+            self.expiry_month.pop();
+        }
+    }
+
+    /// Expiry month as 1-12, if it's a valid month
+    pub fn expiry_month_number(&self) -> Option<u32> {
+        self.expiry_month.parse().ok().filter(|m| (1..=12).contains(m))
+    }
+
+    /// Whether the card is still valid in `now`'s month (cards expire after their last month)
+    /// Two-digit years are read as 20YY
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        let (Some(month), Ok(year)) = (self.expiry_month_number(), self.expiry_year.parse::<i32>()) else {
+            return true;
+        };
+        let year = if self.expiry_year.len() <= 2 { 2000 + year } else { year };
+        (year, month) < (now.year(), now.month())
+    }
+
     pub fn masked_card(&self) -> String {
         if self.card_number.len() >= 4 {
             format!("**** **** **** {}", &self.card_number[self.card_number.len() - 4..])
@@ -154,3 +254,116 @@ impl PaymentInfo {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn card(number: &str, month: &str, year: &str) -> PaymentInfo {
+        PaymentInfo {
+            card_number: number.to_string(),
+            expiry_month: month.to_string(),
+            expiry_year: year.to_string(),
+            ..PaymentInfo::default()
+        }
+    }
+
+    fn address(street_1: &str, street_2: &str, city: &str) -> ShippingAddress {
+        ShippingAddress {
+            street_1: street_1.to_string(),
+            street_2: street_2.to_string(),
+            city: city.to_string(),
+            country: "UZ".to_string(),
+            postal_code: "100000".to_string(),
+            ..ShippingAddress::default()
+        }
+    }
+
+    #[test]
+    fn same_place_ignores_case_and_whitespace() {
+        let saved = address("123 Main St", "", "Tashkent");
+        assert!(saved.is_same_place(&address("123 main st ", "", "tashkent")));
+        assert!(saved.is_same_place(&address("  123   MAIN\tSt", " ", "TASHKENT ")));
+        // Another unit or street is another place
+        assert!(!saved.is_same_place(&address("123 Main St", "apt 4", "Tashkent")));
+        assert!(!saved.is_same_place(&address("124 Main St", "", "Tashkent")));
+    }
+
+    fn email(address: &str) -> PaymentInfo {
+        PaymentInfo { email: address.to_string(), ..PaymentInfo::default() }
+    }
+
+    #[test]
+    fn email_accepts_ordinary_addresses() {
+        assert!(email("ada@anora.cafe").is_email_valid());
+        assert!(email("ada.lovelace+coffee@mail.example.uz").is_email_valid());
+        assert!(email(" ada@anora.cafe ").is_email_valid());
+    }
+
+    #[test]
+    fn email_rejects_the_obvious_mistakes() {
+        assert!(!email("abc").is_email_valid());
+        assert!(!email("@anora.cafe").is_email_valid());
+        assert!(!email("ada@anora").is_email_valid());
+        assert!(!email("ada@@anora.cafe").is_email_valid());
+        assert!(!email("ada@anora.").is_email_valid());
+        assert!(!email("ada lovelace@anora.cafe").is_email_valid());
+    }
+
+    #[test]
+    fn luhn_accepts_valid_numbers_and_rejects_typos() {
+        assert!(card("4242424242424242", "", "").is_card_valid());
+        assert!(card("5555555555554444", "", "").is_card_valid());
+        // Transposed digits
+        assert!(!card("4242424242424224", "", "").is_card_valid());
+        assert!(!card("4242", "", "").is_card_valid());
+        assert!(!card("", "", "").is_card_valid());
+    }
+
+    #[test]
+    fn expiry_is_checked_against_the_current_month() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        assert!(!card("", "10", "2026").is_expired_at(now));
+        assert!(!card("", "1", "27").is_expired_at(now));
+        assert!(card("", "09", "2026").is_expired_at(now));
+        assert!(card("", "12", "25").is_expired_at(now));
+        // Not a month
+        assert_eq!(card("", "13", "2030").expiry_month_number(), None);
+        assert!(card("", "13", "2030").is_expired_at(now));
+    }
+
+    fn type_expiry(typed: &str) -> PaymentInfo {
+        let mut info = PaymentInfo::default();
+        typed.chars().for_each(|c| info.push_expiry_char(c));
+        info
+    }
+
+    #[test]
+    fn combined_expiry_accepts_mm_yy_and_mmyy() {
+        for typed in ["12/29", "1229"] {
+            let info = type_expiry(typed);
+            assert_eq!((info.expiry_month.as_str(), info.expiry_year.as_str()), ("12", "29"));
+            assert_eq!(info.expiry_display(), "12/29");
+        }
+        assert_eq!(type_expiry("4").expiry_display(), "04/");
+        assert_eq!(type_expiry("1/").expiry_display(), "01/");
+        // Not a month: the 3 is rejected, then 0 makes "10"
+        assert_eq!(type_expiry("130").expiry_display(), "10/");
+        assert_eq!(type_expiry("00").expiry_display(), "0");
+        // Year stops at two digits
+        assert_eq!(type_expiry("0530311").expiry_display(), "05/30");
+    }
+
+    #[test]
+    fn expiry_backspace_crosses_the_slash() {
+        let mut info = type_expiry("12/2");
+        info.pop_expiry_char();
+        assert_eq!(info.expiry_display(), "12/");
+        info.pop_expiry_char();
+        assert_eq!(info.expiry_display(), "1");
+        info.pop_expiry_char();
+        info.pop_expiry_char();
+        assert_eq!(info.expiry_display(), "");
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::Utc;
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::Style,
@@ -6,8 +7,10 @@ use ratatui::{
     Frame,
 };
 
-use super::Theme;
+use super::{fit_width, Theme};
 use crate::app::{AccountSection, App};
+use crate::i18n::t;
+use crate::models::Order;
 
 pub fn render_account(f: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::horizontal([
@@ -23,10 +26,12 @@ pub fn render_account(f: &mut Frame, area: Rect, app: &App) {
 
 fn render_account_menu(f: &mut Frame, area: Rect, app: &App) {
     let sections = [
-        (AccountSection::OrderHistory, "order history"),
-        (AccountSection::Subscriptions, "subscriptions"),
-        (AccountSection::Faq, "faq"),
-        (AccountSection::About, "about"),
+        (AccountSection::OrderHistory, t("account.order_history")),
+        (AccountSection::Subscriptions, t("account.subscriptions")),
+        (AccountSection::Faq, t("account.faq")),
+        (AccountSection::About, t("account.about")),
+        (AccountSection::WhatsNew, t("account.whats_new")),
+        (AccountSection::Settings, t("account.settings")),
     ];
 
     let lines: Vec<Line> = sections
@@ -34,19 +39,15 @@ fn render_account_menu(f: &mut Frame, area: Rect, app: &App) {
         .map(|(section, label)| {
             let is_selected = app.account_section == *section;
             let style = if is_selected {
-                Style::default().fg(Theme::FG).bg(Theme::PINK)
+                Style::default().fg(app.theme.fg()).bg(app.theme.accent())
             } else {
-                Style::default().fg(Theme::DIMMED)
+                Style::default().fg(app.theme.dimmed())
             };
 
             // Create a line that spans the full width with padding (same as shop.rs)
-            let padding = " ".repeat(1);
+            let padding = " ";
             let content_width = area.width.saturating_sub(4) as usize;
-            let menu_label = if label.len() > content_width {
-                label.chars().take(content_width).collect::<String>()
-            } else {
-                format!("{:<width$}", label, width = content_width)
-            };
+            let menu_label = fit_width(label, content_width);
 
             Line::from(Span::styled(
                 format!("{}{}{}", padding, menu_label, padding),
@@ -61,10 +62,15 @@ fn render_account_menu(f: &mut Frame, area: Rect, app: &App) {
 
 fn render_account_content(f: &mut Frame, area: Rect, app: &App) {
     let (content, is_empty_state) = match app.account_section {
-        AccountSection::OrderHistory => render_order_history(app),
+        AccountSection::OrderHistory => match app.selected_order() {
+            Some(order) if app.show_order_detail => (render_order_detail(&app.theme, order), false),
+            _ => render_order_history(app, area.height as usize),
+        },
         AccountSection::Subscriptions => render_subscriptions(app),
-        AccountSection::Faq => (render_faq(), false),
-        AccountSection::About => (render_about(), false),
+        AccountSection::Faq => (render_faq(&app.theme), false),
+        AccountSection::About => (render_about(app), false),
+        AccountSection::WhatsNew => (super::whats_new_lines(&app.theme), false),
+        AccountSection::Settings => (render_settings(app), false),
     };
 
     let paragraph = if is_empty_state {
@@ -78,47 +84,108 @@ fn render_account_content(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(paragraph, area);
 }
 
-fn render_order_history(app: &App) -> (Vec<Line<'static>>, bool) {
+/// Only builds lines for the visible window of orders, starting at the scroll offset
+fn render_order_history(app: &App, height: usize) -> (Vec<Line<'static>>, bool) {
     if app.orders.is_empty() {
         (
             vec![Line::from(Span::styled(
-                "no orders found",
-                Style::default().fg(Theme::DIMMED),
+                t("account.no_orders"),
+                Style::default().fg(app.theme.dimmed()),
             ))],
             true,
         )
     } else {
-        (
-            app.orders
-                .iter()
-                .map(|order| {
-                    Line::from(vec![
-                        Span::styled(
-                            format!("Order #{} - ", &order.id.to_string()[..8]),
-                            Style::default().fg(Theme::FG),
-                        ),
-                        Span::styled(
-                            order.total_display(),
-                            Style::default().fg(Theme::PINK),
-                        ),
-                        Span::styled(
-                            format!(" - {}", order.status),
-                            Style::default().fg(Theme::DIMMED),
-                        ),
-                    ])
-                })
-                .collect(),
-            false,
-        )
+        // Keep the last row for the "more" indicator when there's anything below
+        let start = app.order_scroll.min(app.orders.len() - 1);
+        let has_more = !app.orders_exhausted || start + height < app.orders.len();
+        let rows = if has_more { height.saturating_sub(1) } else { height };
+
+        let mut lines: Vec<Line<'static>> = app.orders[start..]
+            .iter()
+            .take(rows)
+            .map(|order| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("Order #{} - ", order.short_id()),
+                        Style::default().fg(app.theme.fg()),
+                    ),
+                    Span::styled(
+                        order.total_display(),
+                        Style::default().fg(app.theme.accent()),
+                    ),
+                    Span::styled(
+                        format!(" - {}", order.status),
+                        Style::default().fg(app.theme.dimmed()),
+                    ),
+                ])
+            })
+            .collect();
+
+        if has_more {
+            lines.push(Line::from(Span::styled(
+                "↓ more (J/pgdn)",
+                Style::default().fg(app.theme.dimmed()),
+            )));
+        }
+        (lines, false)
+    }
+}
+
+/// One order in full; shipped and delivered orders lead with their tracking number
+fn render_order_detail(theme: &Theme, order: &Order) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(format!("Order #{}", order.short_id()), Style::default().fg(theme.fg())),
+            Span::styled(format!(" - {}", order.status), Style::default().fg(theme.dimmed())),
+        ]),
+        Line::from(Span::styled(
+            order.created_at.format("%Y-%m-%d").to_string(),
+            Style::default().fg(theme.dimmed()),
+        )),
+        Line::default(),
+    ];
+
+    if let Some(tracking) = order.tracking_line() {
+        lines.push(Line::from(vec![
+            Span::styled(t("order.tracking"), Style::default().fg(theme.dimmed())),
+            Span::styled(tracking, Style::default().fg(Theme::GREEN)),
+        ]));
+        lines.push(Line::from(Span::styled(t("order.copy_tracking"), Style::default().fg(theme.dimmed()))));
+        lines.push(Line::default());
     }
+
+    for item in &order.items {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} × ", item.quantity), Style::default().fg(theme.dimmed())),
+            Span::styled(item.name.clone(), Style::default().fg(theme.fg())),
+            Span::styled(
+                format!("  {}", order.format_cents(item.total_cents())),
+                Style::default().fg(theme.dimmed()),
+            ),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(t("summary.total"), Style::default().fg(theme.dimmed())),
+        Span::styled(order.total_display(), Style::default().fg(theme.accent())),
+    ]));
+    lines.push(Line::default());
+    lines.push(Line::from(vec![
+        Span::styled(t("confirm.shipping_to"), Style::default().fg(theme.dimmed())),
+        Span::styled(order.shipping_address.name.clone(), Style::default().fg(theme.fg())),
+    ]));
+    lines.push(Line::from(Span::styled(
+        order.shipping_address.display_line(),
+        Style::default().fg(theme.dimmed()),
+    )));
+    lines
 }
 
 fn render_subscriptions(app: &App) -> (Vec<Line<'static>>, bool) {
     if app.subscriptions.is_empty() {
         (
             vec![Line::from(Span::styled(
-                "no active subscriptions",
-                Style::default().fg(Theme::DIMMED),
+                t("account.no_subscriptions"),
+                Style::default().fg(app.theme.dimmed()),
             ))],
             true,
         )
@@ -126,15 +193,22 @@ fn render_subscriptions(app: &App) -> (Vec<Line<'static>>, bool) {
         (
             app.subscriptions
                 .iter()
-                .map(|sub| {
+                .enumerate()
+                .map(|(i, sub)| {
+                    let name_style = if i == app.subscription_index {
+                        Style::default().fg(app.theme.fg()).bg(app.theme.accent())
+                    } else {
+                        Style::default().fg(app.theme.fg())
+                    };
                     Line::from(vec![
+                        Span::styled(sub.product_name.clone(), name_style),
                         Span::styled(
-                            sub.product_name.clone(),
-                            Style::default().fg(Theme::FG),
+                            format!(" - {}", sub.status),
+                            Style::default().fg(app.theme.dimmed()),
                         ),
                         Span::styled(
-                            format!(" - {}", sub.status),
-                            Style::default().fg(Theme::DIMMED),
+                            sub.next_delivery_at(Utc::now()).map(|next| format!(" · {}", next)).unwrap_or_default(),
+                            Style::default().fg(Theme::GREEN),
                         ),
                     ])
                 })
@@ -144,91 +218,111 @@ fn render_subscriptions(app: &App) -> (Vec<Line<'static>>, bool) {
     }
 }
 
-fn render_faq() -> Vec<Line<'static>> {
+fn render_faq(theme: &Theme) -> Vec<Line<'static>> {
     vec![
         Line::from(Span::styled(
             "help, i have a question about my order!",
-            Style::default().fg(Theme::FG),
+            Style::default().fg(theme.fg()),
         )),
         Line::from(Span::styled(
             "send us an email at support@anoralabs.com",
-            Style::default().fg(Theme::DIMMED),
+            Style::default().fg(theme.dimmed()),
         )),
         Line::default(),
         Line::from(Span::styled(
             "when was my coffee roasted? the roast date is blank on my bag.",
-            Style::default().fg(Theme::FG),
+            Style::default().fg(theme.fg()),
         )),
         Line::from(Span::styled(
             "we roast your coffee within 24 hours of shipping, and you'll receive an email once your order ships. we're working on a solution to stamping the roast date on the bags, but so far all attempts have failed (the ink wipes off).",
-            Style::default().fg(Theme::DIMMED),
+            Style::default().fg(theme.dimmed()),
         )),
         Line::default(),
         Line::from(Span::styled(
             "where do you ship?",
-            Style::default().fg(Theme::FG),
+            Style::default().fg(theme.fg()),
         )),
         Line::from(Span::styled(
             "we ship all of our blends in the UZ. Unfortunately, we don't ship to other countries at this time. We are actively looking for other small-batch roasters in other countries and regions though!",
-            Style::default().fg(Theme::DIMMED),
+            Style::default().fg(theme.dimmed()),
         )),
         Line::default(),
         Line::from(Span::styled(
             "is your coffee ethically sourced?",
-            Style::default().fg(Theme::FG),
+            Style::default().fg(theme.fg()),
         )),
         Line::from(Span::styled(
             "absolutely.",
-            Style::default().fg(Theme::DIMMED),
+            Style::default().fg(theme.dimmed()),
         )),
         Line::default(),
         Line::from(Span::styled(
             "is ordering via ssh secure?",
-            Style::default().fg(Theme::FG),
+            Style::default().fg(theme.fg()),
         )),
         Line::from(Span::styled(
             "yes! all payment information is securely processed.",
-            Style::default().fg(Theme::DIMMED),
+            Style::default().fg(theme.dimmed()),
         )),
     ]
 }
 
-fn render_about() -> Vec<Line<'static>> {
-    // Simple blinking cursor using time-based toggle
-    // Use unwrap_or_default() to gracefully handle system time errors
-    let cursor = if (std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() / 500) % 2 == 0 {
-        "█"
-    } else {
-        " "
-    };
+fn render_settings(app: &App) -> Vec<Line<'static>> {
+    let checkbox = |on: bool| if on { "[x] " } else { "[ ] " };
+    vec![
+        Line::from(vec![
+            Span::styled(checkbox(app.settings.confirm_before_order), Style::default().fg(app.theme.accent())),
+            Span::styled(t("settings.confirm_before_order"), Style::default().fg(app.theme.fg())),
+        ]),
+        Line::from(Span::styled(
+            t("settings.confirm_before_order_help"),
+            Style::default().fg(app.theme.dimmed()),
+        )),
+        Line::default(),
+        Line::from(vec![
+            Span::styled(checkbox(app.settings.celebrate_orders), Style::default().fg(app.theme.accent())),
+            Span::styled(t("settings.celebrate_orders"), Style::default().fg(app.theme.fg())),
+        ]),
+        Line::from(Span::styled(
+            t("settings.celebrate_orders_help"),
+            Style::default().fg(app.theme.dimmed()),
+        )),
+        Line::default(),
+        Line::from(vec![
+            Span::styled("w ", Style::default().fg(app.theme.fg())),
+            Span::styled(t("settings.reset_notices"), Style::default().fg(app.theme.dimmed())),
+        ]),
+    ]
+}
+
+fn render_about(app: &App) -> Vec<Line<'static>> {
+    // Simple blinking cursor driven by the app's animation clock
+    let cursor = if app.clock.blink_on() { Theme::CURSOR } else { " " };
 
     vec![
         Line::from(Span::styled(
             "Amazingly awesome products for developers brought to you by a group of talented, good looking, and humble heroes...",
-            Style::default().fg(Theme::DIMMED),
+            Style::default().fg(app.theme.dimmed()),
         )),
         Line::default(),
         Line::from(Span::styled(
             "1. @abdibrokhim",
-            Style::default().fg(Theme::FG),
+            Style::default().fg(app.theme.fg()),
         )),
         Line::default(),
         Line::from(Span::styled(
             "2. @asadbekmake",
-            Style::default().fg(Theme::FG),
+            Style::default().fg(app.theme.fg()),
         )),
         Line::default(),
         Line::from(vec![
             Span::styled(
                 "3. ANORA Labs, Inc.",
-                Style::default().fg(Theme::FG),
+                Style::default().fg(app.theme.fg()),
             ),
             Span::styled(
                 cursor,
-                Style::default().fg(Theme::PINK),
+                Style::default().fg(app.theme.accent()),
             ),
         ]),
     ]
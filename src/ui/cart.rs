@@ -8,11 +8,19 @@ use ratatui::{
 
 use super::Theme;
 use crate::app::{App, CheckoutStep, InputField, PaymentMethod, ShippingMode};
+use crate::i18n::t;
 
 pub fn render_cart(f: &mut Frame, area: Rect, app: &App) {
+    // Never render a later step for an empty cart (the main loop resets it on the next tick)
+    if app.cart.is_empty() {
+        render_cart_items(f, area, app);
+        return;
+    }
+
     match app.checkout_step {
         CheckoutStep::Cart => render_cart_items(f, area, app),
         CheckoutStep::Shipping => render_shipping(f, area, app),
+        CheckoutStep::Gift => render_gift(f, area, app),
         CheckoutStep::Payment => render_payment(f, area, app),
         CheckoutStep::Confirmation => render_confirmation(f, area, app),
     }
@@ -28,14 +36,32 @@ fn render_cart_items(f: &mut Frame, area: Rect, app: &App) {
         .split(area);
 
         let empty = Paragraph::new(Line::from(Span::styled(
-            "Your cart is empty.",
-            Style::default().fg(Theme::DIMMED),
+            t("cart.empty"),
+            Style::default().fg(app.theme.dimmed()),
         )))
         .centered();
         f.render_widget(empty, chunks[1]);
         return;
     }
 
+    // Running totals pinned to the bottom row, with the free-shipping gap just above
+    let gap_cents = app.amount_to_free_shipping();
+    let cart_chunks = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(if gap_cents > 0 { 1 } else { 0 }),
+        Constraint::Length(1),
+    ])
+    .split(area);
+    if gap_cents > 0 {
+        let hint = t("cart.free_shipping_gap").replace("{amount}", &app.region.format_cents(gap_cents));
+        f.render_widget(
+            Paragraph::new(Span::styled(hint, Style::default().fg(app.theme.dimmed()))).right_aligned(),
+            cart_chunks[1],
+        );
+    }
+    render_cart_totals(f, cart_chunks[2], app);
+    let area = cart_chunks[0];
+
     // Each item: 4 lines height (reduced from 6)
     let item_height = 4u16;
     let gap_height = 0u16;
@@ -53,13 +79,16 @@ fn render_cart_items(f: &mut Frame, area: Rect, app: &App) {
 
     for (i, item) in app.cart.items.iter().enumerate() {
         let is_selected = i == app.cart_item_index;
+        let confirming_removal = app.confirm_remove == Some(item.product.id);
         let chunk_index = i * 2;
         let item_area = chunks[chunk_index];
 
-        let border_style = if is_selected {
-            Style::default().fg(Theme::FG)
+        let border_style = if confirming_removal {
+            Style::default().fg(Theme::RED)
+        } else if is_selected {
+            Style::default().fg(app.theme.fg())
         } else {
-            Style::default().fg(Theme::BORDER)
+            Style::default().fg(app.theme.border())
         };
 
         let block = Block::default()
@@ -88,38 +117,105 @@ fn render_cart_items(f: &mut Frame, area: Rect, app: &App) {
         ])
         .split(content_chunks[1]);
 
-        let name_para = Paragraph::new(Line::from(Span::styled(
+        let mut name_spans = vec![Span::styled(
             item.product.name.clone(),
-            Style::default().fg(Theme::FG),
-        )));
+            Style::default().fg(app.theme.fg()),
+        )];
+        if item.unavailable {
+            name_spans.push(Span::styled(t("cart.unavailable"), Style::default().fg(Theme::RED)));
+        } else if item.price_changed {
+            name_spans.push(Span::styled(t("cart.price_updated"), Style::default().fg(Theme::YELLOW)));
+        }
+        if item.has_tier_discount() {
+            name_spans.push(Span::styled(
+                format!(" {} each", app.region.format_cents(item.product.unit_price_cents(item.quantity))),
+                Style::default().fg(Theme::GREEN),
+            ));
+        }
+        let name_para = Paragraph::new(Line::from(name_spans));
         f.render_widget(name_para, name_chunks[0]);
 
-        let details_para = Paragraph::new(Line::from(Span::styled(
-            item.product.details_line(),
-            Style::default().fg(Theme::DIMMED),
-        )));
+        let details_para = Paragraph::new(Line::from(if confirming_removal {
+            Span::styled(t("cart.confirm_remove"), Style::default().fg(Theme::RED))
+        } else {
+            Span::styled(item.product.details_line(), Style::default().fg(app.theme.dimmed()))
+        }));
         f.render_widget(details_para, details_chunks[0]);
 
+        // Subscribe-and-save upsell (skippable, toggled with S)
+        if item.product.offers_subscription() {
+            let discount = item.product.subscription_discount_pct;
+            let upsell = if item.subscribe {
+                Line::from(Span::styled(
+                    t("cart.subscribed").replace("{n}", &discount.to_string()),
+                    Style::default().fg(Theme::GREEN),
+                ))
+            } else if is_selected {
+                Line::from(vec![
+                    Span::styled("S ", Style::default().fg(app.theme.fg())),
+                    Span::styled(t("cart.subscribe").replace("{n}", &discount.to_string()), Style::default().fg(app.theme.dimmed())),
+                ])
+            } else {
+                Line::default()
+            };
+            f.render_widget(Paragraph::new(upsell).right_aligned(), details_chunks[1]);
+        }
+
         let qty_price = if is_selected {
             Line::from(vec![
-                Span::styled(" - ", Style::default().fg(Theme::DIMMED)),
-                Span::styled(format!(" {} ", item.quantity), Style::default().fg(Theme::FG)),
-                Span::styled(" + ", Style::default().fg(Theme::DIMMED)),
+                Span::styled(" - ", Style::default().fg(app.theme.dimmed())),
+                Span::styled(format!(" {} ", item.quantity), Style::default().fg(app.theme.fg())),
+                Span::styled(" + ", Style::default().fg(app.theme.dimmed())),
                 Span::styled(
-                    format!("   {}", item.total_display()),
-                    Style::default().fg(Theme::DIMMED),
+                    format!("   {}", item.total_display(&app.region)),
+                    Style::default().fg(app.theme.dimmed()),
                 ),
             ])
         } else {
             Line::from(vec![
-                Span::styled(format!("{}      ", item.quantity), Style::default().fg(Theme::FG)),
-                Span::styled(item.total_display(), Style::default().fg(Theme::DIMMED)),
+                Span::styled(format!("{}      ", item.quantity), Style::default().fg(app.theme.fg())),
+                Span::styled(item.total_display(&app.region), Style::default().fg(app.theme.dimmed())),
             ])
         };
 
         let right_para = Paragraph::new(qty_price).right_aligned();
         f.render_widget(right_para, name_chunks[1]);
     }
+
+    // Free-shipping nudge below the items
+    if let Some(product) = app.free_shipping_suggestion() {
+        let nudge_area = chunks[chunks.len() - 1];
+        let nudge = Paragraph::new(vec![
+            Line::default(),
+            Line::from(vec![
+                Span::styled(
+                    t("cart.free_shipping_suggestion")
+                        .replace("{name}", &product.name)
+                        .replace("{price}", &product.price_display(&app.region)),
+                    Style::default().fg(app.theme.dimmed()),
+                ),
+                Span::styled("f", Style::default().fg(app.theme.fg())),
+            ]),
+        ])
+        .centered();
+        f.render_widget(nudge, nudge_area);
+    }
+}
+
+/// "3 items · subtotal $66.00 · shipping $8.00" while adjusting the cart
+fn render_cart_totals(f: &mut Frame, area: Rect, app: &App) {
+    let shipping_cents = app.cart.shipping_cents(&app.region);
+    let count = app.cart.total_items();
+    let items = if count == 1 { t("cart.item") } else { t("cart.items") };
+    let totals = Line::from(vec![
+        Span::styled(format!("{} ", count), Style::default().fg(app.theme.fg())),
+        Span::styled(format!("{} · ", items), Style::default().fg(app.theme.dimmed())),
+        Span::styled(t("summary.subtotal"), Style::default().fg(app.theme.dimmed())),
+        Span::styled(app.cart.subtotal_display(&app.region), Style::default().fg(app.theme.fg())),
+        Span::styled(format!(" · {}", t("summary.shipping")), Style::default().fg(app.theme.dimmed())),
+        shipping_cost_span(app, shipping_cents),
+    ]);
+    f.render_widget(Paragraph::new(totals).right_aligned(), area);
 }
 
 fn render_shipping(f: &mut Frame, area: Rect, app: &App) {
@@ -138,8 +234,8 @@ fn render_address_selection(f: &mut Frame, area: Rect, app: &App) {
         height: 2,
     };
     let title = Paragraph::new(Line::from(Span::styled(
-        "select shipping address",
-        Style::default().fg(Theme::DIMMED),
+        t("shipping.select"),
+        Style::default().fg(app.theme.dimmed()),
     )));
     f.render_widget(title, title_area);
 
@@ -166,9 +262,9 @@ fn render_address_selection(f: &mut Frame, area: Rect, app: &App) {
     for (i, address) in app.saved_addresses.iter().enumerate() {
         let is_selected = i == app.address_select_index;
         let border_style = if is_selected {
-            Style::default().fg(Theme::FG)
+            Style::default().fg(app.theme.fg())
         } else {
-            Style::default().fg(Theme::BORDER)
+            Style::default().fg(app.theme.border())
         };
 
         let block = Block::default()
@@ -181,10 +277,10 @@ fn render_address_selection(f: &mut Frame, area: Rect, app: &App) {
 
         // Address content
         let content = Line::from(vec![
-            Span::styled("◉ ", Style::default().fg(if is_selected { Theme::FG } else { Theme::DIMMED })),
-            Span::styled(address.display_line(), Style::default().fg(Theme::FG)),
+            Span::styled(format!("{} ", Theme::RADIO), Style::default().fg(if is_selected { app.theme.fg() } else { app.theme.dimmed() })),
+            Span::styled(address.display_line(), Style::default().fg(app.theme.fg())),
             if is_selected {
-                Span::styled("                    enter", Style::default().fg(Theme::DIMMED))
+                Span::styled("                    enter", Style::default().fg(app.theme.dimmed()))
             } else {
                 Span::styled("", Style::default())
             },
@@ -197,9 +293,9 @@ fn render_address_selection(f: &mut Frame, area: Rect, app: &App) {
     let add_new_index = app.saved_addresses.len();
     let is_add_selected = app.address_select_index == add_new_index;
     let add_border_style = if is_add_selected {
-        Style::default().fg(Theme::FG)
+        Style::default().fg(app.theme.fg())
     } else {
-        Style::default().fg(Theme::BORDER)
+        Style::default().fg(app.theme.border())
     };
 
     let add_block = Block::default()
@@ -211,7 +307,7 @@ fn render_address_selection(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(add_block, chunks[add_new_index]);
 
     let add_content = Line::from(vec![
-        Span::styled("add new address", Style::default().fg(Theme::DIMMED)),
+        Span::styled(t("shipping.add_new"), Style::default().fg(app.theme.dimmed())),
     ]);
     let add_para = Paragraph::new(add_content);
     f.render_widget(add_para, add_inner);
@@ -225,36 +321,43 @@ fn render_address_form(f: &mut Frame, area: Rect, app: &App) {
     ])
     .split(area);
 
-    // Left column fields: name, street 1, city
+    // Left column fields: name, street 1, street 2, city
     let left_fields = [
-        (InputField::Name, "name", &app.shipping_address.name),
-        (InputField::Street1, "street", &app.shipping_address.street_1),
-        (InputField::City, "city", &app.shipping_address.city),
+        (InputField::Name, t("field.name"), &app.shipping_address.name),
+        (InputField::Street1, t("field.street"), &app.shipping_address.street_1),
+        (InputField::Street2, t("field.street_2"), &app.shipping_address.street_2),
+        (InputField::City, t("field.city"), &app.shipping_address.city),
     ];
 
     let left_lines: Vec<Line> = left_fields
         .iter()
         .flat_map(|(field, label, value)| {
             let is_active = app.active_input == *field;
-            render_form_field(label, value, is_active)
+            let jump_key = app.field_jump.then(|| field.jump_number()).flatten();
+            let counter = is_active.then(|| field.length_counter(value.chars().count())).flatten();
+            render_form_field(&app.theme, label, value, is_active, app.input_cursor(), jump_key, counter)
         })
         .collect();
 
     let left_para = Paragraph::new(left_lines);
     f.render_widget(left_para, form_chunks[0]);
 
-    // Right column fields: country, phone, postal code
+    // Right column fields: state, country, phone, postal code
+    let phone = mask_sensitive(&app.shipping_address.phone, app.privacy_mode);
     let right_fields = [
-        (InputField::Country, "country", &app.shipping_address.country),
-        (InputField::Phone, "phone", &app.shipping_address.phone),
-        (InputField::PostalCode, "postal code", &app.shipping_address.postal_code),
+        (InputField::State, t("field.state"), &app.shipping_address.state),
+        (InputField::Country, t("field.country"), &app.shipping_address.country),
+        (InputField::Phone, t("field.phone"), &phone),
+        (InputField::PostalCode, t("field.postal_code"), &app.shipping_address.postal_code),
     ];
 
     let right_lines: Vec<Line> = right_fields
         .iter()
         .flat_map(|(field, label, value)| {
             let is_active = app.active_input == *field;
-            render_form_field(label, value, is_active)
+            let jump_key = app.field_jump.then(|| field.jump_number()).flatten();
+            let counter = is_active.then(|| field.length_counter(value.chars().count())).flatten();
+            render_form_field(&app.theme, label, value, is_active, app.input_cursor(), jump_key, counter)
         })
         .collect();
 
@@ -262,6 +365,29 @@ fn render_address_form(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(right_para, form_chunks[1]);
 }
 
+/// Gift orders only: the optional message, whichever way the order is paid
+fn render_gift(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Fill(1),
+    ])
+    .split(area);
+
+    let title = Paragraph::new(Line::from(Span::styled(
+        t("gift.title"),
+        Style::default().fg(app.theme.dimmed()),
+    )));
+    f.render_widget(title, chunks[0]);
+
+    // One char per line break, so the caret position still lines up
+    let message = app.gift_message.replace('\n', "↵");
+    let field = InputField::GiftMessage;
+    let is_active = app.active_input == field;
+    let counter = is_active.then(|| field.length_counter(message.chars().count())).flatten();
+    let lines = render_form_field(&app.theme, t("field.gift_message"), &message, is_active, app.input_cursor(), None, counter);
+    f.render_widget(Paragraph::new(lines), chunks[1]);
+}
+
 fn render_payment(f: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::vertical([
         Constraint::Length(3),
@@ -271,22 +397,18 @@ fn render_payment(f: &mut Frame, area: Rect, app: &App) {
     .split(area);
 
     // Order summary
-    let shipping_cents = if app.cart.subtotal_cents() >= app.region.free_shipping_threshold * 100 {
-        0
-    } else {
-        800
-    };
+    let shipping_cents = app.cart.shipping_cents(&app.region);
     let total = app.cart.subtotal_cents() + shipping_cents;
 
     let summary = Paragraph::new(vec![
         Line::default(),
         Line::from(vec![
-            Span::styled("subtotal: ", Style::default().fg(Theme::DIMMED)),
-            Span::styled(format!("${:.2}", app.cart.subtotal_cents() as f64 / 100.0), Style::default().fg(Theme::FG)),
-            Span::styled(",  shipping: ", Style::default().fg(Theme::DIMMED)),
-            Span::styled(format!("${:.2}", shipping_cents as f64 / 100.0), Style::default().fg(Theme::FG)),
-            Span::styled(",  total: ", Style::default().fg(Theme::DIMMED)),
-            Span::styled(format!("${:.2}", total as f64 / 100.0), Style::default().fg(Theme::PINK)),
+            Span::styled(t("summary.subtotal"), Style::default().fg(app.theme.dimmed())),
+            Span::styled(app.region.format_cents(app.cart.subtotal_cents()), Style::default().fg(app.theme.fg())),
+            Span::styled(format!(",  {}", t("summary.shipping")), Style::default().fg(app.theme.dimmed())),
+            shipping_cost_span(app, shipping_cents),
+            Span::styled(format!(",  {}", t("summary.total")), Style::default().fg(app.theme.dimmed())),
+            Span::styled(app.region.format_cents(total), Style::default().fg(app.theme.accent())),
         ]),
     ]);
     f.render_widget(summary, chunks[0]);
@@ -294,8 +416,8 @@ fn render_payment(f: &mut Frame, area: Rect, app: &App) {
     // Title
     let title = Paragraph::new(vec![
         Line::from(Span::styled(
-            "select payment method",
-            Style::default().fg(Theme::DIMMED),
+            t("payment.select"),
+            Style::default().fg(app.theme.dimmed()),
         )),
     ]);
     f.render_widget(title, chunks[1]);
@@ -309,8 +431,8 @@ fn render_payment(f: &mut Frame, area: Rect, app: &App) {
 
 fn render_payment_selection(f: &mut Frame, area: Rect, app: &App) {
     let options = [
-        ("add payment information via ssh", PaymentMethod::Ssh),
-        ("add payment information via browser", PaymentMethod::Browser),
+        (t("payment.via_ssh"), PaymentMethod::Ssh),
+        (t("payment.via_browser"), PaymentMethod::Browser),
     ];
 
     let chunks = Layout::vertical([
@@ -324,15 +446,15 @@ fn render_payment_selection(f: &mut Frame, area: Rect, app: &App) {
     for (i, (label, _method)) in options.iter().enumerate() {
         let is_selected = i == app.payment_option_index;
         let border_style = if is_selected {
-            Style::default().fg(Theme::FG)
+            Style::default().fg(app.theme.fg())
         } else {
-            Style::default().fg(Theme::DIMMED)
+            Style::default().fg(app.theme.dimmed())
         };
 
         let content = Line::from(vec![
-            Span::styled("◉ ", Style::default().fg(if is_selected { Theme::FG } else { Theme::DIMMED })),
-            Span::styled(*label, Style::default().fg(Theme::FG)),
-            Span::styled("                            enter", Style::default().fg(Theme::DIMMED)),
+            Span::styled(format!("{} ", Theme::RADIO), Style::default().fg(if is_selected { app.theme.fg() } else { app.theme.dimmed() })),
+            Span::styled(*label, Style::default().fg(app.theme.fg())),
+            Span::styled("                            enter", Style::default().fg(app.theme.dimmed())),
         ]);
 
         let block = Block::default()
@@ -355,35 +477,42 @@ fn render_ssh_payment(f: &mut Frame, area: Rect, app: &App) {
     .split(area);
 
     // Left column: name, email, card number
+    let email = mask_sensitive(&app.payment_info.email, app.privacy_mode);
+    let card_number = mask_sensitive(&app.payment_info.card_number, app.privacy_mode);
     let left_fields = [
-        (InputField::PaymentName, "name", &app.payment_info.name),
-        (InputField::PaymentEmail, "email", &app.payment_info.email),
-        (InputField::CardNumber, "card number", &app.payment_info.card_number),
+        (InputField::PaymentName, t("field.name"), &app.payment_info.name),
+        (InputField::PaymentEmail, t("field.email"), &email),
+        (InputField::CardNumber, t("field.card_number"), &card_number),
     ];
 
     let left_lines: Vec<Line> = left_fields
         .iter()
         .flat_map(|(field, label, value)| {
             let is_active = app.active_input == *field;
-            render_form_field(label, value, is_active)
+            let jump_key = app.field_jump.then(|| field.jump_number()).flatten();
+            let counter = is_active.then(|| field.length_counter(value.chars().count())).flatten();
+            render_form_field(&app.theme, label, value, is_active, app.input_cursor(), jump_key, counter)
         })
         .collect();
 
     let left_para = Paragraph::new(left_lines);
     f.render_widget(left_para, form_chunks[0]);
 
-    // Right column: expiry month, expiry year, cvv
+    // Right column: expiry (MM/YY), cvv
+    let cvv = mask_sensitive(&app.payment_info.cvv, app.privacy_mode);
+    let expiry = app.payment_info.expiry_display();
     let right_fields = [
-        (InputField::ExpiryMonth, "expiry month", &app.payment_info.expiry_month),
-        (InputField::ExpiryYear, "expiry year", &app.payment_info.expiry_year),
-        (InputField::Cvv, "cvv (3 digits)", &app.payment_info.cvv),
+        (InputField::Expiry, t("field.expiry"), &expiry),
+        (InputField::Cvv, t("field.cvv"), &cvv),
     ];
 
     let right_lines: Vec<Line> = right_fields
         .iter()
         .flat_map(|(field, label, value)| {
             let is_active = app.active_input == *field;
-            render_form_field(label, value, is_active)
+            let jump_key = app.field_jump.then(|| field.jump_number()).flatten();
+            let counter = is_active.then(|| field.length_counter(value.chars().count())).flatten();
+            render_form_field(&app.theme, label, value, is_active, app.input_cursor(), jump_key, counter)
         })
         .collect();
 
@@ -391,7 +520,7 @@ fn render_ssh_payment(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(right_para, form_chunks[1]);
 }
 
-fn render_browser_payment(f: &mut Frame, area: Rect, _app: &App) {
+fn render_browser_payment(f: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::vertical([
         Constraint::Length(2),
         Constraint::Length(9),
@@ -417,123 +546,295 @@ fn render_browser_payment(f: &mut Frame, area: Rect, _app: &App) {
     f.render_widget(qr, chunks[1]);
 
     let instruction = Paragraph::new(Line::from(Span::styled(
-        "scan or copy to enter payment information",
-        Style::default().fg(Theme::DIMMED),
+        t("payment.scan"),
+        Style::default().fg(app.theme.dimmed()),
     )))
     .centered();
     f.render_widget(instruction, chunks[3]);
 
     let url = Paragraph::new(Line::from(Span::styled(
         "https://pay.anoralabs.com/checkout/abc123",
-        Style::default().fg(Theme::PINK),
+        Style::default().fg(app.theme.accent()),
     )))
     .centered();
     f.render_widget(url, chunks[4]);
 }
 
+/// "order #1a2b3c4d placed — thank you", shown after checkout until the next key
+pub fn render_order_placed(f: &mut Frame, area: Rect, app: &App) {
+    let Some(order) = &app.placed_order else {
+        return;
+    };
+    let chunks = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(3),
+        Constraint::Fill(1),
+    ])
+    .split(area);
+
+    let lines = vec![
+        Line::from(Span::styled(
+            t("order.placed").replace("{id}", &order.short_id()),
+            Style::default().fg(Theme::GREEN),
+        )),
+        Line::default(),
+        Line::from(Span::styled(t("order.placed_continue"), Style::default().fg(app.theme.dimmed()))),
+    ];
+    f.render_widget(Paragraph::new(lines).centered(), chunks[1]);
+}
+
 fn render_confirmation(f: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::vertical([
         Constraint::Fill(1),
-        Constraint::Length(12),
+        Constraint::Length(14),
         Constraint::Fill(1),
     ])
     .split(area);
 
-    let shipping_cents = if app.cart.subtotal_cents() >= app.region.free_shipping_threshold * 100 {
-        0
+    let shipping_cents = app.cart.shipping_cents(&app.region);
+    let total = app.cart.subtotal_cents() + shipping_cents;
+
+    // Gift orders name the purchaser separately from the recipient
+    let (billing_line, gift_message_line) = if app.gift_order {
+        let purchaser = match app.pending_order.as_ref().and_then(|o| o.gift.as_ref()?.purchaser_name.clone()) {
+            Some(name) => name,
+            None => t("confirm.billed_at_browser").to_string(),
+        };
+        let message = if app.gift_message.trim().is_empty() {
+            Line::default()
+        } else {
+            Line::from(Span::styled(
+                format!("“{}”", app.gift_message.trim().lines().map(str::trim).collect::<Vec<_>>().join(" / ")),
+                Style::default().fg(app.theme.dimmed()),
+            ))
+        };
+        (
+            Line::from(vec![
+                Span::styled(t("confirm.billed_to"), Style::default().fg(app.theme.dimmed())),
+                Span::styled(purchaser, Style::default().fg(app.theme.fg())),
+            ]),
+            message,
+        )
     } else {
-        800
+        (Line::default(), Line::default())
     };
-    let total = app.cart.subtotal_cents() + shipping_cents;
 
     let lines = vec![
         Line::from(Span::styled(
-            "order confirmation",
-            Style::default().fg(Theme::DIMMED),
+            t("confirm.title"),
+            Style::default().fg(app.theme.dimmed()),
         )),
         Line::default(),
         Line::default(),
         Line::from(vec![
-            Span::styled("shipping to: ", Style::default().fg(Theme::DIMMED)),
-            Span::styled(app.shipping_address.name.clone(), Style::default().fg(Theme::FG)),
+            Span::styled(t("confirm.shipping_to"), Style::default().fg(app.theme.dimmed())),
+            Span::styled(app.shipping_address.name.clone(), Style::default().fg(app.theme.fg())),
         ]),
         Line::from(Span::styled(
             format!("{}, {}", app.shipping_address.street_1, app.shipping_address.city),
-            Style::default().fg(Theme::DIMMED),
+            Style::default().fg(app.theme.dimmed()),
         )),
-        Line::default(),
-        Line::default(),
+        billing_line,
+        gift_message_line,
+        Line::from(vec![
+            Span::styled(t("summary.items"), Style::default().fg(app.theme.dimmed())),
+            Span::styled(format!("{}", app.cart.total_items()), Style::default().fg(app.theme.fg())),
+        ]),
         Line::from(vec![
-            Span::styled("items: ", Style::default().fg(Theme::DIMMED)),
-            Span::styled(format!("{}", app.cart.total_items()), Style::default().fg(Theme::FG)),
+            Span::styled(t("summary.shipping"), Style::default().fg(app.theme.dimmed())),
+            shipping_cost_span(app, shipping_cents),
         ]),
         Line::from(vec![
-            Span::styled("total: ", Style::default().fg(Theme::DIMMED)),
-            Span::styled(format!("${:.2}", total as f64 / 100.0), Style::default().fg(Theme::PINK)),
+            Span::styled(t("summary.total"), Style::default().fg(app.theme.dimmed())),
+            Span::styled(app.region.format_cents(total), Style::default().fg(app.theme.accent())),
         ]),
+        subscription_summary_line(app),
         Line::default(),
         Line::default(),
-        Line::from(Span::styled(
-            "press enter to confirm your order",
-            Style::default().fg(Theme::GREEN),
-        )),
+        if app.order_confirm_armed {
+            Line::from(Span::styled(t("confirm.press_again"), Style::default().fg(Theme::YELLOW)))
+        } else {
+            Line::from(Span::styled(t("confirm.press_enter"), Style::default().fg(Theme::GREEN)))
+        },
     ];
 
     let para = Paragraph::new(lines).centered();
     f.render_widget(para, chunks[1]);
 }
+
+/// Shipping cost for the summaries: "free" when the region's threshold waived it, else the amount
+fn shipping_cost_span(app: &App, shipping_cents: i32) -> Span<'static> {
+    let waived = shipping_cents == 0
+        && !app.cart.is_empty()
+        && app.cart.subtotal_cents() >= app.region.free_shipping_threshold_cents();
+    if waived {
+        Span::styled(t("summary.free"), Style::default().fg(Theme::GREEN))
+    } else {
+        Span::styled(app.region.format_cents(shipping_cents), Style::default().fg(app.theme.fg()))
+    }
+}
+
+/// Render the confirmation email preview overlay centered over the given area
+pub fn render_email_preview(f: &mut Frame, area: Rect, app: &App) {
+    let Some(preview) = app.email_preview() else {
+        return;
+    };
+    let mut lines: Vec<Line<'static>> = preview
+        .lines()
+        .map(|l| Line::from(Span::styled(l.to_string(), Style::default().fg(app.theme.fg()))))
+        .collect();
+    lines.push(Line::from(Span::styled(
+        t("preview.help"),
+        Style::default().fg(app.theme.dimmed()),
+    )));
+    super::render_overlay(f, super::popup_area(area, 64, 24), lines, app.email_preview_scroll, &app.theme);
+}
+
+/// Render the read-only cart peek overlay centered over the given area
+pub fn render_cart_peek(f: &mut Frame, area: Rect, app: &App) {
+    let mut lines = vec![
+        Line::from(Span::styled(t("peek.title"), Style::default().fg(app.theme.fg()))),
+        Line::default(),
+    ];
+
+    if app.cart.is_empty() {
+        lines.push(Line::from(Span::styled(t("peek.empty"), Style::default().fg(app.theme.dimmed()))));
+    } else {
+        for item in &app.cart.items {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} × ", item.quantity), Style::default().fg(app.theme.dimmed())),
+                Span::styled(item.product.name.clone(), Style::default().fg(app.theme.fg())),
+                Span::styled(format!("  {}", item.total_display(&app.region)), Style::default().fg(app.theme.dimmed())),
+            ]));
+        }
+
+        let shipping_cents = app.cart.shipping_cents(&app.region);
+        let total = app.cart.subtotal_cents() + shipping_cents;
+        lines.push(Line::default());
+        lines.push(Line::from(vec![
+            Span::styled(t("summary.subtotal"), Style::default().fg(app.theme.dimmed())),
+            Span::styled(app.cart.subtotal_display(&app.region), Style::default().fg(app.theme.fg())),
+            Span::styled(format!(" · {}", t("summary.shipping")), Style::default().fg(app.theme.dimmed())),
+            shipping_cost_span(app, shipping_cents),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled(t("summary.total"), Style::default().fg(app.theme.dimmed())),
+            Span::styled(app.region.format_cents(total), Style::default().fg(app.theme.accent())),
+        ]));
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled(t("peek.close"), Style::default().fg(app.theme.dimmed()))));
+
+    let height = lines.len() as u16 + 2;
+    super::render_overlay(f, super::popup_area(area, 48, height), lines, 0, &app.theme);
+}
+
+/// "Quit with items in the cart?" prompt over the body
+pub fn render_quit_confirm(f: &mut Frame, area: Rect, app: &App) {
+    let message = t("quit.confirm").replace("{n}", &app.cart.total_items().to_string());
+    let lines = vec![Line::from(Span::styled(message, Style::default().fg(app.theme.fg())))];
+    super::render_overlay(f, super::popup_area(area, 44, 5), lines, 0, &app.theme);
+}
+
+/// Replace every character with a dot while privacy mode is on
+fn mask_sensitive(value: &str, privacy_mode: bool) -> String {
+    if privacy_mode {
+        "•".repeat(value.chars().count())
+    } else {
+        value.to_string()
+    }
+}
+
+/// Summary of lines upsold to a subscription, or an empty line if none
+fn subscription_summary_line(app: &App) -> Line<'static> {
+    let count = app.cart.subscribed_items().count();
+    if count == 0 {
+        return Line::default();
+    }
+    Line::from(vec![
+        Span::styled(t("summary.subscriptions"), Style::default().fg(app.theme.dimmed())),
+        Span::styled(format!("{}", count), Style::default().fg(app.theme.fg())),
+        Span::styled(
+            t("summary.saving").replace("{amount}", &app.region.format_cents(app.cart.savings_cents())),
+            Style::default().fg(Theme::GREEN),
+        ),
+    ])
+}
+
 /// Render a form field with label and value in the terminal.shop style
 /// Format:
-///   label
+///   label          ([n] label while in field-jump mode, "42/64" when near the length cap)
 /// > value (or cursor if active and empty)
-fn render_form_field<'a>(label: &'a str, value: &'a str, is_active: bool) -> Vec<Line<'a>> {
-    let label_style = Style::default().fg(Theme::DIMMED);
+fn render_form_field<'a>(
+    theme: &Theme,
+    label: &'a str,
+    value: &'a str,
+    is_active: bool,
+    cursor: usize,
+    jump_key: Option<usize>,
+    counter: Option<String>,
+) -> Vec<Line<'a>> {
+    let label_style = Style::default().fg(theme.dimmed());
     
     // Build the value line with ">" prefix
     let value_line = if is_active {
         if value.is_empty() {
             // Show pink cursor block when active and empty
             Line::from(vec![
-                Span::styled("│ ", Style::default().fg(Theme::FG)),
-                Span::styled("> ", Style::default().fg(Theme::FG)),
-                Span::styled("█", Style::default().fg(Theme::PINK)),
+                Span::styled("│ ", Style::default().fg(theme.fg())),
+                Span::styled("> ", Style::default().fg(theme.fg())),
+                Span::styled(Theme::CURSOR, Style::default().fg(theme.accent())),
             ])
         } else {
-            // Show value with cursor at end
-            Line::from(vec![
-                Span::styled("│ ", Style::default().fg(Theme::FG)),
-                Span::styled("> ", Style::default().fg(Theme::FG)),
-                Span::styled(value, Style::default().fg(Theme::FG)),
-                Span::styled("█", Style::default().fg(Theme::PINK)),
-            ])
+            let mut spans = vec![
+                Span::styled("│ ", Style::default().fg(theme.fg())),
+                Span::styled("> ", Style::default().fg(theme.fg())),
+            ];
+            match value.char_indices().nth(cursor) {
+                // Caret mid-value: highlight the character it sits on
+                Some((at, c)) => {
+                    let next = at + c.len_utf8();
+                    spans.push(Span::styled(&value[..at], Style::default().fg(theme.fg())));
+                    spans.push(Span::styled(&value[at..next], Style::default().fg(theme.fg()).bg(theme.accent())));
+                    spans.push(Span::styled(&value[next..], Style::default().fg(theme.fg())));
+                }
+                // Show value with cursor at end
+                None => {
+                    spans.push(Span::styled(value, Style::default().fg(theme.fg())));
+                    spans.push(Span::styled(Theme::CURSOR, Style::default().fg(theme.accent())));
+                }
+            }
+            Line::from(spans)
         }
     } else {
         if value.is_empty() {
             Line::from(vec![
                 Span::styled("  ", Style::default()),
-                Span::styled(">", Style::default().fg(Theme::DIMMED)),
+                Span::styled(">", Style::default().fg(theme.dimmed())),
             ])
         } else {
             Line::from(vec![
                 Span::styled("  ", Style::default()),
-                Span::styled("> ", Style::default().fg(Theme::DIMMED)),
-                Span::styled(value, Style::default().fg(Theme::FG)),
+                Span::styled("> ", Style::default().fg(theme.dimmed())),
+                Span::styled(value, Style::default().fg(theme.fg())),
             ])
         }
     };
 
     // Add left border indicator for active field
-    let label_line = if is_active {
-        Line::from(vec![
-            Span::styled("│ ", Style::default().fg(Theme::FG)),
-            Span::styled(label, label_style),
-        ])
+    let mut label_line = if is_active {
+        Line::from(vec![Span::styled("│ ", Style::default().fg(theme.fg()))])
     } else {
-        Line::from(vec![
-            Span::styled("  ", Style::default()),
-            Span::styled(label, label_style),
-        ])
+        Line::from(vec![Span::styled("  ", Style::default())])
     };
+    if let Some(n) = jump_key {
+        label_line.push_span(Span::styled(format!("[{}] ", n), Style::default().fg(theme.accent())));
+    }
+    label_line.push_span(Span::styled(label, label_style));
+    if let Some(counter) = counter {
+        label_line.push_span(Span::styled(format!("  {}", counter), Style::default().fg(Theme::YELLOW)));
+    }
 
     vec![
         label_line,
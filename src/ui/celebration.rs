@@ -0,0 +1,41 @@
+use ratatui::{layout::Rect, style::Style, Frame};
+
+use super::Theme;
+use crate::app::{App, CELEBRATION_DURATION};
+
+const CONFETTI: [&str; 4] = ["*", "+", "·", "✦"];
+/// Confetti pieces per 100 cells at the start; thins out to none by the end
+const START_DENSITY: u32 = 12;
+/// Frames advance every 100ms so the pieces twinkle
+const FRAME_MS: u128 = 100;
+
+/// Post-order confetti flash across `area`, drawn over whatever is there without clearing it
+pub fn render_celebration(f: &mut Frame, area: Rect, app: &App) {
+    let Some(elapsed) = app.celebration_elapsed() else {
+        return;
+    };
+    let remaining = 1.0 - elapsed.as_secs_f64() / CELEBRATION_DURATION.as_secs_f64();
+    let density = (START_DENSITY as f64 * remaining).ceil() as u32;
+    let frame = app.clock.tick(FRAME_MS) as u32;
+
+    let buffer = f.buffer_mut();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let roll = scatter(x, y, frame);
+            if roll % 100 >= density {
+                continue;
+            }
+            let piece = CONFETTI[(roll / 100) as usize % CONFETTI.len()];
+            let color = Theme::PRODUCT_PALETTE[(roll / 1000) as usize % Theme::PRODUCT_PALETTE.len()];
+            buffer[(x, y)].set_symbol(piece).set_style(Style::default().fg(color));
+        }
+    }
+}
+
+/// Cheap deterministic hash of a cell and frame (FNV-1a)
+fn scatter(x: u16, y: u16, frame: u32) -> u32 {
+    [x as u32, y as u32, frame]
+        .iter()
+        .flat_map(|n| n.to_le_bytes())
+        .fold(0x811c9dc5u32, |h, b| (h ^ b as u32).wrapping_mul(0x01000193))
+}
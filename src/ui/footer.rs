@@ -2,14 +2,28 @@ use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::Style,
     text::{Line, Span},
-    widgets::Paragraph,
+    widgets::{Clear, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
 use super::Theme;
-use crate::app::{App, ShippingMode, Tab};
+use crate::app::{AccountSection, App, Severity, ShippingMode, Tab};
+use crate::i18n::t;
 
 pub fn render_footer(f: &mut Frame, area: Rect, app: &App) {
+    // Navigation hints based on current tab, fitted to the available width
+    let nav_hints = hint_spans(fit_shortcuts(shortcuts(app), area.width as usize), &app.theme);
+
+    // Too short for the full footer: keep only the nav hints on the last row
+    if area.height < 3 {
+        if area.height > 0 {
+            let hints_area = Rect { y: area.y + area.height - 1, height: 1, ..area };
+            f.render_widget(Paragraph::new(Line::from(nav_hints)).centered(), hints_area);
+        }
+        return;
+    }
+
     let chunks = Layout::vertical([
         Constraint::Length(1), // Notification or shipping text
         Constraint::Length(1), // Divider
@@ -17,23 +31,48 @@ pub fn render_footer(f: &mut Frame, area: Rect, app: &App) {
     ])
     .split(area);
 
-    // Show notification if present, otherwise show shipping text
-    if let Some(notification) = &app.notification {
+    // Show a pending region-change prompt, then any notification, then a fresh free-shipping
+    // unlock, then a one-time warning, otherwise the shipping text
+    if let Some(prompt) = app.region_conflict_prompt() {
+        let prompt_para = Paragraph::new(Line::from(Span::styled(prompt, Style::default().fg(Theme::YELLOW))))
+            .centered();
+        f.render_widget(prompt_para, chunks[0]);
+    } else if let Some(notification) = app.notifications.back() {
         let notification_para = Paragraph::new(Line::from(Span::styled(
-            notification.clone(),
-            Style::default().fg(Theme::RED),
+            notification.message.clone(),
+            severity_style(notification.severity, &app.theme),
         )))
         .centered();
         f.render_widget(notification_para, chunks[0]);
+    } else if app.free_shipping_just_unlocked() {
+        let unlocked = Paragraph::new(Line::from(Span::styled(
+            t("footer.free_shipping_unlocked"),
+            Style::default().fg(Theme::GREEN),
+        )))
+        .centered();
+        f.render_widget(unlocked, chunks[0]);
+    } else if let Some(notice) = &app.notice {
+        let notice_para = Paragraph::new(Line::from(vec![
+            Span::styled(notice.message.clone(), Style::default().fg(Theme::YELLOW)),
+            Span::styled(t("footer.dismiss_notice"), Style::default().fg(app.theme.dimmed())),
+        ]))
+        .centered();
+        f.render_widget(notice_para, chunks[0]);
+    } else if app.regions_failed {
+        let note = Paragraph::new(Line::from(Span::styled(
+            t("footer.regions_unavailable"),
+            Style::default().fg(app.theme.dimmed()),
+        )))
+        .centered();
+        f.render_widget(note, chunks[0]);
     } else {
         // Free shipping text
-        let shipping_text = format!(
-            "free shipping on {} orders over ${}",
-            app.region.code, app.region.free_shipping_threshold
-        );
+        let shipping_text = t("footer.free_shipping")
+            .replace("{code}", &app.region.code)
+            .replace("{amount}", &app.region.format_whole(app.region.free_shipping_threshold_cents()));
         let shipping = Paragraph::new(Line::from(Span::styled(
             shipping_text,
-            Style::default().fg(Theme::DIMMED),
+            Style::default().fg(app.theme.dimmed()),
         )))
         .centered();
         f.render_widget(shipping, chunks[0]);
@@ -41,118 +80,258 @@ pub fn render_footer(f: &mut Frame, area: Rect, app: &App) {
 
     // Divider
     let divider = Paragraph::new(Line::from(Span::styled(
-        "─".repeat(area.width as usize),
-        Style::default().fg(Theme::BORDER),
+        Theme::DIVIDER.repeat(area.width as usize),
+        Style::default().fg(app.theme.border()),
     )));
     f.render_widget(divider, chunks[1]);
 
-    // Navigation hints based on current tab
-    let nav_hints = get_navigation_hints(app);
     let nav = Paragraph::new(Line::from(nav_hints)).centered();
     f.render_widget(nav, chunks[2]);
 }
 
-fn get_navigation_hints(app: &App) -> Vec<Span<'static>> {
+/// Notifications older than the one in the footer, newest lowest, over the bottom of `area`
+pub fn render_notification_stack(f: &mut Frame, area: Rect, app: &App) {
+    // With a region prompt in the footer, the newest notification moves up too
+    let skip = if app.region_conflict_prompt().is_some() { 0 } else { 1 };
+    for (row, notification) in app.notifications.iter().rev().skip(skip).enumerate() {
+        let Some(y) = (area.y + area.height).checked_sub(row as u16 + 1).filter(|y| *y >= area.y) else {
+            break;
+        };
+        let row_area = Rect { y, height: 1, ..area };
+        f.render_widget(Clear, row_area);
+        let line = Line::from(Span::styled(notification.message.clone(), severity_style(notification.severity, &app.theme)));
+        f.render_widget(Paragraph::new(line).centered(), row_area);
+    }
+}
+
+fn severity_style(severity: Severity, theme: &Theme) -> Style {
+    match severity {
+        Severity::Error => Style::default().fg(Theme::RED),
+        Severity::Info => Style::default().fg(theme.fg()),
+        Severity::Success => Style::default().fg(Theme::GREEN),
+    }
+}
+
+/// A key and what it does, as listed in the footer and the help overlay
+#[derive(Debug, Clone)]
+pub struct Shortcut {
+    pub key: &'static str,
+    pub label: String,
+}
+
+fn shortcut(key: &'static str, label: impl Into<String>) -> Shortcut {
+    Shortcut { key, label: label.into() }
+}
+
+/// Gap between two hints in the footer
+const HINT_GAP: &str = "   ";
+
+/// "key label" pairs separated by gaps
+fn hint_spans(shortcuts: Vec<Shortcut>, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for (i, shortcut) in shortcuts.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(HINT_GAP, Style::default()));
+        }
+        spans.push(Span::styled(format!("{} ", shortcut.key), Style::default().fg(theme.fg())));
+        spans.push(Span::styled(shortcut.label, Style::default().fg(theme.dimmed())));
+    }
+    spans
+}
+
+/// Drop trailing hints until the line fits, so the leading (most important) keys stay visible
+fn fit_shortcuts(mut shortcuts: Vec<Shortcut>, width: usize) -> Vec<Shortcut> {
+    let line_width = |shortcuts: &[Shortcut]| {
+        let pairs: usize = shortcuts.iter().map(|s| s.key.width() + 1 + s.label.width()).sum();
+        pairs + HINT_GAP.len() * shortcuts.len().saturating_sub(1)
+    };
+    while line_width(&shortcuts) > width && shortcuts.len() > 1 {
+        shortcuts.pop();
+    }
+    shortcuts
+}
+
+/// "r 🌎 (Global)" leading hint, left out when there's no other region to cycle to
+fn region_hint(app: &App) -> Vec<Shortcut> {
+    if !app.can_change_region() {
+        return Vec::new();
+    }
+    vec![shortcut("r", format!("{} ({})", app.region.flag, app.region.code))]
+}
+
+/// Keys that work from any tab, listed below the current ones in the help overlay
+fn global_shortcuts(app: &App) -> Vec<Shortcut> {
+    let mut shortcuts = Vec::new();
+    // In the shop, plain digits type a quantity, so tabs take alt+digit instead
+    if app.current_tab == Tab::Shop {
+        shortcuts.push(shortcut("alt+1-4", t("hint.tabs")));
+    } else {
+        shortcuts.push(shortcut("1-4", t("hint.tabs")));
+    }
+    shortcuts.extend([
+        shortcut("s/a/c", t("hint.shop_account_cart")),
+        shortcut("b", t("hint.back")),
+        shortcut("v", t("hint.peek")),
+        shortcut("R", t("hint.retry_load")),
+        shortcut("^p", t("hint.privacy")),
+        shortcut("?", t("hint.help")),
+        shortcut("q", t("hint.quit")),
+    ]);
+    if app.notice.is_some() {
+        shortcuts.push(shortcut("D", t("hint.dismiss")));
+    }
+    shortcuts
+}
+
+/// Keys for the current tab and step, most important first; the footer shows as many as fit
+pub fn shortcuts(app: &App) -> Vec<Shortcut> {
+    if app.shop_filter_active {
+        return vec![
+            shortcut("enter", t("hint.apply")),
+            shortcut("esc", t("hint.clear")),
+        ];
+    }
     match app.current_tab {
-        Tab::Home => vec![
-            Span::styled("r ", Style::default().fg(Theme::FG)),
-            Span::styled(format!("{} ({})", app.region.flag, app.region.code), Style::default().fg(Theme::DIMMED)),
-            Span::styled("   ", Style::default()),
-            Span::styled("q ", Style::default().fg(Theme::FG)),
-            Span::styled("quit", Style::default().fg(Theme::DIMMED)),
+        Tab::Home => [region_hint(app), vec![
+            shortcut("v", t("hint.peek")),
+            shortcut("q", t("hint.quit")),
+            shortcut("?", t("hint.help")),
+            shortcut("enter", t("hint.open_shop")),
+        ]].concat(),
+        Tab::Shop => [region_hint(app), vec![
+            shortcut("↑/↓", t("hint.products")),
+            shortcut("+/-", t("hint.qty")),
+            shortcut("/", t("hint.filter")),
+            shortcut("g", t("hint.group")),
+            shortcut("c", t("hint.cart")),
+            shortcut("q", t("hint.quit")),
+            shortcut("enter", t("hint.add_to_cart")),
+            shortcut("0-9", t("hint.type_qty")),
+            shortcut("m", t("hint.more")),
+            shortcut("f", t("hint.favorite")),
+            shortcut("F", t("hint.favorites_only")),
+            shortcut("F5", t("hint.refresh")),
+        ]].concat(),
+        Tab::Account if app.show_order_detail => vec![
+            shortcut("esc", t("hint.back")),
+            shortcut("↑/↓", t("hint.orders")),
+            shortcut("t", t("hint.copy_tracking")),
+        ],
+        Tab::Account if app.account_section == AccountSection::Subscriptions && !app.subscriptions.is_empty() => vec![
+            shortcut("j/k", t("hint.subscriptions")),
+            shortcut("p", t("hint.pause")),
+            shortcut("x", t("hint.cancel")),
+            shortcut("↑/↓", t("hint.navigate")),
         ],
-        Tab::Shop => vec![
-            Span::styled("r ", Style::default().fg(Theme::FG)),
-            Span::styled(format!("{} ({})", app.region.flag, app.region.code), Style::default().fg(Theme::DIMMED)),
-            Span::styled("   ", Style::default()),
-            Span::styled("↑/↓ ", Style::default().fg(Theme::FG)),
-            Span::styled("products", Style::default().fg(Theme::DIMMED)),
-            Span::styled("   ", Style::default()),
-            Span::styled("+/- ", Style::default().fg(Theme::FG)),
-            Span::styled("qty", Style::default().fg(Theme::DIMMED)),
-            Span::styled("   ", Style::default()),
-            Span::styled("c ", Style::default().fg(Theme::FG)),
-            Span::styled("cart", Style::default().fg(Theme::DIMMED)),
-            Span::styled("   ", Style::default()),
-            Span::styled("q ", Style::default().fg(Theme::FG)),
-            Span::styled("quit", Style::default().fg(Theme::DIMMED)),
+        Tab::Account if app.account_section == AccountSection::OrderHistory && !app.orders.is_empty() => vec![
+            shortcut("↑/↓", t("hint.navigate")),
+            shortcut("enter", t("hint.order_details")),
+            shortcut("J/K", t("hint.page")),
+            shortcut("X", t("hint.reset")),
+        ],
+        Tab::Account if app.account_section == AccountSection::Settings => vec![
+            shortcut("↑/↓", t("hint.navigate")),
+            shortcut("enter", t("settings.confirm_before_order")),
+            shortcut("o", t("settings.celebrate_orders")),
+            shortcut("w", t("settings.reset_notices")),
+            shortcut("X", t("hint.reset")),
         ],
         Tab::Account => vec![
-            Span::styled("↑/↓ ", Style::default().fg(Theme::FG)),
-            Span::styled("navigate", Style::default().fg(Theme::DIMMED)),
-            Span::styled("   ", Style::default()),
-            Span::styled("enter ", Style::default().fg(Theme::FG)),
-            Span::styled("select", Style::default().fg(Theme::DIMMED)),
+            shortcut("↑/↓", t("hint.navigate")),
+            shortcut("X", t("hint.reset")),
         ],
         Tab::Cart => {
             use crate::app::CheckoutStep;
             match app.checkout_step {
                 CheckoutStep::Cart => vec![
-                    Span::styled("esc ", Style::default().fg(Theme::FG)),
-                    Span::styled("back", Style::default().fg(Theme::DIMMED)),
-                    Span::styled("   ", Style::default()),
-                    Span::styled("↑/↓ ", Style::default().fg(Theme::FG)),
-                    Span::styled("items", Style::default().fg(Theme::DIMMED)),
-                    Span::styled("   ", Style::default()),
-                    Span::styled("+/- ", Style::default().fg(Theme::FG)),
-                    Span::styled("qty", Style::default().fg(Theme::DIMMED)),
-                    Span::styled("   ", Style::default()),
-                    Span::styled("c ", Style::default().fg(Theme::FG)),
-                    Span::styled("checkout", Style::default().fg(Theme::DIMMED)),
+                    shortcut("esc", t("hint.back")),
+                    shortcut("↑/↓", t("hint.items")),
+                    shortcut("+/-", t("hint.qty")),
+                    shortcut("x", t("hint.remove")),
+                    shortcut("c", t("hint.checkout")),
+                    shortcut("g", if app.gift_order { t("hint.gift_off") } else { t("hint.gift") }),
+                    shortcut("S", t("hint.subscribe")),
+                    shortcut("f", t("hint.add_suggestion")),
                 ],
                 CheckoutStep::Shipping => {
                     if app.shipping_mode == ShippingMode::SelectAddress {
-                        vec![
-                            Span::styled("esc ", Style::default().fg(Theme::FG)),
-                            Span::styled("back", Style::default().fg(Theme::DIMMED)),
-                            Span::styled("   ", Style::default()),
-                            Span::styled("↑/↓ ", Style::default().fg(Theme::FG)),
-                            Span::styled("addresses", Style::default().fg(Theme::DIMMED)),
-                            Span::styled("   ", Style::default()),
-                            Span::styled("x/del ", Style::default().fg(Theme::FG)),
-                            Span::styled("remove", Style::default().fg(Theme::DIMMED)),
-                            Span::styled("   ", Style::default()),
-                            Span::styled("enter ", Style::default().fg(Theme::FG)),
-                            Span::styled("select", Style::default().fg(Theme::DIMMED)),
-                        ]
+                        let mut hints = vec![
+                            shortcut("esc", t("hint.back")),
+                            shortcut("↑/↓", t("hint.addresses")),
+                            shortcut("x/del", t("hint.remove")),
+                            shortcut("enter", t("hint.select")),
+                        ];
+                        if app.address_select_index < app.saved_addresses.len() {
+                            hints.push(shortcut("d", t("hint.duplicate")));
+                            hints.push(shortcut("e", t("hint.edit")));
+                        }
+                        if app.undo_deleted_address.is_some() {
+                            hints.push(shortcut("u", t("hint.undo")));
+                        }
+                        hints
                     } else {
                         vec![
-                            Span::styled("esc ", Style::default().fg(Theme::FG)),
-                            Span::styled("back", Style::default().fg(Theme::DIMMED)),
-                            Span::styled("   ", Style::default()),
-                            Span::styled("↑/↓ ", Style::default().fg(Theme::FG)),
-                            Span::styled("fields", Style::default().fg(Theme::DIMMED)),
-                            Span::styled("   ", Style::default()),
-                            Span::styled("tab ", Style::default().fg(Theme::FG)),
-                            Span::styled("next", Style::default().fg(Theme::DIMMED)),
-                            Span::styled("   ", Style::default()),
-                            Span::styled("enter ", Style::default().fg(Theme::FG)),
-                            Span::styled("continue", Style::default().fg(Theme::DIMMED)),
+                            shortcut("esc", t("hint.back")),
+                            shortcut("↑/↓", t("hint.fields")),
+                            shortcut("tab", t("hint.next")),
+                            shortcut("enter", t("hint.continue")),
+                            shortcut("^g", t("hint.jump")),
                         ]
                     }
                 }
+                CheckoutStep::Payment if app.payment_method.is_none() => vec![
+                    shortcut("esc", t("hint.back")),
+                    shortcut("↑/↓", t("hint.payment_methods")),
+                    shortcut("enter", t("hint.select")),
+                ],
+                CheckoutStep::Gift => vec![
+                    shortcut("esc", t("hint.back")),
+                    shortcut("alt+enter", t("hint.new_line")),
+                    shortcut("enter", t("hint.continue")),
+                ],
                 CheckoutStep::Payment => vec![
-                    Span::styled("esc ", Style::default().fg(Theme::FG)),
-                    Span::styled("back", Style::default().fg(Theme::DIMMED)),
-                    Span::styled("   ", Style::default()),
-                    Span::styled("↑/↓ ", Style::default().fg(Theme::FG)),
-                    Span::styled("fields", Style::default().fg(Theme::DIMMED)),
-                    Span::styled("   ", Style::default()),
-                    Span::styled("tab ", Style::default().fg(Theme::FG)),
-                    Span::styled("next", Style::default().fg(Theme::DIMMED)),
-                    Span::styled("   ", Style::default()),
-                    Span::styled("enter ", Style::default().fg(Theme::FG)),
-                    Span::styled("continue", Style::default().fg(Theme::DIMMED)),
+                    shortcut("esc", t("hint.back")),
+                    shortcut("↑/↓", t("hint.fields")),
+                    shortcut("tab", t("hint.next")),
+                    shortcut("enter", t("hint.continue")),
+                    shortcut("^g", t("hint.jump")),
                 ],
                 CheckoutStep::Confirmation => vec![
-                    Span::styled("esc ", Style::default().fg(Theme::FG)),
-                    Span::styled("back", Style::default().fg(Theme::DIMMED)),
-                    Span::styled("   ", Style::default()),
-                    Span::styled("enter ", Style::default().fg(Theme::FG)),
-                    Span::styled("confirm order", Style::default().fg(Theme::DIMMED)),
+                    shortcut("esc", t("hint.back")),
+                    shortcut("enter", t("hint.confirm_order")),
+                    shortcut("y", t("hint.copy_receipt")),
+                    shortcut("e", t("hint.email_preview")),
                 ],
             }
         }
     }
 }
 
+/// Every shortcut for the current context, then the global ones, in a modal over the body
+pub fn render_help(f: &mut Frame, area: Rect, app: &App) {
+    let here = shortcuts(app);
+    let everywhere = global_shortcuts(app);
+    let key_width = here.iter().chain(&everywhere).map(|s| s.key.width()).max().unwrap_or(0);
+    let rows = |shortcuts: Vec<Shortcut>| {
+        shortcuts.into_iter().map(move |shortcut| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<width$}  ", shortcut.key, width = key_width),
+                    Style::default().fg(app.theme.fg()),
+                ),
+                Span::styled(shortcut.label, Style::default().fg(app.theme.dimmed())),
+            ])
+        })
+    };
+
+    let mut lines = vec![Line::from(Span::styled(t("help.here"), Style::default().fg(app.theme.accent())))];
+    lines.extend(rows(here));
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled(t("help.everywhere"), Style::default().fg(app.theme.accent()))));
+    lines.extend(rows(everywhere));
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled(t("help.close"), Style::default().fg(app.theme.dimmed()))));
+
+    let height = lines.len() as u16 + 2;
+    super::render_overlay(f, super::popup_area(area, 48, height), lines, 0, &app.theme);
+}
@@ -6,8 +6,8 @@ use ratatui::{
     Frame,
 };
 
-use super::Theme;
 use crate::app::{App, Tab};
+use crate::i18n::t;
 
 pub fn render_header(f: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::horizontal([
@@ -18,45 +18,39 @@ pub fn render_header(f: &mut Frame, area: Rect, app: &App) {
     ])
     .split(area);
 
-    // Tab definitions
+    // Tab definitions; in the shop plain digits type a quantity, so only the letters are hinted
+    let digits = app.current_tab != Tab::Shop;
     let tabs = [
-        (Tab::Home, "anora", ""),
-        (Tab::Shop, "s", "shop"),
-        (Tab::Account, "a", "account"),
-        (Tab::Cart, "c", &format!("cart ${} [{}]", app.cart.subtotal_cents() / 100, app.cart.total_items())),
+        (Tab::Home, if digits { "1" } else { "" }, "anora"),
+        (Tab::Shop, if digits { "2/s" } else { "s" }, t("tab.shop")),
+        (Tab::Account, if digits { "3/a" } else { "a" }, t("tab.account")),
+        (Tab::Cart, if digits { "4/c" } else { "c" }, &format!("{} {} [{}]", t("tab.cart"), app.region.format_whole(app.cart.subtotal_cents()), app.cart.total_items())),
     ];
 
     for (i, (tab, key, label)) in tabs.iter().enumerate() {
         let is_active = app.current_tab == *tab;
         
-        let content = if key.is_empty() {
-            label.to_string()
-        } else if label.is_empty() {
-            key.to_string()
-        } else {
-            format!("{} {}", key, label)
-        };
+        let content = if key.is_empty() { label.to_string() } else { format!("{} {}", key, label) };
 
-        let style = if *tab == Tab::Home {
-            Style::default().fg(Theme::FG)
-        } else if is_active {
-            Style::default().fg(Theme::FG)
+        let style = if *tab == Tab::Home || is_active {
+            Style::default().fg(app.theme.fg())
         } else {
-            Style::default().fg(Theme::DIMMED)
+            Style::default().fg(app.theme.dimmed())
         };
 
         let paragraph = Paragraph::new(Line::from(vec![Span::styled(content, style)]))
-            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Theme::BORDER)))
+            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(app.theme.border())))
             .centered();
 
         f.render_widget(paragraph, chunks[i]);
+        app.click_targets.borrow_mut().tabs.push((*tab, chunks[i]));
     }
 }
 
 pub fn render_checkout_header(f: &mut Frame, area: Rect, app: &App) {
     use crate::app::CheckoutStep;
 
-    let steps = ["cart", "shipping", "payment", "confirmation"];
+    let steps = [t("step.cart"), t("step.shipping"), t("step.payment"), t("step.confirmation")];
     
     let spans: Vec<Span> = steps
         .iter()
@@ -64,20 +58,20 @@ pub fn render_checkout_header(f: &mut Frame, area: Rect, app: &App) {
         .flat_map(|(i, step)| {
             let is_current = match app.checkout_step {
                 CheckoutStep::Cart => i == 0,
-                CheckoutStep::Shipping => i == 1,
+                CheckoutStep::Shipping | CheckoutStep::Gift => i == 1,
                 CheckoutStep::Payment => i == 2,
                 CheckoutStep::Confirmation => i == 3,
             };
 
             let style = if is_current {
-                Style::default().fg(Theme::FG)
+                Style::default().fg(app.theme.fg())
             } else {
-                Style::default().fg(Theme::DIMMED)
+                Style::default().fg(app.theme.dimmed())
             };
 
             let mut result = vec![Span::styled(*step, style)];
             if i < steps.len() - 1 {
-                result.push(Span::styled(" / ", Style::default().fg(Theme::DIMMED)));
+                result.push(Span::styled(" / ", Style::default().fg(app.theme.dimmed())));
             }
             result
         })
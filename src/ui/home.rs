@@ -5,21 +5,21 @@ use ratatui::{
     widgets::Paragraph,
     Frame,
 };
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::Theme;
 use crate::app::App;
+use crate::i18n::t;
 
 pub fn render_home(f: &mut Frame, area: Rect, app: &App) {
     if app.show_splash {
-        render_splash(f, area);
+        render_splash(f, area, app);
     } else {
         render_home_content(f, area, app);
     }
 }
 
 /// Render the splash screen with "anora" and blinking cursor
-fn render_splash(f: &mut Frame, area: Rect) {
+fn render_splash(f: &mut Frame, area: Rect, app: &App) {
     // Center the content vertically
     let chunks = Layout::vertical([
         Constraint::Fill(1),
@@ -28,20 +28,12 @@ fn render_splash(f: &mut Frame, area: Rect) {
     ])
     .split(area);
 
-    // Calculate blink state based on time (blink every 500ms)
-    let blink_on = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis()
-        / 500
-        % 2
-        == 0;
-
-    let cursor = if blink_on { "█" } else { " " };
+    // Blink every 500ms, driven by the app's animation clock
+    let cursor = if app.clock.blink_on() { Theme::CURSOR } else { " " };
 
     let line = Line::from(vec![
-        Span::styled("anora", Style::default().fg(Theme::FG)),
-        Span::styled(cursor, Style::default().fg(Theme::PINK)),
+        Span::styled("anora", Style::default().fg(app.theme.fg())),
+        Span::styled(cursor, Style::default().fg(app.theme.accent())),
     ]);
 
     let paragraph = Paragraph::new(line).centered();
@@ -63,13 +55,28 @@ fn render_home_content(f: &mut Frame, area: Rect, app: &App) {
     if has_products {
         let lines = vec![
             Line::from(Span::styled(
-                "welcome to ANORA Labs",
-                Style::default().fg(Theme::FG),
+                t("home.welcome"),
+                Style::default().fg(app.theme.fg()),
+            )),
+            Line::default(),
+            Line::from(Span::styled(
+                t("home.browse"),
+                Style::default().fg(app.theme.dimmed()),
+            )),
+        ];
+
+        let paragraph = Paragraph::new(lines).centered();
+        f.render_widget(paragraph, chunks[1]);
+    } else if app.load_failed {
+        let lines = vec![
+            Line::from(Span::styled(
+                t("home.load_failed"),
+                Style::default().fg(app.theme.fg()),
             )),
             Line::default(),
             Line::from(Span::styled(
-                "press 's' to browse the shop",
-                Style::default().fg(Theme::DIMMED),
+                t("home.retry"),
+                Style::default().fg(app.theme.dimmed()),
             )),
         ];
 
@@ -78,13 +85,13 @@ fn render_home_content(f: &mut Frame, area: Rect, app: &App) {
     } else {
         let lines = vec![
             Line::from(Span::styled(
-                "no products available for this region",
-                Style::default().fg(Theme::FG),
+                t("home.no_products"),
+                Style::default().fg(app.theme.fg()),
             )),
             Line::default(),
             Line::from(Span::styled(
-                "press 'r' to change region",
-                Style::default().fg(Theme::DIMMED),
+                if app.can_change_region() { t("home.change_region") } else { t("home.check_back") },
+                Style::default().fg(app.theme.dimmed()),
             )),
         ];
 
@@ -92,3 +99,23 @@ fn render_home_content(f: &mut Frame, area: Rect, app: &App) {
         f.render_widget(paragraph, chunks[1]);
     }
 }
+
+/// Spinner frames, advanced by the animation clock
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Centered "loading…" line shown in the body while a fetch is in flight
+pub fn render_loading(f: &mut Frame, area: Rect, app: &App) {
+    let chunks = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+    ])
+    .split(area);
+
+    let frame = SPINNER[app.clock.tick(100) % SPINNER.len()];
+    let line = Line::from(vec![
+        Span::styled(format!("{} ", frame), Style::default().fg(app.theme.accent())),
+        Span::styled(t("loading"), Style::default().fg(app.theme.dimmed())),
+    ]);
+    f.render_widget(Paragraph::new(line).centered(), chunks[1]);
+}
@@ -5,6 +5,10 @@ pub mod shop;
 pub mod account;
 pub mod cart;
 pub mod theme;
+pub mod overlay;
+pub mod whats_new;
+pub mod text;
+pub mod celebration;
 
 pub use header::*;
 pub use footer::*;
@@ -13,4 +17,11 @@ pub use shop::*;
 pub use account::*;
 pub use cart::*;
 pub use theme::*;
+pub use overlay::*;
+pub use whats_new::*;
+pub use text::*;
+pub use celebration::*;
+
+#[cfg(test)]
+mod snapshot_tests;
 
@@ -0,0 +1,36 @@
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+use super::Theme;
+
+/// Centered popup rect inside `area`, at most `max_width` x `max_height`
+pub fn popup_area(area: Rect, max_width: u16, max_height: u16) -> Rect {
+    let width = area.width.saturating_sub(8).min(max_width);
+    let height = area.height.saturating_sub(4).min(max_height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Render a bordered modal with text content, scrolled by `scroll` lines
+pub fn render_overlay(f: &mut Frame, popup: Rect, lines: Vec<Line<'static>>, scroll: u16, theme: &Theme) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.fg()))
+        .padding(Padding::horizontal(1));
+    let para = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+
+    f.render_widget(Clear, popup);
+    f.render_widget(para, popup);
+}
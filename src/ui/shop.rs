@@ -6,9 +6,17 @@ use ratatui::{
     Frame,
 };
 
-use super::Theme;
+use super::{collapse_text, fit_width, marquee, wrap_text, Theme};
 use crate::app::App;
-use crate::models::{ProductCategory, ProductType};
+use crate::i18n::t;
+use crate::models::ProductType;
+use unicode_width::UnicodeWidthStr;
+
+/// How often a scrolling product name moves one character
+const MARQUEE_STEP_MS: u128 = 300;
+
+/// Rows a collapsed description takes before "…more"
+const COLLAPSED_DESCRIPTION_ROWS: usize = 2;
 
 pub fn render_shop(f: &mut Frame, area: Rect, app: &App) {
     let chunks = Layout::horizontal([
@@ -25,129 +33,168 @@ pub fn render_shop(f: &mut Frame, area: Rect, app: &App) {
 fn render_product_list(f: &mut Frame, area: Rect, app: &App) {
     let mut lines: Vec<Line> = Vec::new();
 
-    // Group products by category
-    let featured: Vec<_> = app
-        .products
-        .iter()
-        .filter(|p| p.category == ProductCategory::Featured)
-        .collect();
-    let originals: Vec<_> = app
-        .products
-        .iter()
-        .filter(|p| p.category == ProductCategory::Originals)
-        .collect();
-
-    // Featured section
-    if !featured.is_empty() {
-        lines.push(Line::from(Span::styled(
-            "~ featured ~",
-            Style::default().fg(Theme::FG),
-        )));
-
-        for product in &featured {
-            let is_selected = app.selected_product_index < featured.len()
-                && featured[app.selected_product_index].id == product.id;
-            
-            let color = Theme::product_color(&product.name);
-            let style = if is_selected {
-                Style::default().fg(Theme::FG).bg(color)
-            } else {
-                Style::default().fg(Theme::DIMMED)
-            };
-
-            // Create a line that spans the full width with padding
-            let padding = " ".repeat(1); // 1 spaces padding on each side
-            let content_width = area.width.saturating_sub(4) as usize; // Account for padding
-            let product_name = if product.name.len() > content_width {
-                product.name.chars().take(content_width).collect::<String>()
-            } else {
-                format!("{:<width$}", product.name, width = content_width)
-            };
-            
-            lines.push(Line::from(Span::styled(
-                format!("{}{}{}", padding, product_name, padding),
-                style
-            )));
+    // Filter line while typing or filtered
+    let filtering = app.shop_filter_active || !app.shop_filter.is_empty();
+    if filtering {
+        let mut filter_line = Line::from(vec![
+            Span::styled("/ ", Style::default().fg(app.theme.accent())),
+            Span::styled(app.shop_filter.clone(), Style::default().fg(app.theme.fg())),
+        ]);
+        if app.shop_filter_active {
+            filter_line.push_span(Span::styled(Theme::CURSOR, Style::default().fg(app.theme.accent())));
         }
-
+        lines.push(filter_line);
+    }
+    if app.favorites_only {
+        lines.push(Line::from(vec![
+            Span::styled("★ ", Style::default().fg(Theme::YELLOW)),
+            Span::styled(t("shop.favorites"), Style::default().fg(app.theme.fg())),
+            Span::styled(t("shop.show_all"), Style::default().fg(app.theme.dimmed())),
+        ]));
+    }
+    if !lines.is_empty() {
         lines.push(Line::default());
     }
+    let header_rows = lines.len();
+
+    // Products are kept in section order, so the global index runs across sections
+    let visible = app.visible_product_indices();
+    for (section, heading) in app.shop_grouping.headings().into_iter().enumerate() {
+        let products: Vec<_> = visible
+            .iter()
+            .map(|&i| (i, &app.products[i]))
+            .filter(|(_, p)| app.shop_grouping.section_of(p) == section)
+            .collect();
+        if products.is_empty() {
+            continue;
+        }
 
-    // Originals section
-    if !originals.is_empty() {
-        lines.push(Line::from(Span::styled(
-            "~ originals ~",
-            Style::default().fg(Theme::FG),
-        )));
-
-        for (i, product) in originals.iter().enumerate() {
-            let global_index = featured.len() + i;
-            let is_selected = app.selected_product_index == global_index;
-            
-            let color = Theme::product_color(&product.name);
-            let style = if is_selected {
-                Style::default().fg(Theme::FG).bg(color)
-            } else {
-                Style::default().fg(Theme::DIMMED)
+        if lines.len() > header_rows {
+            lines.push(Line::default());
+        }
+        lines.push(Line::from(Span::styled(heading, Style::default().fg(app.theme.fg()))));
+
+        for (index, product) in products {
+            let is_selected = app.selected_product_index == index;
+
+            let color = product.color();
+            // Sold-out rows stay listed but greyed out
+            let style = match (is_selected, product.is_sold_out()) {
+                (true, false) => Style::default().fg(app.theme.fg()).bg(color),
+                (true, true) => Style::default().fg(app.theme.fg()).bg(app.theme.border()),
+                (false, false) => Style::default().fg(app.theme.dimmed()),
+                (false, true) => Style::default().fg(app.theme.border()),
             };
 
             // Create a line that spans the full width with padding
-            let padding = " ".repeat(1); // 1 spaces padding on each side
+            let padding = " "; // 1 spaces padding on each side
             let content_width = area.width.saturating_sub(4) as usize; // Account for padding
-            let product_name = if product.name.len() > content_width {
-                product.name.chars().take(content_width).collect::<String>()
+            // Favorites get a star at the end of the row, taken out of the name's room
+            let favorite = app.favorites.contains(&product.id);
+            let content_width = if favorite { content_width.saturating_sub(2) } else { content_width };
+            // The selected row scrolls its name when it doesn't fit
+            let product_name = if is_selected {
+                marquee(&product.name, content_width, app.clock.tick(MARQUEE_STEP_MS))
             } else {
-                format!("{:<width$}", product.name, width = content_width)
+                fit_width(&product.name, content_width)
             };
-            
-            lines.push(Line::from(Span::styled(
+
+            // Remember where the row lands so a click can select it
+            let row = lines.len() as u16;
+            if row < area.height {
+                let rect = Rect::new(area.x, area.y + row, area.width, 1);
+                app.click_targets.borrow_mut().products.push((index, rect));
+            }
+            let mut row_line = Line::from(Span::styled(
                 format!("{}{}{}", padding, product_name, padding),
                 style
-            )));
+            ));
+            if favorite {
+                row_line.push_span(Span::styled("★ ", style.fg(Theme::YELLOW)));
+            }
+            lines.push(row_line);
         }
     }
 
+    if (filtering || app.favorites_only) && visible.is_empty() {
+        lines.push(Line::from(Span::styled(t("shop.no_matches"), Style::default().fg(app.theme.dimmed()))));
+    }
+
     let paragraph = Paragraph::new(lines);
     f.render_widget(paragraph, area);
 }
 
 fn render_product_details(f: &mut Frame, area: Rect, app: &App) {
-    if app.products.is_empty() {
+    let Some(product) = app.selected_product() else {
         return;
-    }
-
-    let product = &app.products[app.selected_product_index];
-    let color = Theme::product_color(&product.name);
+    };
+    let color = product.color();
 
     let mut lines: Vec<Line> = vec![
         // Product name
         Line::from(Span::styled(
             product.name.clone(),
-            Style::default().fg(Theme::FG),
+            Style::default().fg(app.theme.fg()),
         )),
         // Product details line
         Line::from(Span::styled(
             product.details_line(),
-            Style::default().fg(Theme::DIMMED),
+            Style::default().fg(app.theme.dimmed()),
         )),
         Line::default(),
         // Price
         Line::from(Span::styled(
-            product.price_display(),
+            product.price_display(&app.region),
             Style::default().fg(color),
         )),
-        Line::default(),
     ];
 
-    // Description - wrap it manually for better display
-    let desc_style = Style::default().fg(Theme::DIMMED);
-    lines.push(Line::from(Span::styled(product.description.clone(), desc_style)));
+    // Reviews
+    if let Some(rating) = product.rating_line() {
+        lines.push(Line::from(Span::styled(rating, Style::default().fg(Theme::YELLOW))));
+    }
+
+    // Quantity price breaks
+    for hint in product.tier_hints(&app.region) {
+        lines.push(Line::from(Span::styled(hint, Style::default().fg(Theme::GREEN))));
+    }
+    if product.is_sold_out() {
+        lines.push(Line::from(Span::styled(t("shop.sold_out"), Style::default().fg(Theme::RED))));
+    } else if let Some(max) = product.max_per_order {
+        lines.push(Line::from(Span::styled(
+            t("shop.limit").replace("{n}", &max.to_string()),
+            Style::default().fg(Theme::YELLOW),
+        )));
+    }
+    lines.push(Line::default());
+
+    // Description - collapsed to a couple of rows unless expanded with m
+    let desc_style = Style::default().fg(app.theme.dimmed());
+    let description = product.description_for(&app.region);
+    let toggle_style = Style::default().fg(app.theme.fg());
+    let width = area.width as usize;
+    let (text, toggle) = match collapse_text(description, width, COLLAPSED_DESCRIPTION_ROWS, t("shop.more")) {
+        Some(collapsed) if !app.description_expanded => (collapsed, Some(t("shop.more"))),
+        Some(_) => (description, Some(t("shop.less"))),
+        None => (description, None),
+    };
+    let mut description_lines = wrap_text(text, width, desc_style);
+    if let Some(toggle) = toggle {
+        // The toggle trails the last row when it fits, otherwise it gets its own
+        match description_lines.last_mut() {
+            Some(last) if last.width() + 1 + toggle.width() <= width => {
+                last.spans.push(Span::styled(format!(" {}", toggle), toggle_style));
+            }
+            _ => description_lines.push(Line::from(Span::styled(toggle, toggle_style))),
+        }
+    }
+    lines.extend(description_lines);
     lines.push(Line::default());
 
     // Action based on product type
     match product.product_type {
         ProductType::Subscription => {
-            let padding = " ".repeat(1);
+            let padding = " ";
             let subscribe_text = "subscribe";
             let content_width = area.width.saturating_sub(4) as usize;
             let padded_subscribe = if subscribe_text.len() + 2 <= content_width {
@@ -157,18 +204,27 @@ fn render_product_details(f: &mut Frame, area: Rect, app: &App) {
             };
             
             lines.push(Line::from(vec![
-                Span::styled(padded_subscribe, Style::default().fg(Theme::FG).bg(color)),
-                Span::styled("  enter", Style::default().fg(Theme::DIMMED)),
+                Span::styled(padded_subscribe, Style::default().fg(app.theme.fg()).bg(color)),
+                Span::styled("  enter", Style::default().fg(app.theme.dimmed())),
+            ]));
+        }
+        ProductType::OneTime | ProductType::Other if !app.quantity_input.is_empty() => {
+            // Typed quantity in progress
+            lines.push(Line::from(vec![
+                Span::styled(t("shop.qty"), Style::default().fg(app.theme.dimmed())),
+                Span::styled(app.quantity_input.clone(), Style::default().fg(app.theme.fg())),
+                Span::styled(Theme::CURSOR, Style::default().fg(app.theme.accent())),
+                Span::styled(t("shop.qty_help"), Style::default().fg(app.theme.dimmed())),
             ]));
         }
-        ProductType::OneTime => {
+        ProductType::OneTime | ProductType::Other => {
             lines.push(Line::from(vec![
-                Span::styled("-", Style::default().fg(Theme::DIMMED)),
+                Span::styled("-", Style::default().fg(app.theme.dimmed())),
                 Span::styled(
                     format!(" {} ", app.product_quantity),
-                    Style::default().fg(Theme::FG),
+                    Style::default().fg(app.theme.fg()),
                 ),
-                Span::styled("+", Style::default().fg(Theme::DIMMED)),
+                Span::styled("+", Style::default().fg(app.theme.dimmed())),
             ]));
         }
     }
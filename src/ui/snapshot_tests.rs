@@ -0,0 +1,273 @@
+//! Rendering snapshots: draw views into a `TestBackend` with a fixed `App` and check the cell grid
+
+use ratatui::{backend::TestBackend, layout::Rect, Frame, Terminal};
+
+use super::*;
+use crate::app::{App, Tab};
+use crate::models::{Product, Region};
+
+/// Render into a `width` x `height` buffer and return its rows as text
+fn render(width: u16, height: u16, draw: impl FnOnce(&mut Frame, Rect)) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| draw(f, f.area())).unwrap();
+    let buffer = terminal.backend().buffer();
+    (0..height)
+        .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect::<String>().trim_end().to_string())
+        .collect()
+}
+
+fn app_with_products() -> App {
+    let mut app = App::for_test();
+    app.products = vec![Product::sample("segfault", 2200), Product::sample("404", 2500)];
+    app
+}
+
+#[test]
+fn header_shows_tabs_and_cart_total() {
+    let mut app = app_with_products();
+    let segfault = app.products[0].clone();
+    app.cart.add_item(segfault, 2);
+
+    let rows = render(80, 3, |f, area| render_header(f, area, &app));
+    assert_eq!(rows[1], "│      1 anora     ││     2/s shop     ││    3/a account   ││ 4/c cart $44 [2] │");
+
+    // Digits type a quantity in the shop, so only the letters are hinted there
+    app.current_tab = Tab::Shop;
+    let rows = render(80, 3, |f, area| render_header(f, area, &app));
+    assert_eq!(rows[1], "│       anora      ││      s shop      ││     a account    ││  c cart $44 [2]  │");
+}
+
+#[test]
+fn footer_shows_free_shipping_and_hints() {
+    let mut app = app_with_products();
+    app.current_tab = Tab::Shop;
+
+    let rows = render(80, 3, |f, area| render_footer(f, area, &app));
+    assert_eq!(rows[0].trim(), "free shipping on Global orders over $40");
+    assert_eq!(rows[1], "─".repeat(80));
+    assert!(rows[2].contains("q quit"), "{:?}", rows[2]);
+}
+
+#[test]
+fn home_splash_cursor_is_pinned_by_the_clock() {
+    let mut app = App::for_test();
+    app.show_splash = true;
+
+    let rows = render(20, 3, |f, area| render_home(f, area, &app));
+    assert_eq!(rows[1], "       anora█");
+}
+
+#[test]
+fn home_without_products_offers_region_change() {
+    let mut app = App::for_test();
+    app.regions = vec![Region::default(), Region { id: "uz".to_string(), ..Region::default() }];
+
+    let rows = render(60, 10, |f, area| render_home(f, area, &app));
+    assert!(rows.iter().any(|row| row.contains("no products available for this region")));
+    assert!(rows.iter().any(|row| row.contains("press 'r' to change region")));
+}
+
+#[test]
+fn loading_shows_a_spinner_line() {
+    let app = App::for_test();
+
+    let rows = render(30, 3, |f, area| render_loading(f, area, &app));
+    assert_eq!(rows[1].trim(), "⠋ loading…");
+}
+
+#[test]
+fn single_region_hides_the_region_switch() {
+    let mut app = App::for_test();
+    app.regions = vec![Region::default()];
+
+    let home = render(60, 10, |f, area| render_home(f, area, &app));
+    assert!(!home.iter().any(|row| row.contains("press 'r'")));
+
+    app.regions_failed = true;
+    let footer = render(80, 3, |f, area| render_footer(f, area, &app));
+    assert_eq!(footer[0].trim(), "couldn't load regions — showing the default region");
+    assert!(footer[2].trim_start().starts_with("v peek"), "{:?}", footer[2]);
+}
+
+#[test]
+fn shop_lists_products_and_details() {
+    let app = app_with_products();
+
+    let rows = render(80, 12, |f, area| render_shop(f, area, &app));
+    assert_eq!(rows[0], "~ featured ~             segfault");
+    assert_eq!(rows[1], " segfault                dark roast | 12oz | whole beans");
+    assert_eq!(rows[2], " 404");
+    assert_eq!(rows[3].trim(), "$22");
+}
+
+#[test]
+fn shop_groups_by_roast_on_request() {
+    let mut app = app_with_products();
+    app.products[1].roast_level = None;
+    app.toggle_shop_grouping();
+
+    let rows = render(80, 12, |f, area| render_shop(f, area, &app));
+    assert_eq!(rows[0], "~ dark roast ~           segfault");
+    assert!(rows[3].starts_with("~ other ~"), "{:#?}", rows);
+    assert!(rows[4].starts_with(" 404"), "{:#?}", rows);
+}
+
+#[test]
+fn favorites_get_a_star_and_their_own_view() {
+    let mut app = app_with_products();
+    app.favorites.insert(app.products[1].id);
+
+    let rows = render(80, 12, |f, area| render_shop(f, area, &app));
+    assert_eq!(rows[2], " 404                ★");
+
+    app.toggle_favorites_only();
+    let rows = render(80, 12, |f, area| render_shop(f, area, &app));
+    assert!(rows[0].starts_with("★ favorites · F all"), "{:#?}", rows);
+    assert!(!rows.iter().any(|row| row.contains(" segfault")), "{:#?}", rows);
+}
+
+#[test]
+fn empty_cart_says_so() {
+    let app = App::for_test();
+
+    let rows = render(60, 10, |f, area| render_cart(f, area, &app));
+    assert!(rows.iter().any(|row| row.contains("Your cart is empty.")));
+}
+
+#[test]
+fn account_shows_menu_and_empty_history() {
+    let app = App::for_test();
+
+    let rows = render(80, 12, |f, area| render_account(f, area, &app));
+    assert!(rows.iter().any(|row| row.contains("order history")));
+    assert!(rows.iter().any(|row| row.contains("no orders found")));
+}
+
+#[test]
+fn order_history_keeps_each_order_in_its_own_currency() {
+    let mut app = app_with_products();
+    let segfault = app.products[0].clone();
+    app.cart.add_item(segfault, 2);
+    app.orders = vec![app.build_order()];
+
+    // Switching region doesn't reprice what was already paid
+    app.region = Region { id: "uz".to_string(), currency: "UZS".to_string(), ..Region::default() };
+    let rows = render(80, 12, |f, area| render_account(f, area, &app));
+    assert!(rows.iter().any(|row| row.contains("$44.00")), "{:#?}", rows);
+    assert!(!rows.iter().any(|row| row.contains("so'm")), "{:#?}", rows);
+}
+
+#[test]
+fn address_form_shows_every_shipping_field() {
+    let mut app = app_with_products();
+    app.checkout_step = crate::app::CheckoutStep::Shipping;
+    app.shipping_mode = crate::app::ShippingMode::AddNewAddress;
+    app.cart.add_item(app.products[0].clone(), 1);
+
+    let rows = render(80, 20, |f, area| render_cart(f, area, &app));
+    for label in ["name", "street 2 (optional)", "city", "state (optional)", "postal code"] {
+        assert!(rows.iter().any(|row| row.contains(label)), "{label}: {:#?}", rows);
+    }
+}
+
+#[test]
+fn confirmation_says_free_shipping_over_the_threshold() {
+    let mut app = app_with_products();
+    app.checkout_step = crate::app::CheckoutStep::Confirmation;
+    let segfault = app.products[0].clone();
+    app.cart.add_item(segfault.clone(), 1);
+
+    let rows = render(60, 16, |f, area| render_cart(f, area, &app));
+    assert!(rows.iter().any(|row| row.trim() == "shipping: $8.00"), "{:#?}", rows);
+
+    app.cart.add_item(segfault, 1);
+    let rows = render(60, 16, |f, area| render_cart(f, area, &app));
+    assert!(rows.iter().any(|row| row.trim() == "shipping: free"), "{:#?}", rows);
+}
+
+#[test]
+fn cart_shows_running_totals() {
+    let mut app = app_with_products();
+    app.current_tab = Tab::Cart;
+    let segfault = app.products[0].clone();
+    app.cart.add_item(segfault, 1);
+
+    let rows = render(60, 12, |f, area| render_cart(f, area, &app));
+    assert_eq!(rows[10].trim(), "add $18.00 for free shipping");
+    assert_eq!(rows[11].trim(), "1 item · subtotal: $22.00 · shipping: $8.00");
+
+    let product = app.products[1].clone();
+    app.cart.add_item(product, 1);
+    let rows = render(60, 12, |f, area| render_cart(f, area, &app));
+    assert_eq!(rows[11].trim(), "2 items · subtotal: $47.00 · shipping: free");
+    assert!(!rows.iter().any(|row| row.contains("for free shipping")), "{:#?}", rows);
+}
+
+/// Key column of each row in the help overlay
+fn help_keys(app: &App) -> Vec<String> {
+    render(60, 40, |f, area| render_help(f, area, app))
+        .iter()
+        .filter_map(|row| row.trim_start_matches([' ', '│']).split("  ").next().map(str::to_string))
+        .filter(|key| !key.is_empty())
+        .collect()
+}
+
+#[test]
+fn help_lists_every_key_the_view_handles() {
+    let mut app = app_with_products();
+    app.current_tab = Tab::Shop;
+
+    // The footer fits what it can; the overlay lists all of it, plus the keys that work anywhere
+    let footer = render(40, 3, |f, area| render_footer(f, area, &app));
+    assert!(!footer[2].contains("q quit"), "{:?}", footer[2]);
+    let keys = help_keys(&app);
+    for key in ["↑/↓", "+/-", "/", "g", "enter", "0-9", "m", "f", "F", "F5", "s/a/c", "b", "R", "?", "q"] {
+        assert!(keys.iter().any(|k| k == key), "{key:?} in {keys:?}");
+    }
+    // Digits type a quantity in the shop, so tabs take alt+digit there
+    assert!(!keys.iter().any(|k| k == "1-4"), "{keys:?}");
+    assert!(keys.iter().any(|k| k == "alt+1-4"), "{keys:?}");
+
+    app.current_tab = Tab::Cart;
+    let keys = help_keys(&app);
+    for key in ["S", "f", "g", "1-4"] {
+        assert!(keys.iter().any(|k| k == key), "{key:?} in {keys:?}");
+    }
+
+    app.current_tab = Tab::Account;
+    app.account_section = crate::app::AccountSection::Settings;
+    let keys = help_keys(&app);
+    for key in ["enter", "o", "w", "X"] {
+        assert!(keys.iter().any(|k| k == key), "{key:?} in {keys:?}");
+    }
+}
+
+#[test]
+fn cart_peek_lists_items_or_says_empty() {
+    let mut app = app_with_products();
+    let rows = render(60, 16, |f, area| render_cart_peek(f, area, &app));
+    assert!(rows.iter().any(|row| row.contains("empty")), "{:#?}", rows);
+
+    let segfault = app.products[0].clone();
+    app.cart.add_item(segfault, 2);
+    let rows = render(60, 16, |f, area| render_cart_peek(f, area, &app));
+    assert!(rows.iter().any(|row| row.contains("2 × segfault  $44")), "{:#?}", rows);
+    assert!(rows.iter().any(|row| row.contains("total: $44.00")), "{:#?}", rows);
+}
+
+#[tokio::test]
+async fn clicks_hit_the_tabs_and_product_rows_last_drawn() {
+    let mut app = app_with_products();
+    app.current_tab = Tab::Shop;
+    render(60, 10, |f, area| render_shop(f, area, &app));
+
+    // Row 0 is the section heading, then one row per product
+    app.click_at(2, 2).await;
+    assert_eq!(app.selected_product_index, 1);
+
+    // Each test render draws at the origin, so start over for the header
+    app.click_targets.borrow_mut().clear();
+    render(80, 3, |f, area| render_header(f, area, &app));
+    app.click_at(70, 1).await;
+    assert_eq!(app.current_tab, Tab::Cart);
+}
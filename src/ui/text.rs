@@ -0,0 +1,170 @@
+use ratatui::{style::Style, text::Line};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Truncate text to at most `width` terminal columns without splitting a grapheme
+/// (wide CJK characters count as two columns, combining marks stay with their base)
+pub fn truncate_width(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, grapheme) in text.grapheme_indices(true) {
+        used += grapheme.width();
+        if used > width {
+            return &text[..i];
+        }
+    }
+    text
+}
+
+/// Truncate or right-pad text so it fills exactly `width` columns
+/// A wide character that would straddle the edge is replaced by padding
+pub fn fit_width(text: &str, width: usize) -> String {
+    let truncated = truncate_width(text, width);
+    format!("{}{}", truncated, " ".repeat(width - truncated.width()))
+}
+
+/// Rows `text` takes when word-wrapped to `width` columns; overlong words are split
+pub fn wrapped_rows(text: &str, width: usize) -> usize {
+    let width = width.max(1);
+    let mut rows = 0;
+    let mut used = 0;
+    for word in text.split_whitespace() {
+        let word_width = word.width();
+        if used > 0 && used + 1 + word_width <= width {
+            used += 1 + word_width;
+        } else {
+            rows += 1;
+            used = word_width;
+            while used > width {
+                rows += 1;
+                used -= width;
+            }
+        }
+    }
+    rows
+}
+
+/// Word-wrap `text` to `width` columns as styled lines; each newline starts a new paragraph
+/// Words are never split, so one wider than `width` gets a row to itself
+pub fn wrap_text(text: &str, width: usize, style: Style) -> Vec<Line<'static>> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut row = String::new();
+        for word in paragraph.split_whitespace() {
+            if !row.is_empty() && row.width() + 1 + word.width() > width {
+                lines.push(Line::styled(std::mem::take(&mut row), style));
+            }
+            if !row.is_empty() {
+                row.push(' ');
+            }
+            row.push_str(word);
+        }
+        lines.push(Line::styled(row, style));
+    }
+    lines
+}
+
+/// Cut `text` at a word boundary so it plus `suffix` wraps to at most `max_rows` rows
+/// Returns None when the full text already fits
+pub fn collapse_text<'a>(text: &'a str, width: usize, max_rows: usize, suffix: &str) -> Option<&'a str> {
+    if wrapped_rows(text, width) <= max_rows {
+        return None;
+    }
+    let mut end = text.len();
+    while end > 0 {
+        end = text[..end].trim_end().rfind(char::is_whitespace).unwrap_or(0);
+        let prefix = text[..end].trim_end();
+        if wrapped_rows(&format!("{} {}", prefix, suffix), width) <= max_rows {
+            return Some(prefix);
+        }
+    }
+    Some("")
+}
+
+/// Gap between the end of a scrolling name and its next loop
+const MARQUEE_GAP: &str = "   ";
+
+/// One frame of a looping marquee: text wider than `width` scrolls left one grapheme per step
+/// Text that fits is just padded
+pub fn marquee(text: &str, width: usize, step: usize) -> String {
+    if text.width() <= width {
+        return fit_width(text, width);
+    }
+    let graphemes: Vec<&str> = text.graphemes(true).chain(MARQUEE_GAP.graphemes(true)).collect();
+    let start = step % graphemes.len();
+    let rotated: String = graphemes[start..].iter().chain(&graphemes[..start]).copied().collect();
+    fit_width(&rotated, width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_is_truncated_and_padded_by_columns() {
+        assert_eq!(fit_width("segfault", 4), "segf");
+        assert_eq!(fit_width("404", 6), "404   ");
+    }
+
+    #[test]
+    fn wide_characters_count_as_two_columns() {
+        assert_eq!(truncate_width("珈琲豆", 4), "珈琲");
+        // A wide char that doesn't fit is dropped and the gap padded
+        assert_eq!(fit_width("珈琲豆", 5), "珈琲 ");
+        assert_eq!(fit_width("珈琲豆", 5).width(), 5);
+    }
+
+    #[test]
+    fn marquee_scrolls_long_text_and_loops() {
+        assert_eq!(marquee("segfault", 4, 0), "segf");
+        assert_eq!(marquee("segfault", 4, 3), "faul");
+        assert_eq!(marquee("segfault", 4, 7), "t   ");
+        assert_eq!(marquee("segfault", 4, 11), "segf");
+        // Short text doesn't move
+        assert_eq!(marquee("404", 4, 2), "404 ");
+    }
+
+    #[test]
+    fn wrapped_rows_counts_word_wrapped_lines() {
+        assert_eq!(wrapped_rows("", 10), 0);
+        assert_eq!(wrapped_rows("rich dark roast", 15), 1);
+        assert_eq!(wrapped_rows("rich dark roast", 10), 2);
+        // A word wider than the line spills over
+        assert_eq!(wrapped_rows("supercalifragilistic", 8), 3);
+    }
+
+    #[test]
+    fn wrap_text_breaks_between_words_and_keeps_paragraphs() {
+        let text = "notes of cherry cocoa and toasted almond\n\nroasted to order in tashkent";
+        let rows: Vec<String> = wrap_text(text, 16, Style::default()).iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            rows,
+            ["notes of cherry", "cocoa and", "toasted almond", "", "roasted to order", "in tashkent"]
+        );
+        assert!(rows.iter().all(|row| row.width() <= 16));
+
+        // An overlong word stays whole on its own row
+        let rows: Vec<String> = wrap_text("a supercalifragilistic bean", 8, Style::default())
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(rows, ["a", "supercalifragilistic", "bean"]);
+    }
+
+    #[test]
+    fn collapse_text_keeps_whole_words_within_the_row_budget() {
+        let text = "notes of cherry cocoa and toasted almond with a long finish";
+        assert_eq!(collapse_text(text, 80, 2, "…more"), None);
+        let collapsed = collapse_text(text, 20, 2, "…more").unwrap();
+        assert_eq!(collapsed, "notes of cherry cocoa and");
+        assert!(wrapped_rows(&format!("{} …more", collapsed), 20) <= 2);
+    }
+
+    #[test]
+    fn combining_marks_stay_with_their_base() {
+        // "e" + combining acute is one column
+        let name = "cafe\u{301} noir";
+        assert_eq!(truncate_width(name, 4), "cafe\u{301}");
+        assert_eq!(fit_width(name, 6).width(), 6);
+    }
+}
@@ -1,9 +1,136 @@
-#![allow(dead_code)]
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use uuid::Uuid;
 
-use ratatui::style::Color;
+use crate::models::Product;
 
-/// ANORA Labs color theme
-pub struct Theme;
+/// ANORA Labs color theme: the palette, accent and color support the UI is drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub mode: ThemeMode,
+    pub accent: Color,
+    pub depth: ColorDepth,
+    /// NO_COLOR (https://no-color.org): draw without any color at all
+    pub no_color: bool,
+}
+
+/// Dark, pink and true color: what the UI is designed in, and what tests draw with
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            mode: ThemeMode::Dark,
+            accent: Self::PINK,
+            depth: ColorDepth::TrueColor,
+            no_color: false,
+        }
+    }
+}
+
+/// Terminal background the UI colors are tuned for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeMode {
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    /// ANORA_THEME ("dark" or "light"); dark when unset or unrecognized
+    fn from_env(value: Option<&str>) -> Self {
+        match value.map(|v| v.trim().to_lowercase()).as_deref() {
+            Some("light") => Self::Light,
+            _ => Self::Dark,
+        }
+    }
+
+    fn palette(&self) -> &'static Palette {
+        match self {
+            Self::Dark => &DARK,
+            Self::Light => &LIGHT,
+        }
+    }
+}
+
+/// UI colors (text and borders) for one theme mode
+struct Palette {
+    fg: Color,
+    dimmed: Color,
+    border: Color,
+}
+
+const DARK: Palette = Palette {
+    fg: Color::Rgb(255, 255, 255),           // White text
+    dimmed: Color::Rgb(128, 128, 128),       // Dimmed/gray text
+    border: Color::Rgb(64, 64, 64),          // Border color
+};
+
+const LIGHT: Palette = Palette {
+    fg: Color::Rgb(24, 24, 27),              // Near-black text
+    dimmed: Color::Rgb(100, 100, 108),       // Dimmed text, still readable on white
+    border: Color::Rgb(200, 200, 205),       // Border color
+};
+
+/// How many colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+impl ColorDepth {
+    /// ANORA_COLOR_DEPTH ("truecolor", "256" or "16") if set, else a guess from COLORTERM and TERM
+    fn detect() -> Self {
+        let var = |name| std::env::var(name).ok();
+        Self::from_env(var("ANORA_COLOR_DEPTH"), var("COLORTERM"), var("TERM"))
+    }
+
+    fn from_env(forced: Option<String>, colorterm: Option<String>, term: Option<String>) -> Self {
+        if let Some(depth) = forced.as_deref().and_then(Self::parse) {
+            return depth;
+        }
+        if colorterm.is_some_and(|c| c == "truecolor" || c == "24bit") {
+            Self::TrueColor
+        } else if term.is_some_and(|t| t.contains("256color")) {
+            Self::Ansi256
+        } else {
+            Self::Ansi16
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "truecolor" | "24bit" => Some(Self::TrueColor),
+            "256" => Some(Self::Ansi256),
+            "16" => Some(Self::Ansi16),
+            _ => None,
+        }
+    }
+}
+
+/// xterm's approximate RGB for the 16 ANSI colors
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel levels of the xterm 6x6x6 color cube
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
 
 impl Theme {
     // Brand colors
@@ -21,23 +148,265 @@ impl Theme {
     // --ramp-red-500-light: #f24822;
     pub const RED: Color = Color::Rgb(242, 72, 34);    // #f24822
 
-    // UI colors
-    pub const BG: Color = Color::Rgb(22, 22, 26);              // Dark background
-    pub const FG: Color = Color::Rgb(255, 255, 255);           // White text
-    pub const DIMMED: Color = Color::Rgb(128, 128, 128);       // Dimmed/gray text
-    pub const BORDER: Color = Color::Rgb(64, 64, 64);          // Border color
-    pub const HIGHLIGHT_BG: Color = Color::Rgb(45, 45, 50);    // Highlighted item background
+    // Glyphs (kept here so they're written once, in proper UTF-8)
+    pub const CURSOR: &'static str = "\u{2588}";               // █ full block
+    pub const DIVIDER: &'static str = "\u{2500}";              // ─ box drawings light horizontal
+    pub const RADIO: &'static str = "\u{25c9}";                // ◉ fisheye
+
+    /// Accent palette used for products without a backend-provided color
+    pub const PRODUCT_PALETTE: [Color; 5] = [
+        Self::PINK,
+        Self::YELLOW,
+        Self::BLUE,
+        Self::GREEN,
+        Self::PALE_PINK,
+    ];
+
+    /// Theme from the environment: ANORA_THEME, ANORA_ACCENT (a hex color), the detected
+    /// color depth and NO_COLOR
+    pub fn from_env() -> Self {
+        let var = |name| std::env::var(name).ok();
+        Self {
+            mode: ThemeMode::from_env(var("ANORA_THEME").as_deref()),
+            accent: Self::accent_from(var("ANORA_ACCENT").as_deref()),
+            depth: ColorDepth::detect(),
+            no_color: std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()),
+        }
+    }
+
+    /// Accent for prices, cursors and brand highlights
+    pub fn accent(&self) -> Color {
+        self.accent
+    }
+
+    pub fn fg(&self) -> Color {
+        self.mode.palette().fg
+    }
+
+    pub fn dimmed(&self) -> Color {
+        self.mode.palette().dimmed
+    }
+
+    pub fn border(&self) -> Color {
+        self.mode.palette().border
+    }
+
+    /// Rewrite a drawn frame's RGB colors for terminals without true color, so every
+    /// color downgrades the same way regardless of where it was set
+    pub fn adapt_buffer(&self, buffer: &mut Buffer) {
+        if self.no_color {
+            Self::strip_colors(buffer);
+            return;
+        }
+        let depth = self.depth;
+        if depth == ColorDepth::TrueColor {
+            return;
+        }
+        for cell in buffer.content.iter_mut() {
+            cell.fg = Self::downgrade(cell.fg, depth);
+            cell.bg = Self::downgrade(cell.bg, depth);
+        }
+    }
+
+    /// Monochrome frame: every color back to the terminal default, and whatever stood out
+    /// by its background (selections, badges) shown in reverse video instead
+    fn strip_colors(buffer: &mut Buffer) {
+        for cell in buffer.content.iter_mut() {
+            if cell.bg != Color::Reset {
+                cell.modifier.insert(Modifier::REVERSED);
+            }
+            cell.fg = Color::Reset;
+            cell.bg = Color::Reset;
+        }
+    }
+
+    /// Nearest color the given depth can show; non-RGB colors pass through
+    pub fn downgrade(color: Color, depth: ColorDepth) -> Color {
+        let Color::Rgb(r, g, b) = color else {
+            return color;
+        };
+        let distance = |(r2, g2, b2): (u8, u8, u8)| {
+            let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+            d(r, r2) + d(g, g2) + d(b, b2)
+        };
+        match depth {
+            ColorDepth::TrueColor => color,
+            ColorDepth::Ansi16 => ANSI_16
+                .iter()
+                .min_by_key(|(_, rgb)| distance(*rgb))
+                .map_or(color, |(ansi, _)| *ansi),
+            ColorDepth::Ansi256 => {
+                // Best of the 6x6x6 cube (16-231) and the grayscale ramp (232-255)
+                let level = |c: u8| {
+                    (0..6).min_by_key(|&i| (CUBE_LEVELS[i] as i32 - c as i32).abs()).unwrap_or(0)
+                };
+                let (ri, gi, bi) = (level(r), level(g), level(b));
+                let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+                let cube_index = 16 + 36 * ri + 6 * gi + bi;
+                let gray_step = ((r as usize + g as usize + b as usize) / 3).saturating_sub(3) / 10;
+                let gray_step = gray_step.min(23);
+                let gray_level = (8 + 10 * gray_step) as u8;
+                let gray = (gray_level, gray_level, gray_level);
+                if distance(gray) < distance(cube) {
+                    Color::Indexed(232 + gray_step as u8)
+                } else {
+                    Color::Indexed(cube_index as u8)
+                }
+            }
+        }
+    }
+
+    /// Accent for a configured value, falling back to pink when missing or malformed
+    fn accent_from(value: Option<&str>) -> Color {
+        value.and_then(Self::parse_hex).unwrap_or(Self::PINK)
+    }
+
+    /// Get highlight color for a product
+    /// Uses the product's `highlight_color` when it is a valid hex color,
+    /// then the brand color for known products, then a stable palette color from the id
+    pub fn product_color(product: &Product) -> Color {
+        Self::parse_hex(&product.highlight_color)
+            .or_else(|| Self::named_color(&product.name))
+            .unwrap_or_else(|| Self::palette_color(&product.id))
+    }
 
-    /// Get highlight color for a product by name
-    pub fn product_color(product_name: &str) -> Color {
+    /// Brand colors for the original lineup, by product name
+    fn named_color(product_name: &str) -> Option<Color> {
         match product_name.to_lowercase().as_str() {
-            "cron" => Self::PINK,
-            "[object object]" => Self::YELLOW,
-            "segfault" => Self::BLUE,
-            "dark mode" => Self::GREEN,
-            "404" => Self::PALE_PINK,
-            _ => Self::PINK,
+            "cron" => Some(Self::PINK),
+            "[object object]" => Some(Self::YELLOW),
+            "segfault" => Some(Self::BLUE),
+            "dark mode" => Some(Self::GREEN),
+            "404" => Some(Self::PALE_PINK),
+            _ => None,
         }
     }
+
+    /// Parse a `#rrggbb` (or `rrggbb`) hex string into an RGB color
+    pub fn parse_hex(hex: &str) -> Option<Color> {
+        let hex = hex.trim().trim_start_matches('#');
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    /// Deterministic palette color for an id (FNV-1a over the id bytes)
+    fn palette_color(id: &Uuid) -> Color {
+        let hash = id
+            .as_bytes()
+            .iter()
+            .fold(0x811c9dc5u32, |h, b| (h ^ *b as u32).wrapping_mul(0x01000193));
+        Self::PRODUCT_PALETTE[hash as usize % Self::PRODUCT_PALETTE.len()]
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyphs_are_single_chars() {
+        assert_eq!(Theme::CURSOR.chars().collect::<Vec<_>>(), ['█']);
+        assert_eq!(Theme::DIVIDER.chars().collect::<Vec<_>>(), ['─']);
+        assert_eq!(Theme::RADIO.chars().collect::<Vec<_>>(), ['◉']);
+    }
+
+    #[test]
+    fn parse_hex_accepts_rgb_with_or_without_hash() {
+        assert_eq!(Theme::parse_hex("#ff24bd"), Some(Color::Rgb(255, 36, 189)));
+        assert_eq!(Theme::parse_hex("00A2C2"), Some(Color::Rgb(0, 162, 194)));
+        assert_eq!(Theme::parse_hex("  #678e79 "), Some(Color::Rgb(103, 142, 121)));
+    }
+
+    #[test]
+    fn parse_hex_rejects_malformed_values() {
+        assert_eq!(Theme::parse_hex(""), None);
+        assert_eq!(Theme::parse_hex("#fff"), None);
+        assert_eq!(Theme::parse_hex("#gg24bd"), None);
+        assert_eq!(Theme::parse_hex("#ff24bd00"), None);
+        assert_eq!(Theme::parse_hex("#ff24é"), None);
+    }
+
+    #[test]
+    fn accent_falls_back_to_pink() {
+        assert_eq!(Theme::accent_from(Some("#00a2c2")), Color::Rgb(0, 162, 194));
+        assert_eq!(Theme::accent_from(Some("teal")), Theme::PINK);
+        assert_eq!(Theme::accent_from(None), Theme::PINK);
+    }
+
+    #[test]
+    fn theme_mode_defaults_to_dark() {
+        assert_eq!(ThemeMode::from_env(Some("Light")), ThemeMode::Light);
+        assert_eq!(ThemeMode::from_env(Some("solarized")), ThemeMode::Dark);
+        assert_eq!(ThemeMode::from_env(None), ThemeMode::Dark);
+        assert_eq!(Theme::default().fg(), Color::Rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn light_mode_draws_dark_text_in_the_chosen_accent() {
+        let theme = Theme { mode: ThemeMode::Light, accent: Theme::BLUE, ..Theme::default() };
+        assert_eq!(theme.fg(), Color::Rgb(24, 24, 27));
+        assert_eq!(theme.dimmed(), Color::Rgb(100, 100, 108));
+        assert_eq!(theme.accent(), Theme::BLUE);
+    }
+
+    #[test]
+    fn frames_adapt_to_the_theme_color_support() {
+        use ratatui::{layout::Rect, style::Style};
+        let frame = |theme: &Theme| {
+            let mut buffer = Buffer::empty(Rect::new(0, 0, 1, 1));
+            buffer.set_string(0, 0, "a", Style::default().fg(theme.fg()).bg(theme.accent()));
+            theme.adapt_buffer(&mut buffer);
+            (buffer[(0, 0)].fg, buffer[(0, 0)].bg)
+        };
+        let theme = Theme::default();
+        assert_eq!(frame(&theme), (Color::Rgb(255, 255, 255), Theme::PINK));
+        assert_eq!(frame(&Theme { depth: ColorDepth::Ansi256, ..theme }), (Color::Indexed(231), Color::Indexed(199)));
+        assert_eq!(frame(&Theme { depth: ColorDepth::Ansi16, ..theme }), (Color::White, Color::Magenta));
+        assert_eq!(frame(&Theme { no_color: true, ..theme }), (Color::Reset, Color::Reset));
+    }
+
+    #[test]
+    fn no_color_frames_mark_selections_in_reverse() {
+        use ratatui::{layout::Rect, style::Style};
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        let theme = Theme::default();
+        buffer.set_string(0, 0, "a", Style::default().fg(theme.dimmed()));
+        buffer.set_string(1, 0, "b", Style::default().fg(theme.fg()).bg(Theme::PINK));
+
+        Theme::strip_colors(&mut buffer);
+        assert_eq!((buffer[(0, 0)].fg, buffer[(0, 0)].bg), (Color::Reset, Color::Reset));
+        assert!(!buffer[(0, 0)].modifier.contains(Modifier::REVERSED));
+        assert_eq!((buffer[(1, 0)].fg, buffer[(1, 0)].bg), (Color::Reset, Color::Reset));
+        assert!(buffer[(1, 0)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn color_depth_can_be_forced_or_detected() {
+        let s = |v: &str| Some(v.to_string());
+        assert_eq!(ColorDepth::from_env(s("16"), s("truecolor"), None), ColorDepth::Ansi16);
+        assert_eq!(ColorDepth::from_env(None, s("24bit"), None), ColorDepth::TrueColor);
+        assert_eq!(ColorDepth::from_env(None, None, s("xterm-256color")), ColorDepth::Ansi256);
+        assert_eq!(ColorDepth::from_env(s("lots"), None, s("xterm")), ColorDepth::Ansi16);
+    }
+
+    #[test]
+    fn rgb_downgrades_to_the_nearest_ansi_color() {
+        let theme = Theme::default();
+        assert_eq!(Theme::downgrade(theme.fg(), ColorDepth::Ansi256), Color::Indexed(231));
+        assert_eq!(Theme::downgrade(theme.dimmed(), ColorDepth::Ansi256), Color::Indexed(244));
+        assert_eq!(Theme::downgrade(Theme::PINK, ColorDepth::Ansi256), Color::Indexed(199));
+        assert_eq!(Theme::downgrade(theme.fg(), ColorDepth::Ansi16), Color::White);
+        assert_eq!(Theme::downgrade(Theme::RED, ColorDepth::Ansi16), Color::LightRed);
+        assert_eq!(Theme::downgrade(Color::Reset, ColorDepth::Ansi16), Color::Reset);
+        assert_eq!(Theme::downgrade(Theme::PINK, ColorDepth::TrueColor), Theme::PINK);
+    }
+
+    #[test]
+    fn palette_color_is_stable_for_an_id() {
+        let id = Uuid::new_v4();
+        assert_eq!(Theme::palette_color(&id), Theme::palette_color(&id));
+    }
+}
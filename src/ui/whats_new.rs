@@ -0,0 +1,66 @@
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    Frame,
+};
+
+use super::{popup_area, render_overlay, Theme};
+use crate::i18n::t;
+
+/// Bundled changelog, one `## <version>` section per release
+const CHANGELOG: &str = include_str!("../../CHANGELOG.md");
+
+/// Changelog entries for a version (falls back to the latest section)
+pub fn changelog_entries(version: &str) -> Vec<&'static str> {
+    let sections: Vec<(&str, Vec<&'static str>)> = CHANGELOG
+        .split("\n## ")
+        .skip(1)
+        .map(|section| {
+            let mut lines = section.lines();
+            let heading = lines.next().unwrap_or_default().trim();
+            let entries = lines.filter_map(|l| l.trim().strip_prefix("- ")).collect();
+            (heading, entries)
+        })
+        .collect();
+
+    sections
+        .iter()
+        .find(|(heading, _)| *heading == version)
+        .or(sections.first())
+        .map(|(_, entries)| entries.clone())
+        .unwrap_or_default()
+}
+
+/// Lines listing the changes in the current version
+pub fn whats_new_lines(theme: &Theme) -> Vec<Line<'static>> {
+    let version = env!("CARGO_PKG_VERSION");
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("what's new in {}", version),
+            Style::default().fg(theme.fg()),
+        )),
+        Line::default(),
+    ];
+    lines.extend(changelog_entries(version).into_iter().map(|entry| {
+        Line::from(vec![
+            Span::styled("• ", Style::default().fg(theme.accent())),
+            Span::styled(entry, Style::default().fg(theme.dimmed())),
+        ])
+    }));
+    lines
+}
+
+/// Render the "what's new" overlay centered over the given area
+pub fn render_whats_new(f: &mut Frame, area: Rect, theme: &Theme) {
+    let popup = popup_area(area, 60, 16);
+
+    let mut lines = whats_new_lines(theme);
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled(
+        t("whats_new.continue"),
+        Style::default().fg(Theme::GREEN),
+    )));
+
+    render_overlay(f, popup, lines, 0, theme);
+}
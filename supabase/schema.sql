@@ -14,6 +14,7 @@ CREATE TABLE IF NOT EXISTS regions (
     flag TEXT NOT NULL DEFAULT '🌎',
     currency TEXT NOT NULL DEFAULT 'USD',
     free_shipping_threshold INTEGER NOT NULL DEFAULT 40,
+    locale TEXT,
     created_at TIMESTAMPTZ DEFAULT NOW(),
     updated_at TIMESTAMPTZ DEFAULT NOW()
 );
@@ -45,6 +46,13 @@ CREATE TABLE IF NOT EXISTS products (
     highlight_color TEXT NOT NULL DEFAULT '#ff24bd',
     region_id TEXT NOT NULL REFERENCES regions(id) ON DELETE CASCADE,
     in_stock BOOLEAN NOT NULL DEFAULT true,
+    subscription_discount_pct INTEGER NOT NULL DEFAULT 0,
+    price_tiers JSONB NOT NULL DEFAULT '[]'::jsonb,
+    translations JSONB NOT NULL DEFAULT '{}'::jsonb,
+    max_per_order INTEGER CHECK (max_per_order > 0),
+    stock_quantity INTEGER CHECK (stock_quantity >= 0),
+    rating REAL CHECK (rating BETWEEN 0 AND 5),
+    review_count INTEGER CHECK (review_count >= 0),
     created_at TIMESTAMPTZ DEFAULT NOW(),
     updated_at TIMESTAMPTZ DEFAULT NOW()
 );
@@ -154,6 +162,16 @@ CREATE TABLE IF NOT EXISTS orders (
     shipping_postal_code TEXT NOT NULL,
     shipping_phone TEXT,
     
+    -- Gift orders: billed to the purchaser, shipped to the address above
+    is_gift BOOLEAN NOT NULL DEFAULT FALSE,
+    purchaser_name TEXT,
+    purchaser_email TEXT,
+    gift_message TEXT,
+    
+    -- Tracking, set once the order ships
+    tracking_number TEXT,
+    carrier TEXT,
+    
     created_at TIMESTAMPTZ DEFAULT NOW(),
     updated_at TIMESTAMPTZ DEFAULT NOW()
 );